@@ -102,6 +102,14 @@ fn run<const DEGREE: usize>(
         .expect("failed to verify proof");
 
     let power = (DEGREE as f64).log2() as usize;
+    let description = format!("Compile 2^{} = {} gates", power, DEGREE);
+
+    c.bench_function(description.as_str(), |b| {
+        b.iter(|| {
+            black_box(Compiler::compile::<BenchCircuit<DEGREE>>(&pp, label))
+        })
+    });
+
     let description = format!("Prove 2^{} = {} gates", power, DEGREE);
 
     c.bench_function(description.as_str(), |b| {
@@ -137,9 +145,24 @@ fn constraint_system_benchmark(c: &mut Criterion) {
     run::<{ 1 << 17 }>(c, &pp, label);
 }
 
+fn setup_benchmark(c: &mut Criterion) {
+    [10, 12, 14, 16].into_iter().for_each(|power| {
+        let description = format!("Setup 2^{} = {} degree", power, 1 << power);
+
+        c.bench_function(description.as_str(), |b| {
+            b.iter(|| {
+                black_box(PublicParameters::setup(
+                    1 << power,
+                    &mut rand_core::OsRng,
+                ))
+            })
+        });
+    });
+}
+
 criterion_group! {
     name = plonk;
     config = Criterion::default().sample_size(10);
-    targets = constraint_system_benchmark
+    targets = setup_benchmark, constraint_system_benchmark
 }
 criterion_main!(plonk);
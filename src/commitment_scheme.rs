@@ -25,7 +25,7 @@ pub(crate) use kzg10::AggregateProof;
 pub(crate) use kzg10::{CommitKey, OpeningKey};
 
 #[cfg(feature = "alloc")]
-pub use kzg10::PublicParameters;
+pub use kzg10::{Accumulator, PublicParameters};
 
 #[cfg(all(feature = "alloc", feature = "rkyv-impl"))]
 pub use kzg10::{
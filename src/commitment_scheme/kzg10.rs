@@ -9,11 +9,13 @@
 cfg_if::cfg_if!(
 if #[cfg(feature = "alloc")]
 {
+    pub mod accumulator;
     pub mod key;
     pub mod srs;
 
     pub(crate) use proof::alloc::AggregateProof;
 
+    pub use accumulator::Accumulator;
     pub use key::{CommitKey, OpeningKey};
     pub use srs::PublicParameters;
 
@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Accumulator of the KZG pairing checks deferred by the verification of
+//! proofs.
+
+use dusk_bls12_381::{G1Affine, G1Projective};
+use dusk_bytes::{DeserializableSlice, Serializable};
+
+use crate::composer::Verifier;
+use crate::error::Error;
+use crate::transcript::{keccak256, Merlin, TranscriptExt, TranscriptProtocol};
+
+use super::{Commitment, OpeningKey};
+
+/// Pair of points `(W, C)` left to be checked with the pairing
+/// `e(W, beta_h) == e(C, h)` by the verification of one or more proofs.
+///
+/// An accumulator is created by [`Verifier::verify_deferred`], many of them
+/// can be folded into one with [`Self::fold`] and the pairing is finally
+/// checked with [`Self::finalize`]. Only accumulators of verifiers sharing
+/// the same opening key, meaning the same
+/// [`PublicParameters`](super::PublicParameters), can be folded, so an
+/// accumulator keeps the Keccak-256 digest of the opening key it is checked
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accumulator {
+    w: G1Affine,
+    c: G1Affine,
+    opening_key: [u8; 32],
+}
+
+impl Accumulator {
+    pub(crate) fn new(
+        w: G1Projective,
+        c: G1Projective,
+        opening_key: &OpeningKey,
+    ) -> Self {
+        Self {
+            w: w.into(),
+            c: c.into(),
+            opening_key: keccak256(&opening_key.to_bytes()),
+        }
+    }
+
+    /// Folds `other` into the accumulator, so the pairing check of the
+    /// result holds only if the pairing checks of both accumulators hold.
+    ///
+    /// The accumulators are combined with a challenge derived from both of
+    /// them, so folding is deterministic. Returns
+    /// [`Error::InconsistentOpeningKeys`] if the accumulators don't share the
+    /// same opening key.
+    pub fn fold(&self, other: &Self) -> Result<Self, Error> {
+        if self.opening_key != other.opening_key {
+            return Err(Error::InconsistentOpeningKeys);
+        }
+
+        let mut transcript = Merlin::new(b"dusk-plonk-accumulator");
+        transcript.append_commitment(b"w", &Commitment(self.w));
+        transcript.append_commitment(b"c", &Commitment(self.c));
+        transcript.append_commitment(b"w", &Commitment(other.w));
+        transcript.append_commitment(b"c", &Commitment(other.c));
+
+        let r = transcript.challenge_scalar(b"fold");
+
+        let w = self.w + other.w * r;
+        let c = self.c + other.c * r;
+
+        Ok(Self {
+            w: w.into(),
+            c: c.into(),
+            opening_key: self.opening_key,
+        })
+    }
+
+    /// Performs the deferred pairing check with the opening key of
+    /// `verifier`
+    ///
+    /// Returns [`Error::InconsistentOpeningKeys`] if the opening key of
+    /// `verifier` isn't the one of the accumulated proofs.
+    pub fn finalize<T: TranscriptProtocol>(
+        &self,
+        verifier: &Verifier<T>,
+    ) -> Result<(), Error> {
+        let opening_key = verifier.opening_key();
+        if keccak256(&opening_key.to_bytes()) != self.opening_key {
+            return Err(Error::InconsistentOpeningKeys);
+        }
+
+        opening_key
+            .pairing_check(&self.w.into(), &self.c.into())
+            .map_err(|_| Error::ProofVerificationError)
+    }
+}
+
+impl Serializable<{ 2 * G1Affine::SIZE + 32 }> for Accumulator {
+    type Error = dusk_bytes::Error;
+
+    #[allow(unused_must_use)]
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        use dusk_bytes::Write;
+
+        let mut buf = [0u8; Self::SIZE];
+        let mut writer = &mut buf[..];
+        writer.write(&self.w.to_bytes());
+        writer.write(&self.c.to_bytes());
+        writer.write(&self.opening_key);
+
+        buf
+    }
+
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
+        let mut buffer = &buf[..];
+        let w = G1Affine::from_reader(&mut buffer)?;
+        let c = G1Affine::from_reader(&mut buffer)?;
+        let mut opening_key = [0u8; 32];
+        opening_key.copy_from_slice(buffer);
+
+        Ok(Self { w, c, opening_key })
+    }
+}
@@ -9,15 +9,15 @@
 //! Opening keys.
 use super::{proof::Proof, Commitment};
 use crate::{
-    error::Error, fft::Polynomial, transcript::TranscriptProtocol, util,
+    error::Error, fft::Polynomial, msm::msm_variable_base,
+    transcript::TranscriptProtocol, util,
 };
 use alloc::vec::Vec;
-use dusk_bls12_381::{
-    multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
-    G2Affine, G2Prepared,
-};
+use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective, G2Affine, G2Prepared};
 use dusk_bytes::{DeserializableSlice, Serializable};
-use merlin::Transcript;
+
+#[cfg(feature = "std")]
+use rayon::prelude::*;
 
 #[cfg(feature = "rkyv-impl")]
 use bytecheck::CheckBytes;
@@ -152,24 +152,24 @@ impl CommitKey {
         }
     }
 
-    /// Checks whether the polynomial we are committing to:
-    /// - Has zero degree
-    /// - Has a degree which is more than the max supported degree
+    /// Checks whether the polynomial we are committing to has a degree which
+    /// is more than the max supported degree.
     ///
-    /// Returns an error if any of the above conditions are true.
+    /// Returns an error if it does.
     fn check_commit_degree_is_within_bounds(
         &self,
         poly_degree: usize,
     ) -> Result<(), Error> {
-        match (poly_degree == 0, poly_degree > self.max_degree()) {
-            (true, _) => Err(Error::PolynomialDegreeIsZero),
-            (false, true) => Err(Error::PolynomialDegreeTooLarge),
-            (false, false) => Ok(()),
+        match poly_degree > self.max_degree() {
+            true => Err(Error::PolynomialDegreeTooLarge),
+            false => Ok(()),
         }
     }
 
     /// Commits to a [`Polynomial`] returning the corresponding [`Commitment`].
     ///
+    /// Constant polynomials are accepted, since the selectors of a circuit
+    /// and the unblinded polynomials of a non-hiding proof may be constant.
     /// Returns an error if the polynomial's degree is more than the max degree
     /// of the commit key.
     pub(crate) fn commit(
@@ -186,15 +186,36 @@ impl CommitKey {
         )))
     }
 
+    /// Commits to each of the polynomials as [`Self::commit`] does,
+    /// concurrently when the `std` feature is enabled.
+    pub(crate) fn commit_each<const N: usize>(
+        &self,
+        polynomials: [&Polynomial; N],
+    ) -> Result<[Commitment; N], Error> {
+        #[cfg(not(feature = "std"))]
+        let polynomials = polynomials.iter();
+
+        #[cfg(feature = "std")]
+        let polynomials = polynomials.par_iter();
+
+        let commitments: Vec<_> = polynomials
+            .map(|polynomial| self.commit(polynomial))
+            .collect::<Result<_, _>>()?;
+
+        Ok(commitments
+            .try_into()
+            .expect("there is a commitment per polynomial"))
+    }
+
     /// Computes a single witness for multiple polynomials at the same point, by
     /// taking a random linear combination of the individual witnesses.
     /// We apply the same optimization mentioned in when computing each witness;
     /// removing f(z).
-    pub(crate) fn compute_aggregate_witness(
+    pub(crate) fn compute_aggregate_witness<T: TranscriptProtocol>(
         &self,
         polynomials: &[Polynomial],
         point: &BlsScalar,
-        transcript: &mut Transcript,
+        transcript: &mut T,
     ) -> Polynomial {
         let v_challenge = transcript.challenge_scalar(b"v_challenge");
         let powers = util::powers_of(&v_challenge, polynomials.len() - 1);
@@ -263,6 +284,14 @@ impl Serializable<{ G1Affine::SIZE + G2Affine::SIZE * 2 }> for OpeningKey {
     }
 }
 
+impl PartialEq for OpeningKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.g == other.g && self.h == other.h && self.beta_h == other.beta_h
+    }
+}
+
+impl Eq for OpeningKey {}
+
 impl OpeningKey {
     pub(crate) fn new(
         g: G1Affine,
@@ -282,12 +311,28 @@ impl OpeningKey {
 
     /// Checks whether a batch of polynomials evaluated at different points,
     /// returned their specified value.
-    pub(crate) fn batch_check(
+    #[cfg(test)]
+    pub(crate) fn batch_check<T: TranscriptProtocol>(
         &self,
         points: &[BlsScalar],
         proofs: &[Proof],
-        transcript: &mut Transcript,
+        transcript: &mut T,
     ) -> Result<(), Error> {
+        let (total_w, total_c) =
+            self.batch_accumulate(points, proofs, transcript);
+
+        self.pairing_check(&total_w, &total_c)
+    }
+
+    /// Folds a batch of polynomials evaluated at different points into the
+    /// pair of points `(W, C)` whose pairing check
+    /// `e(W, beta_h) == e(C, h)` is deferred to [`Self::pairing_check`].
+    pub(crate) fn batch_accumulate<T: TranscriptProtocol>(
+        &self,
+        points: &[BlsScalar],
+        proofs: &[Proof],
+        transcript: &mut T,
+    ) -> (G1Projective, G1Projective) {
         let mut total_c = G1Projective::identity();
         let mut total_w = G1Projective::identity();
 
@@ -311,6 +356,16 @@ impl OpeningKey {
         }
         total_c -= self.g * g_multiplier;
 
+        (total_w, total_c)
+    }
+
+    /// Checks the pairing `e(W, beta_h) == e(C, h)` of the points `(W, C)`
+    /// computed by [`Self::batch_accumulate`].
+    pub(crate) fn pairing_check(
+        &self,
+        total_w: &G1Projective,
+        total_c: &G1Projective,
+    ) -> Result<(), Error> {
         let affine_total_w = G1Affine::from(-total_w);
         let affine_total_c = G1Affine::from(total_c);
 
@@ -333,9 +388,9 @@ mod test {
     use super::*;
     use crate::commitment_scheme::{AggregateProof, PublicParameters};
     use crate::fft::Polynomial;
+    use crate::transcript::Merlin;
     use dusk_bls12_381::BlsScalar;
     use dusk_bytes::Serializable;
-    use merlin::Transcript;
     use rand_core::OsRng;
 
     // Checks that a polynomial `p` was evaluated at a point `z` and returned
@@ -383,7 +438,7 @@ mod test {
         polynomials: &[Polynomial],
         evaluations: Vec<BlsScalar>,
         point: &BlsScalar,
-        transcript: &mut Transcript,
+        transcript: &mut Merlin,
     ) -> Result<AggregateProof, Error> {
         // Commit to polynomials
         let mut polynomial_commitments = Vec::with_capacity(polynomials.len());
@@ -442,6 +497,20 @@ mod test {
         Ok(())
     }
     #[test]
+    fn test_constant_commit() -> Result<(), Error> {
+        let (ck, _) = setup_test(25)?;
+        let constant = BlsScalar::from(7);
+
+        let poly = Polynomial::from_coefficients_vec(vec![constant]);
+        let commitment = ck.commit(&poly)?;
+        let [each] = ck.commit_each([&poly])?;
+
+        let expected = G1Affine::from(ck.powers_of_g[0] * constant);
+        assert_eq!(commitment.0, expected);
+        assert_eq!(each.0, expected);
+        Ok(())
+    }
+    #[test]
     fn test_batch_verification() -> Result<(), Error> {
         let degree = 25;
         let (ck, vk) = setup_test(degree)?;
@@ -464,7 +533,7 @@ mod test {
         vk.batch_check(
             &[point_a, point_b],
             &[proof_a, proof_b],
-            &mut Transcript::new(b""),
+            &mut Merlin::new(b""),
         )
     }
     #[test]
@@ -490,14 +559,14 @@ mod test {
                 &[poly_a, poly_b, poly_c],
                 vec![poly_a_eval, poly_b_eval, poly_c_eval],
                 &point,
-                &mut Transcript::new(b"agg_flatten"),
+                &mut Merlin::new(b"agg_flatten"),
             )?
         };
 
         // Verifier's View
         let ok = {
             let flattened_proof =
-                aggregated_proof.flatten(&mut Transcript::new(b"agg_flatten"));
+                aggregated_proof.flatten(&mut Merlin::new(b"agg_flatten"));
             check(&opening_key, point, flattened_proof)
         };
 
@@ -532,7 +601,7 @@ mod test {
                 &[poly_a, poly_b, poly_c],
                 vec![poly_a_eval, poly_b_eval, poly_c_eval],
                 &point_a,
-                &mut Transcript::new(b"agg_batch"),
+                &mut Merlin::new(b"agg_batch"),
             )?;
 
             let single_proof =
@@ -543,7 +612,7 @@ mod test {
 
         // Verifier's View
 
-        let mut transcript = Transcript::new(b"agg_batch");
+        let mut transcript = Merlin::new(b"agg_batch");
         let flattened_proof = aggregated_proof.flatten(&mut transcript);
 
         opening_key.batch_check(
@@ -29,7 +29,6 @@ pub(crate) mod alloc {
     #[rustfmt::skip]
     use ::alloc::vec::Vec;
     use dusk_bls12_381::G1Projective;
-    use merlin::Transcript;
     #[cfg(feature = "std")]
     use rayon::prelude::*;
 
@@ -67,7 +66,10 @@ pub(crate) mod alloc {
         /// Flattens an `AggregateProof` into a `Proof`.
         /// The transcript must have the same view as the transcript that was
         /// used to aggregate the witness in the proving stage.
-        pub(crate) fn flatten(&self, transcript: &mut Transcript) -> Proof {
+        pub(crate) fn flatten<T: TranscriptProtocol>(
+            &self,
+            transcript: &mut T,
+        ) -> Proof {
             let v_challenge = transcript.challenge_scalar(b"v_challenge");
             let powers = powers_of(
                 &v_challenge,
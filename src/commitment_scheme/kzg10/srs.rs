@@ -7,7 +7,7 @@
 //! The Public Parameters can also be referred to as the Structured Reference
 //! String (SRS).
 use super::key::{CommitKey, OpeningKey};
-use crate::{error::Error, util};
+use crate::{error::Error, msm, util};
 use alloc::vec::Vec;
 use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective, G2Affine};
 use dusk_bytes::{DeserializableSlice, Serializable};
@@ -76,7 +76,7 @@ impl PublicParameters {
         // Powers of G1 that will be used to commit to a specified polynomial
         let g = util::random_g1_point(&mut rng);
         let powers_of_g: Vec<G1Projective> =
-            util::slow_multiscalar_mul_single_base(&powers_of_x, g);
+            msm::mul_fixed_base(&powers_of_x, g);
         assert_eq!(powers_of_g.len(), max_degree + 1);
 
         // Normalize all projective points
@@ -6,12 +6,13 @@
 
 //! PLONK turbo composer definitions
 
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::cmp;
 use core::ops::Index;
 
 use dusk_bls12_381::BlsScalar;
-use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
+use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR};
 
 use crate::bit_iterator::BitIterator8;
 use crate::constraint_system::ecc::WnafRound;
@@ -19,21 +20,216 @@ use crate::constraint_system::{
     Constraint, Selector, WiredWitness, Witness, WitnessPoint,
 };
 use crate::error::Error;
+use crate::hades;
+use crate::keccak;
+use crate::merkle;
 use crate::runtime::{Runtime, RuntimeEvent};
+use crate::sha256;
+use crate::signature::{self, WitnessSignature};
 
 mod arithmetization;
 mod builder;
 mod circuit;
 mod compiler;
+mod gate;
+mod mock;
+mod optimizer;
 mod prover;
+mod report;
 mod verifier;
 
 pub use arithmetization::Arithmetization;
 pub use builder::Builder;
 pub use circuit::Circuit;
 pub use compiler::Compiler;
+pub use gate::{CustomGate, CustomWidget, GateWires};
+pub use mock::{MockFailure, MockProver, Selectors, Wire};
 pub use prover::Prover;
-pub use verifier::Verifier;
+pub use report::{CallSite, CircuitReport};
+pub use verifier::{verify_batch, Verifier};
+
+pub use crate::proof_system::widget::GateType;
+
+// Append the witnesses and gates every circuit starts with
+fn initialize<C: Composer>(composer: &mut C) {
+    let zero = composer.append_witness(0);
+    let one = composer.append_witness(1);
+
+    composer.assert_equal_constant(zero, 0, None);
+    composer.assert_equal_constant(one, 1, None);
+
+    composer.append_dummy_gates();
+}
+
+// Append the gates evaluating `a < b` as described in
+// [`Composer::component_less_than`], without range checking `a` and `b`.
+fn less_than<C: Composer, const BITS: usize>(
+    composer: &mut C,
+    a: Witness,
+    b: Witness,
+) -> Witness {
+    // Static assertion
+    assert!(BITS < 253);
+
+    // `a - b + 2^BITS` fits `BITS + 1` bits, and its most significant bit
+    // is set if and only if `a >= b`
+    let constraint = Constraint::new()
+        .left(1)
+        .a(a)
+        .right(-BlsScalar::one())
+        .b(b)
+        .constant(BlsScalar::pow_of_2(BITS as u64));
+    let diff = composer.gate_add(constraint);
+
+    let mut msb = C::ZERO;
+    let acc = composer[diff]
+        .to_bits()
+        .iter()
+        .take(BITS + 1)
+        .enumerate()
+        .fold(C::ZERO, |acc, (i, bit)| {
+            msb = composer.append_witness(BlsScalar::from(*bit as u64));
+
+            composer.component_boolean(msb);
+
+            let constraint = Constraint::new()
+                .left(BlsScalar::pow_of_2(i as u64))
+                .right(1)
+                .a(msb)
+                .b(acc);
+
+            composer.gate_add(constraint)
+        });
+
+    composer.assert_equal(acc, diff);
+
+    let constraint =
+        Constraint::new().left(-BlsScalar::one()).a(msb).constant(1);
+
+    composer.gate_add(constraint)
+}
+
+// Append the gates of a logic component over the `num_bits` least significant
+// bits of `a` and `b`, as described in [`Composer::append_logic_component`].
+//
+// If `wired`, the last accumulators of the inputs are `a` and `b` themselves,
+// so the inputs are constrained to `num_bits` bits instead of being truncated.
+pub(crate) fn logic_component<C: Composer>(
+    composer: &mut C,
+    a: Witness,
+    b: Witness,
+    num_bits: usize,
+    is_component_xor: bool,
+    wired: bool,
+) -> Witness {
+    // the bits are iterated as chunks of two; hence, we require an even
+    // number
+    let num_bits = cmp::min(num_bits, 256);
+    let num_quads = num_bits >> 1;
+
+    let bls_four = BlsScalar::from(4u64);
+    let mut left_acc = BlsScalar::zero();
+    let mut right_acc = BlsScalar::zero();
+    let mut out_acc = BlsScalar::zero();
+
+    // skip bits outside of argument `num_bits`
+    let a_bit_iter = BitIterator8::new(composer[a].to_bytes());
+    let a_bits: Vec<_> = a_bit_iter.skip(256 - num_bits).collect();
+    let b_bit_iter = BitIterator8::new(composer[b].to_bytes());
+    let b_bits: Vec<_> = b_bit_iter.skip(256 - num_bits).collect();
+
+    //
+    // * +-----+-----+-----+-----+
+    // * |  A  |  B  |  C  |  D  |
+    // * +-----+-----+-----+-----+
+    // * | 0   | 0   | w1  | 0   |
+    // * | a1  | b1  | w2  | d1  |
+    // * | a2  | b2  | w3  | d2  |
+    // * |  :  |  :  |  :  |  :  |
+    // * | an  | bn  | 0   | dn  |
+    // * +-----+-----+-----+-----+
+    // `an`, `bn` and `dn` are accumulators: `an [& OR ^] bd = dn`
+    //
+    // each step will shift last computation two bits to the left and add
+    // current quad.
+    //
+    // `wn` product accumulators will safeguard the quotient polynomial.
+
+    let mut constraint = if is_component_xor {
+        Constraint::logic_xor(&Constraint::new())
+    } else {
+        Constraint::logic(&Constraint::new())
+    };
+
+    for i in 0..num_quads {
+        // commit every accumulator
+        let idx = i * 2;
+
+        let l = (a_bits[idx] as u8) << 1;
+        let r = a_bits[idx + 1] as u8;
+        let left_quad = l + r;
+        let left_quad_bls = BlsScalar::from(left_quad as u64);
+
+        let l = (b_bits[idx] as u8) << 1;
+        let r = b_bits[idx + 1] as u8;
+        let right_quad = l + r;
+        let right_quad_bls = BlsScalar::from(right_quad as u64);
+
+        let out_quad_bls = if is_component_xor {
+            left_quad ^ right_quad
+        } else {
+            left_quad & right_quad
+        } as u64;
+        let out_quad_bls = BlsScalar::from(out_quad_bls);
+
+        // `w` argument to safeguard the quotient polynomial
+        let prod_quad_bls = (left_quad * right_quad) as u64;
+        let prod_quad_bls = BlsScalar::from(prod_quad_bls);
+
+        // Now that we've computed this round results, we need to apply the
+        // logic transition constraint that will check that
+        //   a_{i+1} - (a_i << 2) < 4
+        //   b_{i+1} - (b_i << 2) < 4
+        //   d_{i+1} - (d_i << 2) < 4   with d_i = a_i [& OR ^] b_i
+        // Note that multiplying by four is the equivalent of shifting the
+        // bits two positions to the left.
+
+        left_acc = left_acc * bls_four + left_quad_bls;
+        right_acc = right_acc * bls_four + right_quad_bls;
+        out_acc = out_acc * bls_four + out_quad_bls;
+
+        // the last accumulators of wired inputs are the inputs themselves
+        let last = i + 1 == num_quads;
+        let wit_a = match wired && last {
+            true => a,
+            false => composer.append_witness(left_acc),
+        };
+        let wit_b = match wired && last {
+            true => b,
+            false => composer.append_witness(right_acc),
+        };
+        let wit_c = composer.append_witness(prod_quad_bls);
+        let wit_d = composer.append_witness(out_acc);
+
+        constraint = constraint.o(wit_c);
+
+        composer.append_custom_gate(constraint);
+
+        constraint = constraint.a(wit_a).b(wit_b).d(wit_d);
+    }
+
+    // pad last output with `0`
+    // | an  | bn  | 0   | dn  |
+    let a = constraint.witness(WiredWitness::A);
+    let b = constraint.witness(WiredWitness::B);
+    let d = constraint.witness(WiredWitness::D);
+
+    let constraint = Constraint::new().a(a).b(b).d(d);
+
+    composer.append_custom_gate(constraint);
+
+    d
+}
 
 /// Circuit builder tool
 pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
@@ -78,6 +274,29 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
     )]
     fn append_custom_gate_internal(&mut self, constraint: Constraint);
 
+    /// Register a lookup table into the circuit description.
+    #[deprecated(
+        since = "0.18.0",
+        note = "this function is meant for internal use. call `append_lookup_table` instead"
+    )]
+    fn append_lookup_table_internal(
+        &mut self,
+        table_id: BlsScalar,
+        table: &[[BlsScalar; 3]],
+    );
+
+    /// Append a new width-4 poly gate/constraint enabling the selector of
+    /// `widget`.
+    #[deprecated(
+        since = "0.18.0",
+        note = "this function is meant for internal use. call `append_widget` instead"
+    )]
+    fn append_widget_internal(
+        &mut self,
+        widget: CustomWidget,
+        constraint: Constraint,
+    ) -> Result<(), Error>;
+
     /// PLONK runtime controller
     fn runtime(&mut self) -> &mut Runtime;
 
@@ -86,13 +305,7 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         #[allow(deprecated)]
         let mut slf = Self::uninitialized();
 
-        let zero = slf.append_witness(0);
-        let one = slf.append_witness(1);
-
-        slf.assert_equal_constant(zero, 0, None);
-        slf.assert_equal_constant(one, 1, None);
-
-        slf.append_dummy_gates();
+        initialize(&mut slf);
 
         slf
     }
@@ -111,6 +324,63 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         witness
     }
 
+    /// Allocate a witness value into the composer under `name` and return its
+    /// index.
+    ///
+    /// The name is qualified by the current [namespace](Self::namespace) and
+    /// is reported by the [`MockProver`] when a constraint copying the
+    /// witness fails.
+    fn append_witness_named<W: Into<BlsScalar>>(
+        &mut self,
+        witness: W,
+        name: &str,
+    ) -> Witness {
+        let witness = self.append_witness(witness);
+
+        let name = String::from(name);
+        self.runtime()
+            .event(RuntimeEvent::WitnessNamed { w: witness, name });
+
+        witness
+    }
+
+    /// Run `f` in the namespace `name`, nested in the current namespace, and
+    /// return its output.
+    ///
+    /// The constraints appended by `f` and the witnesses it names are
+    /// reported under the path of the namespace, as in `hash/round_3`, by the
+    /// [`MockProver`].
+    fn namespace<F, T>(&mut self, name: &str, f: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let name = String::from(name);
+        self.runtime()
+            .event(RuntimeEvent::NamespaceEntered { name });
+
+        let output = f(self);
+
+        self.runtime().event(RuntimeEvent::NamespaceExited);
+
+        output
+    }
+
+    /// Run the gadget `name` in `f` and return its output.
+    ///
+    /// The constraints appended by `f` are delimited by the
+    /// [`GadgetEntered`](RuntimeEvent::GadgetEntered) and
+    /// [`GadgetExited`](RuntimeEvent::GadgetExited) events of the
+    /// [runtime](crate::runtime), as are the ones of the components of the
+    /// composer.
+    fn gadget<F, T>(&mut self, name: &'static str, f: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let _gadget = self.runtime().gadget(name);
+
+        f(self)
+    }
+
     /// Append a new width-4 poly gate/constraint.
     fn append_custom_gate(&mut self, constraint: Constraint) {
         self.runtime()
@@ -120,6 +390,42 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         self.append_custom_gate_internal(constraint)
     }
 
+    /// Append a gate of the [`CustomGate`] `G`, wiring the witnesses of
+    /// `constraint` to the gate.
+    ///
+    /// The selector of `G` is enabled on the appended row, so the constraint
+    /// of `G` must hold for the values of the witnesses of this row and the
+    /// next. The coefficients of `constraint` are kept, so the gate can be
+    /// combined with the other gates of the row.
+    ///
+    /// Returns [`Error::DuplicateCustomGateSelector`] if the
+    /// [`CustomGate::SELECTOR`] of `G` is the selector of another gate of the
+    /// circuit, or a label of the built-in gates.
+    fn append_widget<G: CustomGate>(
+        &mut self,
+        constraint: Constraint,
+    ) -> Result<(), Error> {
+        self.runtime()
+            .event(RuntimeEvent::ConstraintAppended { c: constraint });
+
+        #[allow(deprecated)]
+        self.append_widget_internal(CustomWidget::new::<G>(), constraint)
+    }
+
+    /// Register `table` under `table_id` so its rows can be queried by
+    /// [`Self::component_lookup`].
+    ///
+    /// The tables are part of the circuit description. Registering a table
+    /// with an id that was already used has no effect.
+    fn append_lookup_table<T: Into<BlsScalar>>(
+        &mut self,
+        table_id: T,
+        table: &[[BlsScalar; 3]],
+    ) {
+        #[allow(deprecated)]
+        self.append_lookup_table_internal(table_id.into(), table)
+    }
+
     /// Performs a logical AND or XOR op between the inputs provided for
     /// `num_bits = BIT_PAIRS * 2` bits (counting from the least significant).
     ///
@@ -137,105 +443,9 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         b: Witness,
         is_component_xor: bool,
     ) -> Witness {
-        // the bits are iterated as chunks of two; hence, we require an even
-        // number
-        let num_bits = cmp::min(BIT_PAIRS * 2, 256);
-        let num_quads = num_bits >> 1;
-
-        let bls_four = BlsScalar::from(4u64);
-        let mut left_acc = BlsScalar::zero();
-        let mut right_acc = BlsScalar::zero();
-        let mut out_acc = BlsScalar::zero();
-
-        // skip bits outside of argument `num_bits`
-        let a_bit_iter = BitIterator8::new(self[a].to_bytes());
-        let a_bits: Vec<_> = a_bit_iter.skip(256 - num_bits).collect();
-        let b_bit_iter = BitIterator8::new(self[b].to_bytes());
-        let b_bits: Vec<_> = b_bit_iter.skip(256 - num_bits).collect();
-
-        //
-        // * +-----+-----+-----+-----+
-        // * |  A  |  B  |  C  |  D  |
-        // * +-----+-----+-----+-----+
-        // * | 0   | 0   | w1  | 0   |
-        // * | a1  | b1  | w2  | d1  |
-        // * | a2  | b2  | w3  | d2  |
-        // * |  :  |  :  |  :  |  :  |
-        // * | an  | bn  | 0   | dn  |
-        // * +-----+-----+-----+-----+
-        // `an`, `bn` and `dn` are accumulators: `an [& OR ^] bd = dn`
-        //
-        // each step will shift last computation two bits to the left and add
-        // current quad.
-        //
-        // `wn` product accumulators will safeguard the quotient polynomial.
-
-        let mut constraint = if is_component_xor {
-            Constraint::logic_xor(&Constraint::new())
-        } else {
-            Constraint::logic(&Constraint::new())
-        };
-
-        for i in 0..num_quads {
-            // commit every accumulator
-            let idx = i * 2;
-
-            let l = (a_bits[idx] as u8) << 1;
-            let r = a_bits[idx + 1] as u8;
-            let left_quad = l + r;
-            let left_quad_bls = BlsScalar::from(left_quad as u64);
-
-            let l = (b_bits[idx] as u8) << 1;
-            let r = b_bits[idx + 1] as u8;
-            let right_quad = l + r;
-            let right_quad_bls = BlsScalar::from(right_quad as u64);
+        let _gadget = self.runtime().gadget("append_logic_component");
 
-            let out_quad_bls = if is_component_xor {
-                left_quad ^ right_quad
-            } else {
-                left_quad & right_quad
-            } as u64;
-            let out_quad_bls = BlsScalar::from(out_quad_bls);
-
-            // `w` argument to safeguard the quotient polynomial
-            let prod_quad_bls = (left_quad * right_quad) as u64;
-            let prod_quad_bls = BlsScalar::from(prod_quad_bls);
-
-            // Now that we've computed this round results, we need to apply the
-            // logic transition constraint that will check that
-            //   a_{i+1} - (a_i << 2) < 4
-            //   b_{i+1} - (b_i << 2) < 4
-            //   d_{i+1} - (d_i << 2) < 4   with d_i = a_i [& OR ^] b_i
-            // Note that multiplying by four is the equivalent of shifting the
-            // bits two positions to the left.
-
-            left_acc = left_acc * bls_four + left_quad_bls;
-            right_acc = right_acc * bls_four + right_quad_bls;
-            out_acc = out_acc * bls_four + out_quad_bls;
-
-            let wit_a = self.append_witness(left_acc);
-            let wit_b = self.append_witness(right_acc);
-            let wit_c = self.append_witness(prod_quad_bls);
-            let wit_d = self.append_witness(out_acc);
-
-            constraint = constraint.o(wit_c);
-
-            self.append_custom_gate(constraint);
-
-            constraint = constraint.a(wit_a).b(wit_b).d(wit_d);
-        }
-
-        // pad last output with `0`
-        // | an  | bn  | 0   | dn  |
-        let a = constraint.witness(WiredWitness::A);
-        let b = constraint.witness(WiredWitness::B);
-        let d = constraint.witness(WiredWitness::D);
-
-        let constraint = Constraint::new().a(a).b(b).d(d);
-
-        self.append_custom_gate(constraint);
-
-        d
+        logic_component(self, a, b, BIT_PAIRS * 2, is_component_xor, false)
     }
 
     /// Evaluate `jubjub · Generator` as a [`WitnessPoint`]
@@ -249,6 +459,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         jubjub: Witness,
         generator: P,
     ) -> Result<WitnessPoint, Error> {
+        let _gadget = self.runtime().gadget("component_mul_generator");
+
         let generator = generator.into();
 
         // the number of bits is truncated to the maximum possible. however, we
@@ -624,6 +836,28 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         self.append_gate(constraint);
     }
 
+    /// Asserts `a != b` by appending the inverse of `a - b` as witness.
+    ///
+    /// Will error with an `UnsatisfiableGadget` error if `a == b`, since their
+    /// difference has no inverse. The gates are appended regardless.
+    fn assert_not_equal(
+        &mut self,
+        a: Witness,
+        b: Witness,
+    ) -> Result<(), Error> {
+        let constraint =
+            Constraint::new().left(1).right(-BlsScalar::one()).a(a).b(b);
+        let diff = self.gate_add(constraint);
+
+        self.component_inverse(diff).map_err(|_| {
+            Error::UnsatisfiableGadget {
+                gadget: "assert_not_equal",
+            }
+        })?;
+
+        Ok(())
+    }
+
     /// Asserts that the coordinates of the two points `a` and `b` are the same
     /// by appending two gates
     fn assert_equal_point(&mut self, a: WitnessPoint, b: WitnessPoint) {
@@ -660,6 +894,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         a: WitnessPoint,
         b: WitnessPoint,
     ) -> WitnessPoint {
+        let _gadget = self.runtime().gadget("component_add_point");
+
         // In order to verify that two points were correctly added
         // without going over a degree 4 polynomial, we will need
         // x_1, y_1, x_2, y_2
@@ -705,6 +941,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
     /// is not representing a value equalling 0 or 1, will always force the
     /// equation to fail.
     fn component_boolean(&mut self, a: Witness) {
+        let _gadget = self.runtime().gadget("component_boolean");
+
         let zero = Self::ZERO;
         let constraint = Constraint::new()
             .mult(1)
@@ -717,6 +955,40 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         self.append_gate(constraint);
     }
 
+    /// Evaluates and returns `a / b` by appending the quotient as witness.
+    ///
+    /// Will error with an `UnsatisfiableGadget` error if `b` is zero. The
+    /// gates are appended regardless.
+    ///
+    /// Consumes 2 gates
+    fn component_div(
+        &mut self,
+        a: Witness,
+        b: Witness,
+    ) -> Result<Witness, Error> {
+        let _gadget = self.runtime().gadget("component_div");
+
+        // constraining `b` to be invertible prevents any quotient from
+        // satisfying `q · 0 = 0`
+        let inverse = self.component_inverse(b);
+        let quotient = inverse.map(|inverse| self[a] * self[inverse]);
+        let quotient = self.append_witness(quotient.unwrap_or_default());
+
+        let constraint = Constraint::new()
+            .mult(1)
+            .fourth(-BlsScalar::one())
+            .a(quotient)
+            .b(b)
+            .d(a);
+        self.append_gate(constraint);
+
+        inverse
+            .map(|_| quotient)
+            .map_err(|_| Error::UnsatisfiableGadget {
+                gadget: "component_div",
+            })
+    }
+
     /// Decomposes `scalar` into an array truncated to `N` bits (max 256) in
     /// little endian.
     /// The `scalar` for 4, for example, would be deconstructed into the array
@@ -731,6 +1003,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         &mut self,
         scalar: Witness,
     ) -> [Witness; N] {
+        let _gadget = self.runtime().gadget("component_decomposition");
+
         // Static assertion
         assert!(0 < N && N <= 256);
 
@@ -761,6 +1035,368 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         decomposition
     }
 
+    /// Decomposes `scalar` into an array of `N` limbs of `limb_bits` bits each
+    /// in little endian.
+    ///
+    /// Every limb is constrained via [`Self::component_range_bits`], so
+    /// `limb_bits` doesn't need to be even. The scalar for `0x0102`, for
+    /// example, would be decomposed into the array `[0x02, 0x01, 0x00]` for
+    /// `N = 3` and `limb_bits = 8`.
+    ///
+    /// Asserts the reconstruction of the limbs to be equal to `scalar`, so a
+    /// `scalar` that doesn't fit `N · limb_bits` bits results in an
+    /// unsatisfied circuit.
+    ///
+    /// Consumes the gates of `N` range checks of `limb_bits` bits, plus
+    /// `N + 1` gates
+    fn component_decomposition_limbs<const N: usize>(
+        &mut self,
+        scalar: Witness,
+        limb_bits: usize,
+    ) -> [Witness; N] {
+        let _gadget = self.runtime().gadget("component_decomposition_limbs");
+
+        // Static assertion
+        assert!(0 < N && 0 < limb_bits && N * limb_bits <= 256);
+
+        let bits = self[scalar].to_bits();
+        let mut decomposition = [Self::ZERO; N];
+
+        let acc = Self::ZERO;
+        let acc = bits
+            .chunks(limb_bits)
+            .enumerate()
+            .zip(decomposition.iter_mut())
+            .fold(acc, |acc, ((i, limb), w_limb)| {
+                let limb = limb.iter().rev().fold(BlsScalar::zero(), |l, b| {
+                    l.double() + BlsScalar::from(*b as u64)
+                });
+                *w_limb = self.append_witness(limb);
+
+                self.component_range_bits(*w_limb, limb_bits);
+
+                let constraint = Constraint::new()
+                    .left(BlsScalar::pow_of_2((i * limb_bits) as u64))
+                    .right(1)
+                    .a(*w_limb)
+                    .b(acc);
+
+                self.gate_add(constraint)
+            });
+
+        self.assert_equal(acc, scalar);
+
+        decomposition
+    }
+
+    /// Applies the Hades252 permutation to `state` and returns the permuted
+    /// state.
+    ///
+    /// The result is consistent with [`hades::permutation`].
+    ///
+    /// Consumes `25 · FULL_ROUNDS + 13 · PARTIAL_ROUNDS` gates
+    fn component_hades_permutation(
+        &mut self,
+        state: &[Witness; hades::WIDTH],
+    ) -> [Witness; hades::WIDTH] {
+        let _gadget = self.runtime().gadget("component_hades_permutation");
+
+        let constants = hades::constants();
+        let mds = hades::mds();
+
+        let mut constants = constants.iter();
+        let mut state = *state;
+
+        (0..hades::FULL_ROUNDS + hades::PARTIAL_ROUNDS).for_each(|round| {
+            // the round constants are carried along with the state until they
+            // can be folded into the constant selector of a gate
+            let mut words = [(Self::ZERO, BlsScalar::zero()); hades::WIDTH];
+            words
+                .iter_mut()
+                .zip(state.iter().zip(&mut constants))
+                .for_each(|(word, (s, c))| *word = (*s, *c));
+
+            let s_boxes = if hades::is_full_round(round) {
+                0..hades::WIDTH
+            } else {
+                hades::WIDTH - 1..hades::WIDTH
+            };
+
+            // (w + k)^5
+            words[s_boxes].iter_mut().for_each(|(w, k)| {
+                let constraint = Constraint::new()
+                    .mult(1)
+                    .left(k.double())
+                    .constant(k.square())
+                    .a(*w)
+                    .b(*w);
+                let square = self.gate_mul(constraint);
+
+                let constraint = Constraint::new().mult(1).a(square).b(square);
+                let quartic = self.gate_mul(constraint);
+
+                let constraint =
+                    Constraint::new().mult(1).left(*k).a(quartic).b(*w);
+
+                *w = self.gate_mul(constraint);
+                *k = BlsScalar::zero();
+            });
+
+            state.iter_mut().zip(mds.iter()).for_each(|(s, row)| {
+                let k: BlsScalar =
+                    row.iter().zip(words.iter()).map(|(m, (_, k))| m * k).sum();
+
+                let constraint = Constraint::new()
+                    .left(row[0])
+                    .a(words[0].0)
+                    .right(row[1])
+                    .b(words[1].0)
+                    .fourth(row[2])
+                    .d(words[2].0)
+                    .constant(k);
+                let acc = self.gate_add(constraint);
+
+                let constraint = Constraint::new()
+                    .left(row[3])
+                    .a(words[3].0)
+                    .right(row[4])
+                    .b(words[4].0)
+                    .fourth(1)
+                    .d(acc);
+
+                *s = self.gate_add(constraint);
+            });
+        });
+
+        state
+    }
+
+    /// Evaluates and returns the inverse of `a` by appending it as witness.
+    ///
+    /// Will error with an `UnsatisfiableGadget` error if `a` is zero. The
+    /// gates are appended regardless, with a zero inverse, so the circuit
+    /// description doesn't depend on the witness.
+    ///
+    /// Consumes 1 gate
+    fn component_inverse(&mut self, a: Witness) -> Result<Witness, Error> {
+        let _gadget = self.runtime().gadget("component_inverse");
+
+        let inverse: Option<BlsScalar> = self[a].invert().into();
+        let witness = self.append_witness(inverse.unwrap_or_default());
+
+        let constraint = Constraint::new()
+            .mult(1)
+            .constant(-BlsScalar::one())
+            .a(a)
+            .b(witness);
+        self.append_gate(constraint);
+
+        inverse.map(|_| witness).ok_or(Error::UnsatisfiableGadget {
+            gadget: "component_inverse",
+        })
+    }
+
+    /// Evaluates `lower <= x <= upper` as a boolean [`Witness`].
+    ///
+    /// `x`, `lower` and `upper` are constrained to `BITS` bits via
+    /// [`Self::component_range_bits`]. Constant bounds can be appended with
+    /// [`Self::append_constant`].
+    ///
+    /// Consumes `4 · BITS + 11` gates, plus the gates of the three range
+    /// checks
+    fn component_in_range<const BITS: usize>(
+        &mut self,
+        x: Witness,
+        lower: Witness,
+        upper: Witness,
+    ) -> Witness {
+        let _gadget = self.runtime().gadget("component_in_range");
+
+        self.component_range_bits(x, BITS);
+        self.component_range_bits(lower, BITS);
+        self.component_range_bits(upper, BITS);
+
+        // `lower <= x <= upper` is `!(x < lower) && !(upper < x)`
+        let below = less_than::<Self, BITS>(self, x, lower);
+        let above = less_than::<Self, BITS>(self, upper, x);
+
+        let constraint = Constraint::new()
+            .mult(1)
+            .left(-BlsScalar::one())
+            .right(-BlsScalar::one())
+            .constant(1)
+            .a(below)
+            .b(above);
+
+        self.gate_add(constraint)
+    }
+
+    /// Evaluates `a == b` as a boolean [`Witness`].
+    ///
+    /// Consumes 3 gates
+    fn component_is_equal(&mut self, a: Witness, b: Witness) -> Witness {
+        let _gadget = self.runtime().gadget("component_is_equal");
+
+        let constraint =
+            Constraint::new().left(1).right(-BlsScalar::one()).a(a).b(b);
+        let diff = self.gate_add(constraint);
+
+        self.component_is_zero(diff)
+    }
+
+    /// Evaluates `a == 0` as a boolean [`Witness`].
+    ///
+    /// The inverse of `a`, or zero if there is none, is appended as witness
+    /// `inv` so the result is constrained by `out = 1 - a · inv` and
+    /// `a · out = 0`.
+    ///
+    /// Consumes 2 gates
+    fn component_is_zero(&mut self, a: Witness) -> Witness {
+        let _gadget = self.runtime().gadget("component_is_zero");
+
+        let inverse = self[a].invert();
+        let inverse = self.append_witness(inverse.unwrap_or_default());
+
+        let constraint = Constraint::new()
+            .mult(-BlsScalar::one())
+            .constant(1)
+            .a(a)
+            .b(inverse);
+        let out = self.gate_mul(constraint);
+
+        let constraint = Constraint::new().mult(1).a(a).b(out);
+        self.append_gate(constraint);
+
+        out
+    }
+
+    /// Applies the Keccak-f\[1600\] permutation to a `state` of 64 bit lanes
+    /// and returns the permuted state.
+    ///
+    /// The lanes of `state` are constrained to 64 bits. The result is
+    /// consistent with [`keccak::permutation`].
+    ///
+    /// Consumes 92520 gates
+    fn component_keccak_f1600(
+        &mut self,
+        state: &[Witness; keccak::LANES],
+    ) -> [Witness; keccak::LANES] {
+        let _gadget = self.runtime().gadget("component_keccak_f1600");
+
+        keccak::keccak_f1600_gadget(self, state)
+    }
+
+    /// Computes the Keccak-256 digest of a `message` of little endian 64 bit
+    /// lanes and returns the digest as little endian lanes.
+    ///
+    /// The multi-rate padding fills whole lanes: the message is followed by
+    /// the lane `0x01`, the zero lanes and the lane `0x80 << 56`, the first
+    /// and last padding lanes being merged when a single lane is missing from
+    /// the last block. The padding lanes are appended as constants, since
+    /// they only depend on the count of lanes. The lanes of `message` are
+    /// constrained to 64 bits. The result is consistent with
+    /// [`keccak::digest`] for messages with a length multiple of 8 bytes.
+    ///
+    /// Consumes 93081 gates per block of [`keccak::RATE_LANES`] lanes, plus
+    /// up to 2 gates for the padding
+    fn component_keccak256(
+        &mut self,
+        message: &[Witness],
+    ) -> [Witness; keccak::DIGEST_LANES] {
+        let _gadget = self.runtime().gadget("component_keccak256");
+
+        keccak::keccak256_gadget(self, message)
+    }
+
+    /// Evaluates `a <= b` as a boolean [`Witness`].
+    ///
+    /// `a` and `b` are constrained to `BITS` bits via
+    /// [`Self::component_range_bits`].
+    ///
+    /// Consumes `2 · BITS + 6` gates, plus the gates of the two range checks
+    fn component_less_or_equal<const BITS: usize>(
+        &mut self,
+        a: Witness,
+        b: Witness,
+    ) -> Witness {
+        let _gadget = self.runtime().gadget("component_less_or_equal");
+
+        self.component_range_bits(a, BITS);
+        self.component_range_bits(b, BITS);
+
+        let greater = less_than::<Self, BITS>(self, b, a);
+
+        let constraint = Constraint::new()
+            .left(-BlsScalar::one())
+            .a(greater)
+            .constant(1);
+
+        self.gate_add(constraint)
+    }
+
+    /// Evaluates `a < b` as a boolean [`Witness`].
+    ///
+    /// `a` and `b` are constrained to `BITS` bits via
+    /// [`Self::component_range_bits`], so the comparison can't wrap around
+    /// the modulus. `BITS` doesn't need to be even, but must be smaller than
+    /// 253.
+    ///
+    /// Consumes `2 · BITS + 5` gates, plus the gates of the two range checks
+    fn component_less_than<const BITS: usize>(
+        &mut self,
+        a: Witness,
+        b: Witness,
+    ) -> Witness {
+        let _gadget = self.runtime().gadget("component_less_than");
+
+        self.component_range_bits(a, BITS);
+        self.component_range_bits(b, BITS);
+
+        less_than::<Self, BITS>(self, a, b)
+    }
+
+    /// Asserts `(a, b, c)` is a row of the lookup table registered under
+    /// `table_id` via [`Self::append_lookup_table`].
+    ///
+    /// The table id is appended to the circuit description as the constant
+    /// selector of the gate.
+    ///
+    /// Consumes 1 gate
+    fn component_lookup<T: Into<BlsScalar>>(
+        &mut self,
+        table_id: T,
+        a: Witness,
+        b: Witness,
+        c: Witness,
+    ) {
+        let _gadget = self.runtime().gadget("component_lookup");
+
+        let constraint = Constraint::new().constant(table_id).a(a).b(b).o(c);
+        let constraint = Constraint::lookup(&constraint);
+
+        self.append_custom_gate(constraint);
+    }
+
+    /// Computes the root of a Merkle tree of arity `A` from a `leaf` and the
+    /// `path` of its opening, as defined in the [`merkle`] module.
+    ///
+    /// For every level of the path, the bits of the position are constrained
+    /// to be boolean, the node computed so far is placed among the siblings
+    /// at the position, and the children are hashed into the node of the
+    /// next level.
+    ///
+    /// The result is consistent with [`merkle::Opening::root`]. Only the
+    /// arities 2 and 4 are supported.
+    fn component_merkle_opening<const A: usize>(
+        &mut self,
+        leaf: Witness,
+        path: &[merkle::WitnessLevel<A>],
+    ) -> Witness {
+        let _gadget = self.runtime().gadget("component_merkle_opening");
+
+        merkle::opening_gadget(self, leaf, path)
+    }
+
     /// Conditionally selects identity as [`WitnessPoint`] based on an input
     /// bit.
     ///
@@ -774,6 +1410,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         bit: Witness,
         a: WitnessPoint,
     ) -> WitnessPoint {
+        let _gadget = self.runtime().gadget("component_select_identity");
+
         let x = self.component_select_zero(bit, *a.x());
         let y = self.component_select_one(bit, *a.y());
 
@@ -786,6 +1424,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         jubjub: Witness,
         point: WitnessPoint,
     ) -> WitnessPoint {
+        let _gadget = self.runtime().gadget("component_mul_point");
+
         // Turn scalar into bits
         let scalar_bits = self.component_decomposition::<252>(jubjub);
 
@@ -814,6 +1454,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         a: Witness,
         b: Witness,
     ) -> Witness {
+        let _gadget = self.runtime().gadget("component_select");
+
         // bit * a
         let constraint = Constraint::new().mult(1).a(bit).b(a);
         let bit_times_a = self.gate_mul(constraint);
@@ -848,6 +1490,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         bit: Witness,
         value: Witness,
     ) -> Witness {
+        let _gadget = self.runtime().gadget("component_select_one");
+
         let b = self[bit];
         let v = self[value];
 
@@ -881,6 +1525,8 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         a: WitnessPoint,
         b: WitnessPoint,
     ) -> WitnessPoint {
+        let _gadget = self.runtime().gadget("component_select_point");
+
         let x = self.component_select(bit, *a.x(), *b.x());
         let y = self.component_select(bit, *a.y(), *b.y());
 
@@ -899,11 +1545,107 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         bit: Witness,
         value: Witness,
     ) -> Witness {
+        let _gadget = self.runtime().gadget("component_select_zero");
+
         let constraint = Constraint::new().mult(1).a(bit).b(value);
 
         self.gate_mul(constraint)
     }
 
+    /// Applies the SHA-256 compression function to `state` with a single
+    /// message `block` of big endian words and returns the resulting state.
+    ///
+    /// The words of `state` and `block` are constrained to 32 bits. The result
+    /// is consistent with [`sha256::compress`].
+    ///
+    /// Consumes 23080 gates
+    fn component_sha256(
+        &mut self,
+        state: &[Witness; 8],
+        block: &[Witness; sha256::BLOCK_WORDS],
+    ) -> [Witness; 8] {
+        let _gadget = self.runtime().gadget("component_sha256");
+
+        sha256::compress_gadget(self, state, block)
+    }
+
+    /// Pads a `message` of big endian 32 bit words into blocks to be
+    /// compressed with [`Self::component_sha256`].
+    ///
+    /// The message is followed by the word `0x80000000`, the zero words
+    /// filling the last block up to its last two words and the length of the
+    /// message in bits as two words, which may take a block of their own.
+    /// The padding words are appended as constants, since they only depend
+    /// on the count of words. The result is consistent with [`sha256::pad`]
+    /// for messages with a length multiple of 4 bytes.
+    fn component_sha256_pad(
+        &mut self,
+        message: &[Witness],
+    ) -> Vec<[Witness; sha256::BLOCK_WORDS]> {
+        let _gadget = self.runtime().gadget("component_sha256_pad");
+
+        sha256::pad_gadget(self, message)
+    }
+
+    /// Verifies an EdDSA `signature` of `message` for `public_key`, as
+    /// defined in the [`signature`] module.
+    ///
+    /// Asserts `s · G == R + c · PK`, where the challenge
+    /// `c = H(R, PK, message)` is computed in the circuit.
+    ///
+    /// Will error with a `JubJubScalarMalformed` error if the scalar of the
+    /// signature doesn't fit `Fr`
+    fn component_verify_eddsa(
+        &mut self,
+        public_key: WitnessPoint,
+        message: &[Witness],
+        signature: WitnessSignature,
+    ) -> Result<(), Error> {
+        let _gadget = self.runtime().gadget("component_verify_eddsa");
+
+        let c = signature::challenge_gadget(
+            self,
+            signature.r(),
+            Some(&public_key),
+            message,
+        );
+
+        let s_g = self.component_mul_generator(*signature.s(), GENERATOR)?;
+        let c_pk = self.component_mul_point(c, public_key);
+        let r_c_pk = self.component_add_point(*signature.r(), c_pk);
+
+        self.assert_equal_point(s_g, r_c_pk);
+
+        Ok(())
+    }
+
+    /// Verifies a Schnorr `signature` of `message` for `public_key`, as
+    /// defined in the [`signature`] module.
+    ///
+    /// Asserts `u · G + c · PK == R`, where the challenge `c = H(R, message)`
+    /// is computed in the circuit.
+    ///
+    /// Will error with a `JubJubScalarMalformed` error if the scalar of the
+    /// signature doesn't fit `Fr`
+    fn component_verify_schnorr(
+        &mut self,
+        public_key: WitnessPoint,
+        message: &[Witness],
+        signature: WitnessSignature,
+    ) -> Result<(), Error> {
+        let _gadget = self.runtime().gadget("component_verify_schnorr");
+
+        let c = signature::challenge_gadget(self, signature.r(), None, message);
+
+        let u_g = self.component_mul_generator(*signature.s(), GENERATOR)?;
+        let c_pk = self.component_mul_point(c, public_key);
+        let r = self.component_add_point(u_g, c_pk);
+
+        self.assert_equal_point(r, *signature.r());
+
+        Ok(())
+    }
+
     /// Adds a range-constraint gate that checks and constrains a [`Witness`]
     /// to be encoded in at most `num_bits = BIT_PAIRS * 2` bits, which means
     /// that the underlying [`BlsScalar`] of the [`Witness`] will be within the
@@ -914,15 +1656,44 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
     /// and 7 gates, when num_bits = 0
     /// to the circuit description.
     fn component_range<const BIT_PAIRS: usize>(&mut self, witness: Witness) {
-        // the bits are iterated as chunks of two; hence, we require an even
-        // number
-        let num_bits = cmp::min(BIT_PAIRS * 2, 256);
+        let _gadget = self.runtime().gadget("component_range");
+
+        self.component_range_bits(witness, BIT_PAIRS * 2);
+    }
+
+    /// Constrains a [`Witness`] to be encoded in at most `num_bits` bits,
+    /// which means that the underlying [`BlsScalar`] of the [`Witness`] will
+    /// be within the range `[0, 2^num_bits[`.
+    ///
+    /// Unlike [`Self::component_range`], the width is defined at runtime and
+    /// doesn't need to be even: an odd width is checked as the next even one
+    /// with its most significant quad constrained to be boolean. A width over
+    /// 256 bits is checked as 256 bits, which every [`BlsScalar`] fits in.
+    ///
+    /// Returns the base-4 accumulators of the range gates, starting from the
+    /// most significant quad; the last accumulator is equal to `witness`.
+    ///
+    /// Consumes the gates of [`Self::component_range`] for the width rounded
+    /// up to an even number, plus one gate if `num_bits` is odd and below
+    /// 256.
+    fn component_range_bits(
+        &mut self,
+        witness: Witness,
+        num_bits: usize,
+    ) -> Vec<Witness> {
+        let _gadget = self.runtime().gadget("component_range_bits");
+
+        // the bits are iterated as chunks of two; hence, an odd number is
+        // padded with a most significant bit that must be zero, unless the
+        // width is clamped to the 256 bits of a scalar
+        let odd = num_bits % 2 == 1 && num_bits < 256;
+        let num_bits = cmp::min(num_bits + odd as usize, 256);
 
         // if num_bits = 0 constrain witness to 0
         if num_bits == 0 {
             let constraint = Constraint::new().left(1).a(witness);
             self.append_gate(constraint);
-            return;
+            return Vec::new();
         }
 
         // convert witness to bit representation and reverse
@@ -1015,6 +1786,16 @@ pub trait Composer: Sized + Index<Witness, Output = BlsScalar> {
         if let Some(accumulator) = accumulators.last() {
             self.assert_equal(*accumulator, witness);
         }
+
+        // the most significant quad holds the padding bit and the most
+        // significant bit of an odd width
+        if odd {
+            if let Some(accumulator) = accumulators.first() {
+                self.component_boolean(*accumulator);
+            }
+        }
+
+        accumulators
     }
 
     /// Evaluate and return `o` by appending a new constraint into the circuit.
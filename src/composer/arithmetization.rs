@@ -5,8 +5,18 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::EDWARDS_D;
 
-use crate::constraint_system::Witness;
+use crate::constraint_system::{Constraint, Selector, WiredWitness, Witness};
+use crate::lookup::LookupTable;
+use crate::proof_system::widget::ecc::scalar_mul::fixed_base::proverkey::{
+    check_bit_consistency, extract_bit,
+};
+use crate::proof_system::widget::logic::proverkey::delta_xor_and;
+use crate::proof_system::widget::range::proverkey::delta;
+use crate::proof_system::widget::GateType;
+
+use super::GateWires;
 
 /// Represents a polynomial in coefficient form with its associated wire data
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +44,8 @@ pub struct Arithmetization {
     pub(crate) q_fixed_group_add: BlsScalar,
     /// Variable base group addition selector
     pub(crate) q_variable_group_add: BlsScalar,
+    /// Lookup selector
+    pub(crate) q_lookup: BlsScalar,
 
     /// Left wire witness.
     pub(crate) w_a: Witness,
@@ -44,3 +56,145 @@ pub struct Arithmetization {
     /// Output wire witness.
     pub(crate) w_o: Witness,
 }
+
+impl From<&Constraint> for Arithmetization {
+    fn from(constraint: &Constraint) -> Self {
+        Self {
+            q_m: *constraint.coeff(Selector::Multiplication),
+            q_l: *constraint.coeff(Selector::Left),
+            q_r: *constraint.coeff(Selector::Right),
+            q_o: *constraint.coeff(Selector::Output),
+            q_c: *constraint.coeff(Selector::Constant),
+            q_d: *constraint.coeff(Selector::Fourth),
+            q_arith: *constraint.coeff(Selector::Arithmetic),
+            q_range: *constraint.coeff(Selector::Range),
+            q_logic: *constraint.coeff(Selector::Logic),
+            q_fixed_group_add: *constraint.coeff(Selector::GroupAddFixedBase),
+            q_variable_group_add: *constraint
+                .coeff(Selector::GroupAddVariableBase),
+            q_lookup: *constraint.coeff(Selector::Lookup),
+            w_a: constraint.witness(WiredWitness::A),
+            w_b: constraint.witness(WiredWitness::B),
+            w_d: constraint.witness(WiredWitness::D),
+            w_o: constraint.witness(WiredWitness::O),
+        }
+    }
+}
+
+impl Arithmetization {
+    /// Return the first gate enabled on this row that isn't satisfied by
+    /// `wires`, `pi` being the public input of the row.
+    ///
+    /// Each identity of a gate must hold on its own, so no separation
+    /// challenge is needed. The lookup and custom gates depend on the tables
+    /// and widgets of the circuit, so they are left to the caller.
+    pub(crate) fn unsatisfied_gate(
+        &self,
+        pi: &BlsScalar,
+        wires: &GateWires,
+    ) -> Option<GateType> {
+        let zero = BlsScalar::zero();
+        let one = BlsScalar::one();
+        let four = BlsScalar::from(4);
+
+        let GateWires {
+            a,
+            b,
+            c,
+            d,
+            a_next,
+            b_next,
+            d_next,
+        } = wires;
+
+        let arithmetic = (self.q_m * a * b
+            + self.q_l * a
+            + self.q_r * b
+            + self.q_o * c
+            + self.q_d * d
+            + self.q_c)
+            * self.q_arith
+            + pi;
+
+        if arithmetic != zero {
+            return Some(GateType::Arithmetic);
+        }
+
+        if self.q_range != zero {
+            let quads =
+                [c - four * d, b - four * c, a - four * b, d_next - four * a];
+
+            if quads.into_iter().any(|quad| delta(quad) != zero) {
+                return Some(GateType::Range);
+            }
+        }
+
+        if self.q_logic != zero {
+            let a = a_next - four * a;
+            let b = b_next - four * b;
+            let d = d_next - four * d;
+
+            if delta(a) != zero
+                || delta(b) != zero
+                || delta(d) != zero
+                || c != &(a * b)
+                || delta_xor_and(&a, &b, c, &d, &self.q_c) != zero
+            {
+                return Some(GateType::Logic);
+            }
+        }
+
+        if self.q_fixed_group_add != zero {
+            let bit = extract_bit(d, d_next);
+
+            // the fixed base is given by the selectors
+            let y_alpha = bit.square() * (self.q_r - one) + one;
+            let x_alpha = bit * self.q_l;
+
+            let xy = c * a * b * EDWARDS_D;
+            let x = a_next + a_next * xy - (a * y_alpha + b * x_alpha);
+            let y = b_next - b_next * xy - (b * y_alpha + a * x_alpha);
+
+            if check_bit_consistency(bit) != zero
+                || bit * self.q_c != *c
+                || x != zero
+                || y != zero
+            {
+                return Some(GateType::FixedBaseAddition);
+            }
+        }
+
+        if self.q_variable_group_add != zero {
+            // (x_1, y_1) = (a, b), (x_2, y_2) = (c, d), (x_3, y_3) =
+            // (a_next, b_next) and d_next = x_1 · y_2
+            let x1_y2 = d_next;
+            let y1_x2 = b * c;
+            let xy = EDWARDS_D * x1_y2 * y1_x2;
+
+            let x = x1_y2 + y1_x2 - a_next - a_next * xy;
+            let y = b * d + a * c - b_next + b_next * xy;
+
+            if a * d != *x1_y2 || x != zero || y != zero {
+                return Some(GateType::VariableBaseAddition);
+            }
+        }
+
+        None
+    }
+
+    /// Return [`GateType::Lookup`] if this row is a lookup gate whose query
+    /// isn't a row of `table`.
+    ///
+    /// The query of a lookup gate is its wires, the table id being `q_c`.
+    pub(crate) fn unsatisfied_lookup(
+        &self,
+        table: &LookupTable,
+        wires: &GateWires,
+    ) -> Option<GateType> {
+        let query = [wires.a, wires.b, wires.c];
+
+        (self.q_lookup != BlsScalar::zero()
+            && !table.contains(&self.q_c, &query))
+        .then_some(GateType::Lookup)
+    }
+}
@@ -4,17 +4,22 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::sync::Arc;
 use alloc::vec::Vec;
-use core::ops;
+use core::{cmp, ops};
 
 use dusk_bls12_381::BlsScalar;
 use hashbrown::HashMap;
 
-use crate::constraint_system::{Constraint, Selector, WiredWitness, Witness};
+use crate::constraint_system::{Constraint, Selector, Witness};
+use crate::error::Error;
+use crate::lookup::LookupTable;
 use crate::permutation::Permutation;
-use crate::runtime::Runtime;
+use crate::proof_system::widget::GateType;
+use crate::runtime::{Observer, Runtime, RuntimeEvent};
 
-use super::{Arithmetization, Composer};
+use super::gate::RESERVED_SELECTORS;
+use super::{Arithmetization, Circuit, Composer, CustomWidget, GateWires};
 
 /// Construct and prove circuits
 #[derive(Debug, Clone)]
@@ -31,11 +36,74 @@ pub struct Builder {
     /// Permutation argument.
     pub(crate) perm: Permutation,
 
+    /// Lookup tables queried by the lookup gates
+    pub(crate) lookup_table: LookupTable,
+
+    /// Custom gates, in order of appearance, with the rows they are enabled on
+    pub(crate) widgets: Vec<(CustomWidget, Vec<usize>)>,
+
     /// PLONK runtime controller
     pub(crate) runtime: Runtime,
 }
 
 impl Builder {
+    /// Create an initialized builder reporting its events to `observer`,
+    /// including the ones of its initialization.
+    ///
+    /// More observers can be registered with [`Runtime::observe`].
+    pub fn observed(observer: Arc<dyn Observer>) -> Self {
+        Self::observed_by(&[observer])
+    }
+
+    /// Create an initialized builder reporting its events to `observers`
+    pub(crate) fn observed_by(observers: &[Arc<dyn Observer>]) -> Self {
+        #[allow(deprecated)]
+        let mut builder = Self::uninitialized();
+
+        observers
+            .iter()
+            .for_each(|observer| builder.runtime.observe(observer.clone()));
+        super::initialize(&mut builder);
+
+        builder
+    }
+
+    /// Append the circuit of a proof to a builder observed by `observers`,
+    /// optimizing its gates if `optimized` is set
+    ///
+    /// The circuit is expected to have `constraints` gates, as compiled.
+    pub(crate) fn prove_observed<C>(
+        constraints: usize,
+        circuit: &C,
+        observers: &[Arc<dyn Observer>],
+        optimized: bool,
+    ) -> Result<Self, Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Self::observed_by(observers);
+
+        circuit.circuit(&mut builder)?;
+
+        // the debugger outputs the circuit as it was appended, so the
+        // constraints match their sources
+        #[cfg(feature = "debug")]
+        builder.runtime.write_debug_output(&builder);
+
+        if optimized {
+            builder.optimize();
+        }
+
+        // assert that the circuit has the expected amount of constraints
+        if builder.constraints() != constraints {
+            return Err(Error::InvalidCircuitSize);
+        }
+
+        builder.runtime().event(RuntimeEvent::ProofFinished);
+
+        Ok(builder)
+    }
+
     pub(crate) fn public_input_indexes(&self) -> Vec<usize> {
         let mut public_input_indexes: Vec<_> =
             self.public_inputs.keys().copied().collect();
@@ -66,6 +134,109 @@ impl Builder {
 
         dense_public_inputs
     }
+
+    /// Number of rows of the circuit, before padding the domain.
+    ///
+    /// The lookup table shares the domain with the gates, so the circuit has
+    /// at least as many rows as the table.
+    pub(crate) fn rows(&self) -> usize {
+        cmp::max(self.constraints.len(), self.lookup_table.len())
+    }
+
+    /// Number of rows of the domain the circuit is proved over.
+    pub(crate) fn domain_size(&self) -> usize {
+        self.rows().next_power_of_two()
+    }
+
+    /// Whether the circuit appends lookup gates or tables, in which case its
+    /// proofs carry the lookup argument.
+    pub(crate) fn has_lookups(&self) -> bool {
+        !self.lookup_table.is_empty()
+            || self
+                .constraints
+                .iter()
+                .any(|c| c.q_lookup != BlsScalar::zero())
+    }
+
+    /// Values of the wires of the row `i` and of the next row of a domain of
+    /// `size` rows.
+    ///
+    /// The rows past the constraints are padded with zeroes, and the row
+    /// after the last one of the domain is the first.
+    pub(crate) fn gate_wires(&self, i: usize, size: usize) -> GateWires {
+        let row = &self.constraints[i];
+        let next = self.constraints.get((i + 1) % size);
+
+        GateWires {
+            a: self[row.w_a],
+            b: self[row.w_b],
+            c: self[row.w_o],
+            d: self[row.w_d],
+            a_next: next.map(|n| self[n.w_a]).unwrap_or_default(),
+            b_next: next.map(|n| self[n.w_b]).unwrap_or_default(),
+            d_next: next.map(|n| self[n.w_d]).unwrap_or_default(),
+        }
+    }
+
+    /// Public input of the row `i`, zero if the row has none
+    pub(crate) fn public_input(&self, i: usize) -> BlsScalar {
+        self.public_inputs.get(&i).copied().unwrap_or_default()
+    }
+
+    /// Index in [`Self::widgets`] of the custom gate enabled on every row,
+    /// if any
+    ///
+    /// Every row enables at most one custom gate, so the gate of a row is
+    /// found in constant time instead of searching the rows of every gate.
+    pub(crate) fn widget_rows(&self) -> Vec<Option<usize>> {
+        let mut widget_rows = vec![None; self.constraints.len()];
+
+        self.widgets.iter().enumerate().for_each(|(w, (_, rows))| {
+            rows.iter().for_each(|&i| widget_rows[i] = Some(w));
+        });
+
+        widget_rows
+    }
+
+    /// Return the first gate enabled on the row `i` that isn't satisfied by
+    /// `wires`, including the lookup and custom gates.
+    ///
+    /// The custom gate of the row is taken from `widget_rows`, as returned by
+    /// [`Self::widget_rows`].
+    pub(crate) fn unsatisfied_gate(
+        &self,
+        i: usize,
+        wires: &GateWires,
+        widget_rows: &[Option<usize>],
+    ) -> Option<GateType> {
+        let row = &self.constraints[i];
+
+        row.unsatisfied_gate(&self.public_input(i), wires)
+            .or_else(|| row.unsatisfied_lookup(&self.lookup_table, wires))
+            .or_else(|| {
+                widget_rows[i]
+                    .map(|w| &self.widgets[w].0)
+                    .filter(|w| w.constraint(wires) != BlsScalar::zero())
+                    .map(|w| GateType::Custom(w.selector()))
+            })
+    }
+
+    /// Evaluate the gates of every row of a domain of `size` rows, failing
+    /// with the first unsatisfied one
+    pub(crate) fn check_constraints(&self, size: usize) -> Result<(), Error> {
+        let widget_rows = self.widget_rows();
+
+        (0..self.constraints.len()).try_for_each(|index| {
+            let wires = self.gate_wires(index, size);
+
+            match self.unsatisfied_gate(index, &wires, &widget_rows) {
+                Some(gate_type) => {
+                    Err(Error::UnsatisfiedConstraint { index, gate_type })
+                }
+                None => Ok(()),
+            }
+        })
+    }
 }
 
 impl ops::Index<Witness> for Builder {
@@ -83,6 +254,8 @@ impl Composer for Builder {
             public_inputs: HashMap::new(),
             witnesses: Vec::new(),
             perm: Permutation::new(),
+            lookup_table: LookupTable::new(),
+            widgets: Vec::new(),
             runtime: Runtime::new(),
         }
     }
@@ -106,42 +279,7 @@ impl Composer for Builder {
     fn append_custom_gate_internal(&mut self, constraint: Constraint) {
         let n = self.constraints.len();
 
-        let w_a = constraint.witness(WiredWitness::A);
-        let w_b = constraint.witness(WiredWitness::B);
-        let w_o = constraint.witness(WiredWitness::O);
-        let w_d = constraint.witness(WiredWitness::D);
-
-        let q_m = *constraint.coeff(Selector::Multiplication);
-        let q_l = *constraint.coeff(Selector::Left);
-        let q_r = *constraint.coeff(Selector::Right);
-        let q_o = *constraint.coeff(Selector::Output);
-        let q_c = *constraint.coeff(Selector::Constant);
-        let q_d = *constraint.coeff(Selector::Fourth);
-
-        let q_arith = *constraint.coeff(Selector::Arithmetic);
-        let q_range = *constraint.coeff(Selector::Range);
-        let q_logic = *constraint.coeff(Selector::Logic);
-        let q_fixed_group_add = *constraint.coeff(Selector::GroupAddFixedBase);
-        let q_variable_group_add =
-            *constraint.coeff(Selector::GroupAddVariableBase);
-
-        let poly = Arithmetization {
-            q_m,
-            q_l,
-            q_r,
-            q_o,
-            q_c,
-            q_d,
-            q_arith,
-            q_range,
-            q_logic,
-            q_fixed_group_add,
-            q_variable_group_add,
-            w_a,
-            w_b,
-            w_o,
-            w_d,
-        };
+        let poly = Arithmetization::from(&constraint);
 
         self.constraints.push(poly);
 
@@ -151,10 +289,56 @@ impl Composer for Builder {
             self.public_inputs.insert(n, pi);
         }
 
-        self.perm.add_witnesses_to_map(w_a, w_b, w_o, w_d, n);
+        self.perm
+            .add_witnesses_to_map(poly.w_a, poly.w_b, poly.w_o, poly.w_d, n);
+    }
+
+    fn append_widget_internal(
+        &mut self,
+        widget: CustomWidget,
+        constraint: Constraint,
+    ) -> Result<(), Error> {
+        // the selector labels the commitment and the separation challenge of
+        // the gate in the transcript
+        let selector = widget.selector();
+        let duplicate = RESERVED_SELECTORS.contains(&selector)
+            || self
+                .widgets
+                .iter()
+                .any(|(w, _)| *w != widget && w.selector() == selector);
+        if duplicate {
+            return Err(Error::DuplicateCustomGateSelector { selector });
+        }
+
+        let n = self.constraints.len();
+
+        #[allow(deprecated)]
+        self.append_custom_gate_internal(constraint);
+
+        match self.widgets.iter_mut().find(|(w, _)| *w == widget) {
+            Some((_, rows)) => rows.push(n),
+            None => self.widgets.push((widget, vec![n])),
+        }
+
+        Ok(())
+    }
+
+    fn append_lookup_table_internal(
+        &mut self,
+        table_id: BlsScalar,
+        table: &[[BlsScalar; 3]],
+    ) {
+        self.lookup_table.append(table_id, table);
     }
 
     fn runtime(&mut self) -> &mut Runtime {
         &mut self.runtime
     }
+
+    fn prove<C>(constraints: usize, circuit: &C) -> Result<Self, Error>
+    where
+        C: Circuit,
+    {
+        Self::prove_observed(constraints, circuit, &[], false)
+    }
 }
@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::sync::Arc;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
@@ -15,8 +16,12 @@ use crate::error::Error;
 use crate::fft::{EvaluationDomain, Evaluations, Polynomial};
 use crate::proof_system::preprocess::Polynomials;
 use crate::proof_system::{widget, ProverKey};
+use crate::runtime::Observer;
 
-use super::{Arithmetization, Builder, Circuit, Composer, Prover, Verifier};
+use super::{
+    Arithmetization, Builder, Circuit, CircuitReport, Composer, CustomWidget,
+    Prover, Verifier,
+};
 
 #[cfg(feature = "alloc")]
 mod compress;
@@ -38,7 +43,57 @@ impl Compiler {
         let mut builder = Builder::initialized();
         C::default().circuit(&mut builder)?;
 
-        Self::compile_with_builder(pp, label, &builder)
+        Self::compile_with_builder(pp, label, &builder, true, false)
+    }
+
+    /// Create a new arguments set from a given circuit instance, for proofs
+    /// that don't hide the witnesses
+    ///
+    /// Use the default implementation of the circuit. The prover skips the
+    /// blinding of the polynomials of the witnesses and of the quotient, so
+    /// proving is cheaper but the proofs leak information on the witnesses;
+    /// this only fits circuits whose witnesses may be public. The mode is
+    /// recorded in the proving key, and the proofs are verified as any other.
+    pub fn compile_non_hiding<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::initialized();
+        C::default().circuit(&mut builder)?;
+
+        Self::compile_with_builder(pp, label, &builder, false, false)
+    }
+
+    /// Create a new arguments set from a given circuit instance, with its
+    /// gates optimized
+    ///
+    /// Use the default implementation of the circuit. The constant witnesses
+    /// are deduplicated and folded into the gates using them, the gates
+    /// involving constants only are removed, chained linear combinations are
+    /// merged into single width-4 gates and the gates whose outputs aren't
+    /// used are removed. The optimization only depends on the structure of
+    /// the circuit, so the prover optimizes the gates of every proof the
+    /// same way, and the proofs are verified as any other.
+    ///
+    /// The optimized circuit is satisfiable exactly when the original one
+    /// is, but the witnesses that no longer appear in any gate aren't
+    /// constrained by the proofs anymore.
+    pub fn compile_optimized<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::initialized();
+        C::default().circuit(&mut builder)?;
+
+        builder.optimize();
+
+        Self::compile_with_builder(pp, label, &builder, true, true)
     }
 
     /// Create a new arguments set from a given circuit instance
@@ -55,7 +110,63 @@ impl Compiler {
         let mut builder = Builder::initialized();
         circuit.circuit(&mut builder)?;
 
-        Self::compile_with_builder(pp, label, &builder)
+        Self::compile_with_builder(pp, label, &builder, true, false)
+    }
+
+    /// Create a new arguments set from a given circuit instance, for proofs
+    /// that don't hide the witnesses
+    ///
+    /// Use the provided circuit instead of the default implementation, as
+    /// [`Self::compile_non_hiding`] does for the default one.
+    pub fn compile_with_circuit_non_hiding<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+        circuit: &C,
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::initialized();
+        circuit.circuit(&mut builder)?;
+
+        Self::compile_with_builder(pp, label, &builder, false, false)
+    }
+
+    /// Create a new arguments set from a given circuit instance, reporting
+    /// the events of its circuit to `observer`
+    ///
+    /// Use the default implementation of the circuit. The observer only
+    /// receives the events of the compilation; the circuits of the proofs
+    /// are observed by registering it with [`Prover::with_observer`].
+    pub fn compile_observed<C>(
+        pp: &PublicParameters,
+        label: &[u8],
+        observer: Arc<dyn Observer>,
+    ) -> Result<(Prover, Verifier), Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::observed(observer);
+        C::default().circuit(&mut builder)?;
+
+        Self::compile_with_builder(pp, label, &builder, true, false)
+    }
+
+    /// Report the cost of a circuit
+    ///
+    /// Use the default implementation of the circuit. The report counts the
+    /// rows enabling each gate, the public inputs, the witnesses and the
+    /// cycles of the copy constraints, along with the padded size of the
+    /// domain. With the `debug` feature, it also lists the call sites of the
+    /// constraints, by decreasing count of constraints.
+    pub fn report<C>() -> Result<CircuitReport, Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::initialized();
+        C::default().circuit(&mut builder)?;
+
+        Ok(builder.report())
     }
 
     /// Return a bytes representation of a compressed circuit, capable of
@@ -65,47 +176,151 @@ impl Compiler {
     where
         C: Circuit,
     {
-        compress::CompressedCircuit::from_circuit::<C>(true)
+        compress::CompressedCircuit::from_circuit::<C>(true, true)
+    }
+
+    /// Return a bytes representation of a compressed circuit, capable of
+    /// generating its prover and verifier instances for proofs that don't
+    /// hide the witnesses.
+    ///
+    /// The mode is part of the buffer, so [`Self::decompress`] generates the
+    /// instances of [`Self::compile_non_hiding`].
+    #[cfg(feature = "alloc")]
+    pub fn compress_non_hiding<C>() -> Result<Vec<u8>, Error>
+    where
+        C: Circuit,
+    {
+        compress::CompressedCircuit::from_circuit::<C>(true, false)
     }
 
     /// Generates a [Prover] and [Verifier] from a buffer created by
     /// [Self::compress].
+    ///
+    /// The circuits with custom gates are decompressed with
+    /// [Self::decompress_with_gates].
     pub fn decompress(
         pp: &PublicParameters,
         label: &[u8],
         compressed: &[u8],
     ) -> Result<(Prover, Verifier), Error> {
-        compress::CompressedCircuit::from_bytes(pp, label, compressed)
+        compress::CompressedCircuit::from_bytes(pp, label, compressed, &[])
+    }
+
+    /// Generates a [Prover] and [Verifier] from a buffer created by
+    /// [Self::compress] for a circuit with custom gates.
+    ///
+    /// The rows of the gates are part of the buffer, but their constraints
+    /// are not, so every gate of the circuit must be registered in `gates`,
+    /// as created by [`CustomWidget::new`]. Otherwise,
+    /// [`Error::UnregisteredCustomGate`] is returned.
+    pub fn decompress_with_gates(
+        pp: &PublicParameters,
+        label: &[u8],
+        compressed: &[u8],
+        gates: &[CustomWidget],
+    ) -> Result<(Prover, Verifier), Error> {
+        compress::CompressedCircuit::from_bytes(pp, label, compressed, gates)
     }
 
     /// Create a new arguments set from a given circuit instance
     ///
-    /// Use the default implementation of the circuit
+    /// Use the default implementation of the circuit, with proofs hiding the
+    /// witnesses if `hiding` is set, and with the gates of the proofs
+    /// optimized if `optimized` is set
     fn compile_with_builder(
         pp: &PublicParameters,
         label: &[u8],
         builder: &Builder,
+        hiding: bool,
+        optimized: bool,
     ) -> Result<(Prover, Verifier), Error> {
-        let n = (builder.constraints() + 6).next_power_of_two();
+        let n = (builder.rows() + 6).next_power_of_two();
 
         let (commit, opening) = pp.trim(n)?;
 
-        let (prover, verifier) =
-            Self::preprocess(label, commit, opening, builder)?;
+        let (prover, verifier) = Self::preprocess(
+            label, commit, opening, builder, hiding, optimized,
+        )?;
 
         Ok((prover, verifier))
     }
 
+    /// Compute the keys of the lookup argument, from the lookup selector and
+    /// the table of the circuit
+    fn preprocess_lookup(
+        commit_key: &CommitKey,
+        prover: &Builder,
+        domain: &EvaluationDomain,
+        domain_8n: &EvaluationDomain,
+    ) -> Result<(widget::lookup::VerifierKey, widget::lookup::ProverKey), Error>
+    {
+        let size = domain.size();
+
+        let mut q_lookup = vec![BlsScalar::zero(); size];
+        prover
+            .constraints
+            .iter()
+            .enumerate()
+            .for_each(|(i, c)| q_lookup[i] = c.q_lookup);
+
+        let [table_1, table_2, table_3, table_4] =
+            prover.lookup_table.columns(size);
+
+        let [q_lookup_poly, table_1_poly, table_2_poly, table_3_poly, table_4_poly] =
+            [q_lookup, table_1, table_2, table_3, table_4].map(|evals| {
+                Polynomial::from_coefficients_vec(domain.ifft(&evals))
+            });
+
+        // the table columns are padded with their last row, so they are
+        // often constant
+        let verifier_key = widget::lookup::VerifierKey {
+            q_lookup: commit_key.commit(&q_lookup_poly)?,
+            table_1: commit_key.commit(&table_1_poly)?,
+            table_2: commit_key.commit(&table_2_poly)?,
+            table_3: commit_key.commit(&table_3_poly)?,
+            table_4: commit_key.commit(&table_4_poly)?,
+        };
+
+        let [q_lookup, table_1, table_2, table_3, table_4] = [
+            q_lookup_poly,
+            table_1_poly,
+            table_2_poly,
+            table_3_poly,
+            table_4_poly,
+        ]
+        .map(|poly| {
+            let evals = Evaluations::from_vec_and_domain(
+                domain_8n.coset_fft(&poly),
+                *domain_8n,
+            );
+            (poly, evals)
+        });
+
+        let prover_key = widget::lookup::ProverKey {
+            q_lookup,
+            table_1,
+            table_2,
+            table_3,
+            table_4,
+        };
+
+        Ok((verifier_key, prover_key))
+    }
+
     fn preprocess(
         label: &[u8],
         commit_key: CommitKey,
         opening_key: OpeningKey,
         prover: &Builder,
+        hiding: bool,
+        optimized: bool,
     ) -> Result<(Prover, Verifier), Error> {
         let mut perm = prover.perm.clone();
 
         let constraints = prover.constraints();
-        let size = constraints.next_power_of_two();
+
+        let rows = prover.rows();
+        let size = prover.domain_size();
 
         let domain = EvaluationDomain::new(size - 1)?;
 
@@ -169,24 +384,19 @@ impl Compiler {
         let [s_sigma_1_poly, s_sigma_2_poly, s_sigma_3_poly, s_sigma_4_poly] =
             perm.compute_sigma_polynomials(size, &domain);
 
-        let q_m_poly_commit = commit_key.commit(&q_m_poly).unwrap_or_default();
-        let q_l_poly_commit = commit_key.commit(&q_l_poly).unwrap_or_default();
-        let q_r_poly_commit = commit_key.commit(&q_r_poly).unwrap_or_default();
-        let q_o_poly_commit = commit_key.commit(&q_o_poly).unwrap_or_default();
-        let q_c_poly_commit = commit_key.commit(&q_c_poly).unwrap_or_default();
-        let q_d_poly_commit = commit_key.commit(&q_d_poly).unwrap_or_default();
-        let q_arith_poly_commit =
-            commit_key.commit(&q_arith_poly).unwrap_or_default();
-        let q_range_poly_commit =
-            commit_key.commit(&q_range_poly).unwrap_or_default();
-        let q_logic_poly_commit =
-            commit_key.commit(&q_logic_poly).unwrap_or_default();
-        let q_fixed_group_add_poly_commit = commit_key
-            .commit(&q_fixed_group_add_poly)
-            .unwrap_or_default();
-        let q_variable_group_add_poly_commit = commit_key
-            .commit(&q_variable_group_add_poly)
-            .unwrap_or_default();
+        let q_m_poly_commit = commit_key.commit(&q_m_poly)?;
+        let q_l_poly_commit = commit_key.commit(&q_l_poly)?;
+        let q_r_poly_commit = commit_key.commit(&q_r_poly)?;
+        let q_o_poly_commit = commit_key.commit(&q_o_poly)?;
+        let q_c_poly_commit = commit_key.commit(&q_c_poly)?;
+        let q_d_poly_commit = commit_key.commit(&q_d_poly)?;
+        let q_arith_poly_commit = commit_key.commit(&q_arith_poly)?;
+        let q_range_poly_commit = commit_key.commit(&q_range_poly)?;
+        let q_logic_poly_commit = commit_key.commit(&q_logic_poly)?;
+        let q_fixed_group_add_poly_commit =
+            commit_key.commit(&q_fixed_group_add_poly)?;
+        let q_variable_group_add_poly_commit =
+            commit_key.commit(&q_variable_group_add_poly)?;
 
         let s_sigma_1_poly_commit = commit_key.commit(&s_sigma_1_poly)?;
         let s_sigma_2_poly_commit = commit_key.commit(&s_sigma_2_poly)?;
@@ -237,13 +447,33 @@ impl Compiler {
             s_sigma_4: s_sigma_4_poly_commit,
         };
 
+        // The polynomial needs an evaluation domain of 4n.
+        // Plus, adding the blinding factors translates to
+        // the polynomial not fitting in 4n, so now we need
+        // 8n, the next power of 2
+        let domain_8n = EvaluationDomain::new(8 * domain.size())?;
+
+        // keys of the lookup argument, only proved for circuits with lookups
+        let (lookup_verifier_key, lookup_prover_key) =
+            match prover.has_lookups() {
+                true => Some(Self::preprocess_lookup(
+                    &commit_key,
+                    prover,
+                    &domain,
+                    &domain_8n,
+                )?),
+                false => None,
+            }
+            .unzip();
+
         let verifier_key = widget::VerifierKey {
-            n: constraints,
+            n: rows,
             arithmetic: arithmetic_verifier_key,
             logic: logic_verifier_key,
             range: range_verifier_key,
             fixed_base: ecc_verifier_key,
             variable_base: curve_addition_verifier_key,
+            lookup: lookup_verifier_key,
             permutation: permutation_verifier_key,
         };
 
@@ -265,12 +495,6 @@ impl Compiler {
             s_sigma_4: s_sigma_4_poly,
         };
 
-        // The polynomial needs an evaluation domain of 4n.
-        // Plus, adding the blinding factors translates to
-        // the polynomial not fitting in 4n, so now we need
-        // 8n, the next power of 2
-        let domain_8n = EvaluationDomain::new(8 * domain.size())?;
-
         let q_m_eval_8n = Evaluations::from_vec_and_domain(
             domain_8n.coset_fft(&selectors.q_m),
             domain_8n,
@@ -388,15 +612,42 @@ impl Compiler {
 
         let prover_key = ProverKey {
             n: domain.size(),
+            hiding,
+            optimized,
             arithmetic: arithmetic_prover_key,
             logic: logic_prover_key,
             range: range_prover_key,
             permutation: permutation_prover_key,
             variable_base: curve_addition_prover_key,
             fixed_base: ecc_prover_key,
+            lookup: lookup_prover_key,
             v_h_coset_8n,
         };
 
+        // keys of the custom gates, with their selectors enabled on the rows
+        // the gates were appended to
+        let mut custom_prover_key = widget::custom::ProverKey::default();
+        let mut custom_verifier_key = widget::custom::VerifierKey::default();
+
+        prover.widgets.iter().try_for_each(|(widget, rows)| {
+            let mut q = vec![BlsScalar::zero(); size];
+            rows.iter().for_each(|i| q[*i] = BlsScalar::one());
+
+            let q_poly = Polynomial::from_coefficients_vec(domain.ifft(&q));
+            let q_poly_commit = commit_key.commit(&q_poly)?;
+            let q_eval_8n = Evaluations::from_vec_and_domain(
+                domain_8n.coset_fft(&q_poly),
+                domain_8n,
+            );
+
+            custom_prover_key
+                .widgets
+                .push((*widget, (q_poly, q_eval_8n)));
+            custom_verifier_key.widgets.push((*widget, q_poly_commit));
+
+            Ok::<_, Error>(())
+        })?;
+
         let public_input_indexes = prover.public_input_indexes();
 
         let label = label.to_vec();
@@ -408,7 +659,8 @@ impl Compiler {
             verifier_key,
             size,
             constraints,
-        );
+        )
+        .with_widgets(custom_prover_key, custom_verifier_key.clone());
 
         let verifier = Verifier::new(
             label,
@@ -417,7 +669,8 @@ impl Compiler {
             public_input_indexes,
             size,
             constraints,
-        );
+        )
+        .with_widgets(custom_verifier_key);
 
         Ok((prover, verifier))
     }
@@ -10,13 +10,14 @@ use msgpacker::{MsgPacker, Packable, Unpackable};
 
 use alloc::vec::Vec;
 
+use crate::hades;
+
 use super::{
     Arithmetization, BlsScalar, Builder, Circuit, Compiler, Composer,
-    Constraint, Error, Prover, PublicParameters, Selector, Verifier, Witness,
+    Constraint, CustomWidget, Error, Prover, PublicParameters, Selector,
+    Verifier, Witness,
 };
 
-mod hades;
-
 #[derive(
     Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, MsgPacker,
 )]
@@ -43,6 +44,22 @@ pub struct CompressedPolynomial {
     pub q_logic: usize,
     pub q_fixed_group_add: usize,
     pub q_variable_group_add: usize,
+    pub q_lookup: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, MsgPacker)]
+pub struct CompressedLookupTable {
+    pub id: usize,
+    // scalar indexes of the rows, flattened as `[a_0, b_0, c_0, a_1, ...]`
+    pub rows: Vec<usize>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, MsgPacker)]
+pub struct CompressedWidget {
+    // label of the selector of the gate, which identifies its implementation
+    // when the circuit is decompressed
+    pub selector: Vec<u8>,
+    pub rows: Vec<usize>,
 }
 
 fn scalar_map(hades_optimization: bool) -> HashMap<BlsScalar, usize> {
@@ -57,12 +74,12 @@ fn scalar_map(hades_optimization: bool) -> HashMap<BlsScalar, usize> {
         // assert we don't override a previously inserted constant
         for s in hades::constants() {
             let len = scalars.len();
-            scalars.entry(s).or_insert(len);
+            scalars.entry(*s).or_insert(len);
         }
         for r in hades::mds() {
             for s in r {
                 let len = scalars.len();
-                scalars.entry(s).or_insert(len);
+                scalars.entry(*s).or_insert(len);
             }
         }
     }
@@ -72,24 +89,36 @@ fn scalar_map(hades_optimization: bool) -> HashMap<BlsScalar, usize> {
 #[derive(Debug, Clone, PartialEq, Eq, MsgPacker)]
 pub struct CompressedCircuit {
     hades_optimization: bool,
+    // whether the proofs of the decompressed prover hide the witnesses
+    hiding: bool,
     public_inputs: Vec<usize>,
     witnesses: usize,
     scalars: Vec<[u8; BlsScalar::SIZE]>,
     polynomials: Vec<CompressedPolynomial>,
     constraints: Vec<CompressedConstraint>,
+    lookup_tables: Vec<CompressedLookupTable>,
+    widgets: Vec<CompressedWidget>,
 }
 
 impl CompressedCircuit {
-    pub fn from_circuit<C>(hades_optimization: bool) -> Result<Vec<u8>, Error>
+    pub fn from_circuit<C>(
+        hades_optimization: bool,
+        hiding: bool,
+    ) -> Result<Vec<u8>, Error>
     where
         C: Circuit,
     {
         let mut builder = Builder::initialized();
         C::default().circuit(&mut builder)?;
-        Ok(Self::from_builder(hades_optimization, builder))
+
+        Ok(Self::from_builder(hades_optimization, hiding, builder))
     }
 
-    pub fn from_builder(hades_optimization: bool, builder: Builder) -> Vec<u8> {
+    pub fn from_builder(
+        hades_optimization: bool,
+        hiding: bool,
+        builder: Builder,
+    ) -> Vec<u8> {
         let mut public_inputs: Vec<_> =
             builder.public_inputs.keys().copied().collect();
         public_inputs.sort();
@@ -115,6 +144,7 @@ impl CompressedCircuit {
                      q_logic,
                      q_fixed_group_add,
                      q_variable_group_add,
+                     q_lookup,
                      w_a,
                      w_b,
                      w_d,
@@ -144,6 +174,8 @@ impl CompressedCircuit {
                     let len = scalars.len();
                     let q_variable_group_add =
                         *scalars.entry(q_variable_group_add).or_insert(len);
+                    let len = scalars.len();
+                    let q_lookup = *scalars.entry(q_lookup).or_insert(len);
                     let polynomial = CompressedPolynomial {
                         q_m,
                         q_l,
@@ -156,6 +188,7 @@ impl CompressedCircuit {
                         q_logic,
                         q_fixed_group_add,
                         q_variable_group_add,
+                        q_lookup,
                     };
 
                     let len = polynomials.len();
@@ -173,6 +206,34 @@ impl CompressedCircuit {
             )
             .collect();
 
+        let lookup_tables = builder
+            .lookup_table
+            .tables()
+            .map(|(id, rows)| {
+                let len = scalars.len();
+                let id = *scalars.entry(*id).or_insert(len);
+                let rows = rows
+                    .iter()
+                    .flatten()
+                    .map(|s| {
+                        let len = scalars.len();
+                        *scalars.entry(*s).or_insert(len)
+                    })
+                    .collect();
+
+                CompressedLookupTable { id, rows }
+            })
+            .collect();
+
+        let widgets = builder
+            .widgets
+            .iter()
+            .map(|(widget, rows)| CompressedWidget {
+                selector: widget.selector().to_vec(),
+                rows: rows.clone(),
+            })
+            .collect();
+
         let scalars_map = scalars;
         let mut scalars = vec![[0u8; BlsScalar::SIZE]; scalars_map.len()];
         scalars_map
@@ -192,11 +253,14 @@ impl CompressedCircuit {
 
         let compressed = Self {
             hades_optimization,
+            hiding,
             public_inputs,
             witnesses,
             scalars,
             polynomials,
             constraints,
+            lookup_tables,
+            widgets,
         };
         let mut buf = Vec::with_capacity(
             1 + compressed.scalars.len() * BlsScalar::SIZE
@@ -211,6 +275,7 @@ impl CompressedCircuit {
         pp: &PublicParameters,
         label: &[u8],
         compressed: &[u8],
+        gates: &[CustomWidget],
     ) -> Result<(Prover, Verifier), Error> {
         let compressed = miniz_oxide::inflate::decompress_to_vec(compressed)
             .map_err(|_| Error::InvalidCompressedCircuit)?;
@@ -218,11 +283,14 @@ impl CompressedCircuit {
             _,
             Self {
                 hades_optimization,
+                hiding,
                 public_inputs,
                 witnesses,
                 scalars,
                 polynomials,
                 constraints,
+                lookup_tables,
+                widgets,
             },
         ) = Self::unpack(&compressed)
             .map_err(|_| Error::InvalidCompressedCircuit)?;
@@ -274,6 +342,7 @@ impl CompressedCircuit {
                 q_logic,
                 q_fixed_group_add,
                 q_variable_group_add,
+                q_lookup,
             } = polynomials
                 .get(polynomial)
                 .copied()
@@ -323,6 +392,10 @@ impl CompressedCircuit {
                 .get(q_variable_group_add)
                 .copied()
                 .ok_or(Error::InvalidCompressedCircuit)?;
+            let q_lookup = scalars
+                .get(q_lookup)
+                .copied()
+                .ok_or(Error::InvalidCompressedCircuit)?;
 
             let w_a = Witness::new(w_a);
             let w_b = Witness::new(w_b);
@@ -341,6 +414,7 @@ impl CompressedCircuit {
                 .set(Selector::Logic, q_logic)
                 .set(Selector::GroupAddFixedBase, q_fixed_group_add)
                 .set(Selector::GroupAddVariableBase, q_variable_group_add)
+                .set(Selector::Lookup, q_lookup)
                 .a(w_a)
                 .b(w_b)
                 .d(w_d)
@@ -356,6 +430,50 @@ impl CompressedCircuit {
             builder.append_custom_gate(constraint);
         }
 
-        Compiler::compile_with_builder(pp, label, &builder)
+        for CompressedLookupTable { id, rows } in lookup_tables {
+            if rows.len() % 3 != 0 {
+                return Err(Error::InvalidCompressedCircuit);
+            }
+
+            let id = scalars
+                .get(id)
+                .copied()
+                .ok_or(Error::InvalidCompressedCircuit)?;
+            let rows = rows
+                .chunks(3)
+                .map(|row| {
+                    let mut scalar_row = [BlsScalar::zero(); 3];
+                    for (s, i) in scalar_row.iter_mut().zip(row) {
+                        *s = scalars
+                            .get(*i)
+                            .copied()
+                            .ok_or(Error::InvalidCompressedCircuit)?;
+                    }
+                    Ok(scalar_row)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            builder.append_lookup_table(id, &rows);
+        }
+
+        let constraints = builder.constraints();
+        builder.widgets = widgets
+            .into_iter()
+            .map(|CompressedWidget { selector, rows }| {
+                let widget = gates
+                    .iter()
+                    .find(|gate| gate.selector() == selector.as_slice())
+                    .copied()
+                    .ok_or(Error::UnregisteredCustomGate)?;
+
+                if rows.iter().any(|i| *i >= constraints) {
+                    return Err(Error::InvalidCompressedCircuit);
+                }
+
+                Ok((widget, rows))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Compiler::compile_with_builder(pp, label, &builder, hiding, false)
     }
 }
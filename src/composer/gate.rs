@@ -0,0 +1,169 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use core::any::TypeId;
+use core::fmt;
+
+use dusk_bls12_381::BlsScalar;
+
+/// Values of the wires of a row of the circuit, along with the values of the
+/// wires of the next row that are available to the constraints of a gate.
+///
+/// When proving, these are the values of a row; when verifying, they are the
+/// evaluations of the wire polynomials at the evaluation challenge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GateWires {
+    /// Left wire
+    pub a: BlsScalar,
+    /// Right wire
+    pub b: BlsScalar,
+    /// Output wire
+    pub c: BlsScalar,
+    /// Fourth wire
+    pub d: BlsScalar,
+    /// Left wire of the next row
+    pub a_next: BlsScalar,
+    /// Right wire of the next row
+    pub b_next: BlsScalar,
+    /// Fourth wire of the next row
+    pub d_next: BlsScalar,
+}
+
+/// Gate defined outside of the crate, appended to a circuit with
+/// [`Composer::append_widget`](super::Composer::append_widget).
+///
+/// Each gate owns a selector polynomial that is one on the rows where the gate
+/// was appended and zero elsewhere. The proof then asserts that
+/// `q_selector(X) · constraint(X) = 0` on every row of the circuit, so the
+/// constraint of the gate must hold on the rows where it is enabled.
+///
+/// The gates of a circuit are picked up by [`Compiler`](super::Compiler) when
+/// the circuit is compiled, and the [`Prover`](super::Prover) and
+/// [`Verifier`](super::Verifier) carry them from there on.
+///
+/// The degree of the constraint in the wires must not be higher than 4.
+pub trait CustomGate: 'static {
+    /// Label of the selector polynomial of the gate, used to append its
+    /// commitment and to derive its separation challenge in the transcript.
+    ///
+    /// The label must be unique among the gates of a circuit, and differ from
+    /// the labels of the selectors and challenges of the built-in gates, such
+    /// as `q_m` or `alpha`, otherwise
+    /// [`Composer::append_widget`](super::Composer::append_widget) returns
+    /// [`Error::DuplicateCustomGateSelector`](crate::error::Error::DuplicateCustomGateSelector).
+    const SELECTOR: &'static [u8];
+
+    /// Evaluate the constraint of the gate, which must be zero on the rows
+    /// where the gate is enabled
+    fn constraint(wires: &GateWires) -> BlsScalar;
+
+    /// Evaluate the scalar the selector polynomial is multiplied by in the
+    /// linearization polynomial, given the evaluations of the wires at the
+    /// evaluation challenge.
+    ///
+    /// The selector is the only fixed polynomial of a gate, so this is the
+    /// constraint itself by default.
+    fn linearization(evaluations: &GateWires) -> BlsScalar {
+        Self::constraint(evaluations)
+    }
+}
+
+/// Labels the built-in gates append to the transcript, for their selector
+/// commitments and their challenges, which the selectors of the custom gates
+/// can't take
+pub(crate) const RESERVED_SELECTORS: &[&[u8]] = &[
+    b"q_m",
+    b"q_l",
+    b"q_r",
+    b"q_o",
+    b"q_c",
+    b"q_4",
+    b"q_arith",
+    b"q_range",
+    b"q_logic",
+    b"q_variable_group_add",
+    b"q_fixed_group_add",
+    b"q_lookup",
+    b"table_1",
+    b"table_2",
+    b"table_3",
+    b"table_4",
+    b"s_sigma_1",
+    b"s_sigma_2",
+    b"s_sigma_3",
+    b"s_sigma_4",
+    b"zeta",
+    b"beta",
+    b"gamma",
+    b"delta",
+    b"epsilon",
+    b"alpha",
+    b"range separation challenge",
+    b"logic separation challenge",
+    b"fixed base separation challenge",
+    b"variable base separation challenge",
+    b"lookup separation challenge",
+    b"z_challenge",
+    b"v_challenge",
+    b"batch",
+];
+
+/// Type erased [`CustomGate`] appended to a circuit
+///
+/// The constraints of the gates can't be serialized, so the gates of a circuit
+/// are registered again as widgets when its prover, verifier or compressed
+/// representation is deserialized, as in
+/// [`Prover::try_from_bytes_with_gates`](super::Prover::try_from_bytes_with_gates).
+#[derive(Clone, Copy)]
+pub struct CustomWidget {
+    id: TypeId,
+    selector: &'static [u8],
+    constraint: fn(&GateWires) -> BlsScalar,
+    linearization: fn(&GateWires) -> BlsScalar,
+}
+
+impl CustomWidget {
+    /// Erase the type of the gate `G`
+    pub fn new<G: CustomGate>() -> Self {
+        Self {
+            id: TypeId::of::<G>(),
+            selector: G::SELECTOR,
+            constraint: G::constraint,
+            linearization: G::linearization,
+        }
+    }
+
+    /// Label of the selector polynomial of the gate
+    pub const fn selector(&self) -> &'static [u8] {
+        self.selector
+    }
+
+    /// Evaluate the constraint of the gate
+    pub fn constraint(&self, wires: &GateWires) -> BlsScalar {
+        (self.constraint)(wires)
+    }
+
+    /// Evaluate the linearization scalar of the gate
+    pub fn linearization(&self, evaluations: &GateWires) -> BlsScalar {
+        (self.linearization)(evaluations)
+    }
+}
+
+impl PartialEq for CustomWidget {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomWidget {}
+
+impl fmt::Debug for CustomWidget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomWidget")
+            .field("selector", &self.selector)
+            .finish()
+    }
+}
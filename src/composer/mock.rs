@@ -0,0 +1,515 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Constraint checker that runs a circuit without public parameters
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::constraint_system::{WireData, Witness};
+use crate::error::Error;
+use crate::proof_system::widget::GateType;
+
+use super::{Arithmetization, Builder, Circuit, Composer, GateWires};
+
+/// Wire of a row of the circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wire {
+    /// Left wire
+    A,
+    /// Right wire
+    B,
+    /// Output wire
+    C,
+    /// Fourth wire
+    D,
+}
+
+impl fmt::Display for Wire {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::A => write!(f, "a"),
+            Self::B => write!(f, "b"),
+            Self::C => write!(f, "c"),
+            Self::D => write!(f, "d"),
+        }
+    }
+}
+
+/// Selectors of a row of the circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selectors {
+    /// Multiplier selector
+    pub q_m: BlsScalar,
+    /// Left wire selector
+    pub q_l: BlsScalar,
+    /// Right wire selector
+    pub q_r: BlsScalar,
+    /// Output wire selector
+    pub q_o: BlsScalar,
+    /// Fourth wire selector
+    pub q_d: BlsScalar,
+    /// Constant wire selector
+    pub q_c: BlsScalar,
+    /// Arithmetic wire selector
+    pub q_arith: BlsScalar,
+    /// Range selector
+    pub q_range: BlsScalar,
+    /// Logic selector
+    pub q_logic: BlsScalar,
+    /// Fixed base group addition selector
+    pub q_fixed_group_add: BlsScalar,
+    /// Variable base group addition selector
+    pub q_variable_group_add: BlsScalar,
+    /// Lookup selector
+    pub q_lookup: BlsScalar,
+}
+
+impl From<&Arithmetization> for Selectors {
+    fn from(row: &Arithmetization) -> Self {
+        Self {
+            q_m: row.q_m,
+            q_l: row.q_l,
+            q_r: row.q_r,
+            q_o: row.q_o,
+            q_d: row.q_d,
+            q_c: row.q_c,
+            q_arith: row.q_arith,
+            q_range: row.q_range,
+            q_logic: row.q_logic,
+            q_fixed_group_add: row.q_fixed_group_add,
+            q_variable_group_add: row.q_variable_group_add,
+            q_lookup: row.q_lookup,
+        }
+    }
+}
+
+impl fmt::Display for Selectors {
+    // only the enabled selectors are displayed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let selectors = [
+            ("q_m", &self.q_m),
+            ("q_l", &self.q_l),
+            ("q_r", &self.q_r),
+            ("q_o", &self.q_o),
+            ("q_d", &self.q_d),
+            ("q_c", &self.q_c),
+            ("q_arith", &self.q_arith),
+            ("q_range", &self.q_range),
+            ("q_logic", &self.q_logic),
+            ("q_fixed_group_add", &self.q_fixed_group_add),
+            ("q_variable_group_add", &self.q_variable_group_add),
+            ("q_lookup", &self.q_lookup),
+        ];
+
+        let mut enabled =
+            selectors.iter().filter(|(_, q)| **q != BlsScalar::zero());
+
+        match enabled.next() {
+            Some((name, q)) => write!(f, "{} = {:?}", name, q)?,
+            None => return write!(f, "none"),
+        }
+
+        enabled.try_for_each(|(name, q)| write!(f, ", {} = {:?}", name, q))
+    }
+}
+
+/// Constraint of a circuit that doesn't hold, as reported by [`MockProver`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum MockFailure {
+    /// A gate isn't satisfied by the values of the wires of its row
+    Constraint {
+        /// Index of the row
+        index: usize,
+        /// Unsatisfied gate
+        gate: GateType,
+        /// Selectors of the row
+        selectors: Selectors,
+        /// Values of the wires of the row and of the next row
+        wires: GateWires,
+        /// Public input of the row
+        public_input: BlsScalar,
+        /// Path of the namespace the row was appended in, if any
+        namespace: Option<String>,
+        /// Names of the witnesses wired to `a`, `b`, `c` and `d`, if they
+        /// were named
+        names: [Option<String>; 4],
+        /// Location of the code that appended the row, if the `debug`
+        /// feature is enabled and the location was resolved
+        source: Option<String>,
+    },
+
+    /// A wire doesn't carry the value of a witness it is copied from
+    CopyConstraint {
+        /// Copied witness
+        witness: Witness,
+        /// Name of the witness, if it was named
+        name: Option<String>,
+        /// Value of the witness
+        value: BlsScalar,
+        /// Index of the row of the wire
+        index: usize,
+        /// Wire of the row
+        wire: Wire,
+        /// Value of the wire
+        wire_value: BlsScalar,
+    },
+}
+
+impl fmt::Display for MockFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // value of a wire, followed by the name of its witness
+        let wire = |value: &BlsScalar, name: &Option<String>| match name {
+            Some(name) => format!("{:?} (`{}`)", value, name),
+            None => format!("{:?}", value),
+        };
+
+        match self {
+            Self::Constraint {
+                index,
+                gate,
+                selectors,
+                wires,
+                public_input,
+                namespace,
+                names,
+                source,
+            } => {
+                write!(f, "constraint {} fails its {} gate", index, gate)?;
+                if let Some(namespace) = namespace {
+                    write!(f, " in `{}`", namespace)?;
+                }
+                if let Some(source) = source {
+                    write!(f, " (appended at {})", source)?;
+                }
+
+                write!(f, "\n  selectors: {}", selectors)?;
+                write!(
+                    f,
+                    "\n  wires: a = {}, b = {}, c = {}, d = {}",
+                    wire(&wires.a, &names[0]),
+                    wire(&wires.b, &names[1]),
+                    wire(&wires.c, &names[2]),
+                    wire(&wires.d, &names[3]),
+                )?;
+                write!(
+                    f,
+                    "\n  next wires: a = {:?}, b = {:?}, d = {:?}",
+                    wires.a_next, wires.b_next, wires.d_next
+                )?;
+                write!(f, "\n  public input: {:?}", public_input)
+            }
+
+            Self::CopyConstraint {
+                witness,
+                name,
+                value,
+                index,
+                wire,
+                wire_value,
+            } => {
+                write!(f, "wire {} of constraint {} ", wire, index)?;
+                write!(f, "is {:?}, but is copied from ", wire_value)?;
+                write!(f, "witness {} ", witness.index())?;
+                if let Some(name) = name {
+                    write!(f, "`{}` ", name)?;
+                }
+                write!(f, "of value {:?}", value)
+            }
+        }
+    }
+}
+
+/// Check that a circuit is satisfied without generating public parameters,
+/// compiling or proving it.
+///
+/// The circuit is run on a [`Builder`], and every gate of every row is then
+/// evaluated on the values of the wires, along with the copy constraints of
+/// the witnesses. Unlike a failed verification, every unsatisfied constraint
+/// is reported.
+///
+/// ```
+/// use dusk_plonk::prelude::*;
+///
+/// #[derive(Default)]
+/// struct Mul {
+///     a: BlsScalar,
+///     b: BlsScalar,
+///     c: BlsScalar,
+/// }
+///
+/// impl Circuit for Mul {
+///     fn circuit<C: Composer>(&self, composer: &mut C) -> Result<(), Error> {
+///         let a = composer.append_witness(self.a);
+///         let b = composer.append_witness(self.b);
+///         let c = composer.append_witness(self.c);
+///
+///         let constraint = Constraint::new().mult(1).a(a).b(b).o(c);
+///         composer.append_gate(constraint.output(-BlsScalar::one()));
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let circuit = Mul {
+///     a: BlsScalar::from(2),
+///     b: BlsScalar::from(3),
+///     c: BlsScalar::from(6),
+/// };
+/// MockProver::run(&circuit)?.assert_satisfied();
+///
+/// let circuit = Mul {
+///     c: BlsScalar::from(7),
+///     ..circuit
+/// };
+/// let failures = MockProver::run(&circuit)?
+///     .verify()
+///     .expect_err("the circuit isn't satisfied");
+/// assert_eq!(failures.len(), 1);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockProver {
+    builder: Builder,
+}
+
+impl MockProver {
+    /// Run `circuit` on an initialized [`Builder`]
+    pub fn run<C>(circuit: &C) -> Result<Self, Error>
+    where
+        C: Circuit,
+    {
+        let mut builder = Builder::initialized();
+
+        circuit.circuit(&mut builder)?;
+
+        Ok(Self { builder })
+    }
+
+    /// Constraints count of the circuit
+    pub fn constraints(&self) -> usize {
+        self.builder.constraints()
+    }
+
+    /// Public inputs of the circuit, in the order expected by the verifier
+    pub fn public_inputs(&self) -> Vec<BlsScalar> {
+        self.builder.public_inputs()
+    }
+
+    /// Evaluate every gate and copy constraint of the circuit, returning the
+    /// unsatisfied ones ordered by row.
+    ///
+    /// The wires of the next row are the ones of the padded domain the
+    /// circuit would be proved over.
+    pub fn verify(&self) -> Result<(), Vec<MockFailure>> {
+        let builder = &self.builder;
+        let size = builder.domain_size();
+        let widget_rows = builder.widget_rows();
+
+        let mut failures: Vec<_> = builder
+            .constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| {
+                let wires = builder.gate_wires(index, size);
+                let public_input = builder.public_input(index);
+
+                builder.unsatisfied_gate(index, &wires, &widget_rows).map(
+                    |gate| MockFailure::Constraint {
+                        index,
+                        gate,
+                        selectors: row.into(),
+                        wires,
+                        public_input,
+                        namespace: self.namespace(index),
+                        names: [row.w_a, row.w_b, row.w_o, row.w_d]
+                            .map(|w| self.name(&w)),
+                        source: self.source(index),
+                    },
+                )
+            })
+            .collect();
+
+        failures.extend(self.unsatisfied_copies());
+        failures.sort_by_key(|failure| match failure {
+            MockFailure::Constraint { index, .. } => *index,
+            MockFailure::CopyConstraint { index, .. } => *index,
+        });
+
+        match failures.is_empty() {
+            true => Ok(()),
+            false => Err(failures),
+        }
+    }
+
+    /// Panic with the unsatisfied constraints of the circuit, if any
+    pub fn assert_satisfied(&self) {
+        if let Err(failures) = self.verify() {
+            let report: Vec<_> =
+                failures.iter().map(|failure| failure.to_string()).collect();
+
+            panic!("the circuit isn't satisfied:\n\n{}", report.join("\n\n"));
+        }
+    }
+
+    // every wire of the cycle of a witness must carry its value
+    fn unsatisfied_copies(&self) -> Vec<MockFailure> {
+        let builder = &self.builder;
+
+        builder
+            .perm
+            .witness_map
+            .iter()
+            .flat_map(|(witness, wires)| {
+                let value = builder.witnesses[witness.index()];
+
+                wires.iter().filter_map(move |wire| {
+                    let (index, wire) = match *wire {
+                        WireData::Left(i) => (i, Wire::A),
+                        WireData::Right(i) => (i, Wire::B),
+                        WireData::Output(i) => (i, Wire::C),
+                        WireData::Fourth(i) => (i, Wire::D),
+                    };
+
+                    let row = &builder.constraints[index];
+                    let wired = match wire {
+                        Wire::A => row.w_a,
+                        Wire::B => row.w_b,
+                        Wire::C => row.w_o,
+                        Wire::D => row.w_d,
+                    };
+
+                    let wire_value = builder[wired];
+
+                    (wire_value != value).then_some(
+                        MockFailure::CopyConstraint {
+                            witness: *witness,
+                            name: self.name(witness),
+                            value,
+                            index,
+                            wire,
+                            wire_value,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn namespace(&self, index: usize) -> Option<String> {
+        let runtime = &self.builder.runtime;
+        runtime.constraint_namespace(index).map(String::from)
+    }
+
+    fn name(&self, witness: &Witness) -> Option<String> {
+        self.builder.runtime.witness_name(witness).map(String::from)
+    }
+
+    #[allow(unused_variables)]
+    fn source(&self, index: usize) -> Option<String> {
+        #[cfg(feature = "debug")]
+        return self.builder.runtime.constraint_source(index);
+
+        #[cfg(not(feature = "debug"))]
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dusk_jubjub::{JubJubScalar, GENERATOR_EXTENDED};
+
+    #[derive(Default)]
+    struct TestCircuit;
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::from(0xa5));
+            let w_b = composer.append_witness(BlsScalar::from(0x3c));
+
+            composer.component_range::<4>(w_a);
+            composer.append_logic_xor::<4>(w_a, w_b);
+
+            let scalar = JubJubScalar::from(0xc0ffeeu64);
+            let w_scalar = composer.append_witness(scalar);
+            let w_point = composer
+                .component_mul_generator(w_scalar, GENERATOR_EXTENDED)?;
+            composer.component_add_point(w_point, w_point);
+
+            Ok(())
+        }
+    }
+
+    // tamper the wire `o` of the first row with the selector `q`, expecting
+    // the row to fail `gate`
+    fn tamper<F>(q: F, gate: GateType)
+    where
+        F: Fn(&Arithmetization) -> BlsScalar,
+    {
+        let mut mock = MockProver::run(&TestCircuit).expect("circuit runs");
+        mock.assert_satisfied();
+
+        let builder = &mut mock.builder;
+        let index = builder
+            .constraints
+            .iter()
+            .position(|row| q(row) != BlsScalar::zero())
+            .expect("the gate is enabled");
+
+        let w_o = builder.constraints[index].w_o;
+        builder.witnesses[w_o.index()] += BlsScalar::one();
+
+        let failures = mock.verify().expect_err("tampered circuit fails");
+        assert!(failures.iter().any(|failure| matches!(
+            failure,
+            MockFailure::Constraint { index: i, gate: g, .. }
+                if *i == index && *g == gate
+        )));
+    }
+
+    #[test]
+    fn tampered_gates() {
+        tamper(|row| row.q_range, GateType::Range);
+        tamper(|row| row.q_logic, GateType::Logic);
+        tamper(|row| row.q_fixed_group_add, GateType::FixedBaseAddition);
+        tamper(
+            |row| row.q_variable_group_add,
+            GateType::VariableBaseAddition,
+        );
+    }
+
+    #[test]
+    fn tampered_copy() {
+        let mut mock = MockProver::run(&TestCircuit).expect("circuit runs");
+
+        // rewire the left wire of the last row to another witness
+        let builder = &mut mock.builder;
+        let index = builder.constraints.len() - 1;
+        let w_a = builder.constraints[index].w_a;
+        let w_other = builder.append_witness(BlsScalar::one());
+        builder.witnesses[w_other.index()] = builder[w_a] + BlsScalar::one();
+        builder.constraints[index].w_a = w_other;
+
+        let failures = mock.verify().expect_err("tampered circuit fails");
+        assert!(failures.contains(&MockFailure::CopyConstraint {
+            witness: w_a,
+            name: None,
+            value: mock.builder[w_a],
+            index,
+            wire: Wire::A,
+            wire_value: mock.builder[w_a] + BlsScalar::one(),
+        }));
+    }
+}
@@ -0,0 +1,772 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Optimization of the gates appended by a [`Builder`]
+//!
+//! The passes only read the selectors and the wiring of the gates, never the
+//! values of the witnesses, so the gates of a circuit are rewritten the same
+//! way when it is compiled and when it is proven. Every rewrite keeps the set
+//! of assignments satisfying the circuit, up to the witnesses that no longer
+//! appear in any gate.
+//!
+//! Only arithmetic gates are rewritten or removed. The gates that read the
+//! wires of the next row (range, logic, curve additions and custom gates)
+//! pin the row that follows them, and the public inputs and the
+//! initialization of the builder are left as they are.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use hashbrown::{HashMap, HashSet};
+
+use crate::constraint_system::{Constraint, Witness};
+use crate::permutation::Permutation;
+
+use super::{Arithmetization, Builder, Composer};
+
+/// Linear combination `Σ cᵢ·wᵢ + k` of the witnesses of an arithmetic gate,
+/// once its constant witnesses are substituted
+#[derive(Debug, Clone)]
+struct Linear {
+    terms: Vec<(Witness, BlsScalar)>,
+    constant: BlsScalar,
+}
+
+impl Linear {
+    fn add(&mut self, w: Witness, c: BlsScalar) {
+        match self.terms.iter_mut().find(|(t, _)| *t == w) {
+            Some((_, s)) => *s += c,
+            None => self.terms.push((w, c)),
+        }
+    }
+
+    fn coeff(&self, w: Witness) -> BlsScalar {
+        self.terms
+            .iter()
+            .find_map(|(t, c)| (*t == w).then_some(*c))
+            .unwrap_or_default()
+    }
+
+    /// Drop the witnesses whose coefficients cancelled out
+    fn normalized(mut self) -> Self {
+        self.terms.retain(|(_, c)| *c != BlsScalar::zero());
+        self
+    }
+
+    /// `self + factor · other`
+    fn combined(&self, other: &Self, factor: BlsScalar) -> Self {
+        let mut linear = self.clone();
+
+        other
+            .terms
+            .iter()
+            .for_each(|(w, c)| linear.add(*w, factor * c));
+        linear.constant += factor * other.constant;
+
+        linear.normalized()
+    }
+
+    /// Arithmetic gate asserting the combination is zero, if its witnesses
+    /// fit the wires of a gate
+    fn arithmetization(&self) -> Option<Arithmetization> {
+        if self.terms.len() > 4 {
+            return None;
+        }
+
+        let mut row =
+            Arithmetization::from(&Constraint::arithmetic(&Constraint::new()));
+        row.q_c = self.constant;
+
+        let wires = [
+            (&mut row.q_l, &mut row.w_a),
+            (&mut row.q_r, &mut row.w_b),
+            (&mut row.q_o, &mut row.w_o),
+            (&mut row.q_d, &mut row.w_d),
+        ];
+
+        for ((q, w), (t, c)) in
+            wires.into_iter().zip(self.terms.iter().copied())
+        {
+            *q = c;
+            *w = t;
+        }
+
+        Some(row)
+    }
+}
+
+/// Gates of a builder being optimized
+struct Optimizer {
+    /// Gates of the rows, `None` once removed
+    rows: Vec<Option<Arithmetization>>,
+    /// Rows of the initialization of the builder
+    pinned: usize,
+    /// Rows with public inputs or custom gates
+    fixed: HashSet<usize>,
+    /// Rows of the gates reading the wires of the next row
+    reads_next: HashSet<usize>,
+    /// Constant witnesses, with their values and the rows defining them
+    constants: HashMap<Witness, (BlsScalar, usize)>,
+    /// First witness defined for every constant
+    values: HashMap<BlsScalar, Witness>,
+    /// Rows that had the witness on one of their wires, possibly no longer
+    occurrences: Vec<Vec<usize>>,
+    /// Rows to simplify, with the ones that are queued
+    rows_queue: VecDeque<usize>,
+    queued_rows: Vec<bool>,
+    /// Witnesses to eliminate, with the ones that are queued
+    witnesses_queue: VecDeque<Witness>,
+    queued_witnesses: Vec<bool>,
+}
+
+impl Optimizer {
+    fn new(builder: &Builder) -> Self {
+        let rows: Vec<_> =
+            builder.constraints.iter().copied().map(Some).collect();
+
+        let pinned = Builder::initialized().constraints();
+
+        let fixed = builder
+            .public_inputs
+            .keys()
+            .copied()
+            .chain(builder.widgets.iter().flat_map(|(_, rows)| rows).copied())
+            .collect();
+
+        let reads_next = builder
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.q_range != BlsScalar::zero()
+                    || row.q_logic != BlsScalar::zero()
+                    || row.q_fixed_group_add != BlsScalar::zero()
+                    || row.q_variable_group_add != BlsScalar::zero()
+            })
+            .map(|(i, _)| i)
+            .chain(builder.widgets.iter().flat_map(|(_, rows)| rows).copied())
+            .collect();
+
+        let mut occurrences = vec![Vec::new(); builder.witnesses.len()];
+        builder.constraints.iter().enumerate().for_each(|(i, row)| {
+            wires(row)
+                .into_iter()
+                .filter(is_tracked)
+                .for_each(|w| occurrences[w.index()].push(i));
+        });
+
+        Self {
+            rows_queue: (0..rows.len()).collect(),
+            queued_rows: vec![true; rows.len()],
+            witnesses_queue: (0..builder.witnesses.len())
+                .map(Witness::new)
+                .collect(),
+            queued_witnesses: vec![true; builder.witnesses.len()],
+            rows,
+            pinned,
+            fixed,
+            reads_next,
+            constants: HashMap::new(),
+            values: HashMap::new(),
+            occurrences,
+        }
+    }
+
+    /// Simplify the queued rows and eliminate the queued witnesses, until
+    /// no rewrite applies
+    fn run(&mut self) {
+        loop {
+            if let Some(i) = self.rows_queue.pop_front() {
+                self.queued_rows[i] = false;
+                self.fold_constants(i);
+            } else if let Some(w) = self.witnesses_queue.pop_front() {
+                self.queued_witnesses[w.index()] = false;
+                self.eliminate(w);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn queue_row(&mut self, i: usize) {
+        if !self.queued_rows[i] {
+            self.queued_rows[i] = true;
+            self.rows_queue.push_back(i);
+        }
+    }
+
+    fn queue_witness(&mut self, w: Witness) {
+        if is_tracked(&w) && !self.queued_witnesses[w.index()] {
+            self.queued_witnesses[w.index()] = true;
+            self.witnesses_queue.push_back(w);
+        }
+    }
+
+    /// Replace the gate of the row, queueing the rows and witnesses it
+    /// affects
+    ///
+    /// The constants defined by the replaced gate are forgotten, since their
+    /// witnesses are no longer constrained by it. A rewritten gate is queued,
+    /// so the constants it still defines are recorded again.
+    fn set_row(&mut self, i: usize, row: Option<Arithmetization>) {
+        if let Some(old) = self.rows[i] {
+            for w in wires(&old) {
+                self.forget_constant(w, i);
+                self.queue_witness(w);
+            }
+        }
+
+        if let Some(new) = &row {
+            for w in wires(new).into_iter().filter(is_tracked) {
+                self.occurrences[w.index()].push(i);
+                self.queue_witness(w);
+            }
+            self.queue_row(i);
+        }
+
+        self.rows[i] = row;
+    }
+
+    /// Forget the witness as a constant if it is defined by the row `i`
+    fn forget_constant(&mut self, w: Witness, i: usize) {
+        let v = match self.constants.get(&w) {
+            Some((v, definition)) if *definition == i => *v,
+            _ => return,
+        };
+
+        self.constants.remove(&w);
+        if self.values.get(&v) == Some(&w) {
+            self.values.remove(&v);
+        }
+    }
+
+    /// Rows with the witness on one of their wires, along with the number of
+    /// wires
+    fn rows_of(&mut self, w: Witness) -> (Vec<usize>, usize) {
+        let rows = &self.rows;
+        let occurrences = &mut self.occurrences[w.index()];
+
+        occurrences.sort_unstable();
+        occurrences.dedup();
+        occurrences.retain(|i| {
+            rows[*i].as_ref().is_some_and(|row| wires(row).contains(&w))
+        });
+
+        let count = occurrences
+            .iter()
+            .filter_map(|i| rows[*i].as_ref())
+            .map(|row| wires(row).iter().filter(|t| **t == w).count())
+            .sum();
+
+        (occurrences.clone(), count)
+    }
+
+    /// Rewire the witness `w` to the witness `first` of equal value
+    fn replace(&mut self, w: Witness, first: Witness) {
+        let (rows, _) = self.rows_of(w);
+
+        for i in rows {
+            if let Some(mut row) = self.rows[i] {
+                [&mut row.w_a, &mut row.w_b, &mut row.w_o, &mut row.w_d]
+                    .into_iter()
+                    .filter(|t| **t == w)
+                    .for_each(|t| *t = first);
+
+                self.set_row(i, Some(row));
+            }
+        }
+    }
+
+    /// Whether the row holds a plain arithmetic gate
+    fn is_arithmetic(&self, i: usize) -> bool {
+        let row = match &self.rows[i] {
+            Some(row) if !self.fixed.contains(&i) => row,
+            _ => return false,
+        };
+
+        row.q_arith == BlsScalar::one()
+            && row.q_range == BlsScalar::zero()
+            && row.q_logic == BlsScalar::zero()
+            && row.q_fixed_group_add == BlsScalar::zero()
+            && row.q_variable_group_add == BlsScalar::zero()
+            && row.q_lookup == BlsScalar::zero()
+    }
+
+    /// Whether the arithmetic gate of the row may be rewritten or removed,
+    /// ignoring the row `skip`
+    fn is_free(&self, i: usize, skip: usize) -> bool {
+        let previous =
+            (0..i).rev().find(|j| *j != skip && self.rows[*j].is_some());
+
+        i >= self.pinned
+            && self.is_arithmetic(i)
+            && !previous.is_some_and(|j| self.reads_next.contains(&j))
+    }
+
+    /// Value of the witness, if it is a constant defined by another row than
+    /// `i`
+    fn constant(&self, w: &Witness, i: usize) -> Option<BlsScalar> {
+        self.constants
+            .get(w)
+            .filter(|(_, definition)| *definition != i)
+            .map(|(v, _)| *v)
+    }
+
+    /// Linear combination of the gate of the row, if the gate is arithmetic
+    /// and linear once its constant witnesses are substituted
+    fn linear(&self, i: usize) -> Option<Linear> {
+        if !self.is_arithmetic(i) {
+            return None;
+        }
+
+        let row = self.rows[i].as_ref()?;
+
+        let mut linear = Linear {
+            terms: Vec::with_capacity(4),
+            constant: row.q_c,
+        };
+
+        if row.q_m != BlsScalar::zero() {
+            match (self.constant(&row.w_a, i), self.constant(&row.w_b, i)) {
+                (Some(a), Some(b)) => linear.constant += row.q_m * a * b,
+                (Some(a), None) => linear.add(row.w_b, row.q_m * a),
+                (None, Some(b)) => linear.add(row.w_a, row.q_m * b),
+                (None, None) => return None,
+            }
+        }
+
+        let wires = [
+            (row.w_a, row.q_l),
+            (row.w_b, row.q_r),
+            (row.w_o, row.q_o),
+            (row.w_d, row.q_d),
+        ];
+
+        for (w, q) in wires {
+            match self.constant(&w, i) {
+                Some(v) => linear.constant += q * v,
+                None => linear.add(w, q),
+            }
+        }
+
+        Some(linear.normalized())
+    }
+
+    /// Whether the gate of the row has a constant witness to fold
+    fn has_constants(&self, i: usize) -> bool {
+        let row = match &self.rows[i] {
+            Some(row) => row,
+            None => return false,
+        };
+
+        let multiplied = row.q_m != BlsScalar::zero();
+        let wires = [
+            (row.w_a, row.q_l != BlsScalar::zero() || multiplied),
+            (row.w_b, row.q_r != BlsScalar::zero() || multiplied),
+            (row.w_o, row.q_o != BlsScalar::zero()),
+            (row.w_d, row.q_d != BlsScalar::zero()),
+        ];
+
+        wires
+            .iter()
+            .any(|(w, used)| *used && self.constant(w, i).is_some())
+    }
+
+    /// Record the constant defined by the row, removing the row if the
+    /// constant is a duplicate or if the gate only involves constants, and
+    /// fold the constants into the constant selector of the gate otherwise
+    fn fold_constants(&mut self, i: usize) {
+        let linear = match self.linear(i) {
+            Some(linear) => linear,
+            None => return,
+        };
+
+        match linear.terms.as_slice() {
+            [] if linear.constant == BlsScalar::zero() => {
+                if self.is_free(i, i) {
+                    self.set_row(i, None);
+                }
+                return;
+            }
+
+            [(w, c)] if !self.constants.contains_key(w) => {
+                let w = *w;
+                let v = -linear.constant * c.invert().expect("non zero");
+
+                self.constants.insert(w, (v, i));
+                match self.values.get(&v).copied() {
+                    // the duplicated constant is equal to the first one, so
+                    // the gates reading the next row are rewired as well
+                    Some(first) if self.is_free(i, i) => {
+                        self.set_row(i, None);
+                        self.replace(w, first);
+                        return;
+                    }
+                    Some(_) => (),
+                    None => {
+                        self.values.insert(v, w);
+                    }
+                }
+
+                if is_tracked(&w) {
+                    let (rows, _) = self.rows_of(w);
+                    rows.into_iter().for_each(|r| self.queue_row(r));
+                }
+            }
+
+            _ => (),
+        }
+
+        if self.has_constants(i) && self.is_free(i, i) {
+            let row = linear.arithmetization();
+            if row.is_some() && row != self.rows[i] {
+                self.set_row(i, row);
+            }
+        }
+    }
+
+    /// Eliminate the witness if it is only used by a gate that defines it,
+    /// removing the gate, or if it is shared by exactly two linear gates
+    /// whose combination fits the wires of a single gate, merging the gates
+    fn eliminate(&mut self, w: Witness) {
+        let (rows, count) = self.rows_of(w);
+
+        match (rows.as_slice(), count) {
+            ([i], 1) => self.remove_dead(w, *i),
+            ([r1, r2], 2) => self.merge_linear(w, *r1, *r2),
+            _ => (),
+        }
+    }
+
+    fn remove_dead(&mut self, w: Witness, i: usize) {
+        if !self.is_free(i, i) {
+            return;
+        }
+
+        let row = self.rows[i].as_ref().expect("the row is arithmetic");
+
+        // the witness must be solvable from the other wires
+        let linear = row.q_m == BlsScalar::zero();
+        let coefficient = match w {
+            w if w == row.w_a && linear => row.q_l,
+            w if w == row.w_b && linear => row.q_r,
+            w if w == row.w_o => row.q_o,
+            w if w == row.w_d => row.q_d,
+            _ => BlsScalar::zero(),
+        };
+
+        if coefficient != BlsScalar::zero() {
+            self.set_row(i, None);
+        }
+    }
+
+    fn merge_linear(&mut self, t: Witness, r1: usize, r2: usize) {
+        let merge = [(r1, r2), (r2, r1)].into_iter().find_map(|(r1, r2)| {
+            if !self.is_free(r1, r1) || !self.is_free(r2, r1) {
+                return None;
+            }
+
+            let l1 = self.linear(r1)?;
+            let l2 = self.linear(r2)?;
+
+            let c1 = l1.coeff(t);
+            let c2 = l2.coeff(t);
+            if c1 == BlsScalar::zero() || c2 == BlsScalar::zero() {
+                return None;
+            }
+
+            let factor = -c2 * c1.invert().expect("non zero");
+            let merged = l2.combined(&l1, factor);
+
+            merged.arithmetization().map(|row| (r1, r2, row))
+        });
+
+        if let Some((r1, r2, row)) = merge {
+            self.set_row(r1, None);
+            self.set_row(r2, Some(row));
+        }
+    }
+}
+
+fn wires(row: &Arithmetization) -> [Witness; 4] {
+    [row.w_a, row.w_b, row.w_o, row.w_d]
+}
+
+// the zero and one witnesses are wired to the initialization of the builder
+// and to most of the gates, so they are never eliminated
+fn is_tracked(w: &Witness) -> bool {
+    *w != Witness::ZERO && *w != Witness::ONE
+}
+
+impl Builder {
+    /// Optimize the gates of the circuit
+    ///
+    /// Deduplicate the constant witnesses, fold the constants into the gates
+    /// using them, remove the gates involving constants only, merge chained
+    /// linear combinations into single width-4 gates and remove the gates
+    /// whose outputs aren't used. The witnesses are kept, so the gates of
+    /// the optimized circuit index the same values.
+    pub(crate) fn optimize(&mut self) {
+        let mut optimizer = Optimizer::new(self);
+
+        optimizer.run();
+
+        // the indexes of the rows that are kept
+        let mut indexes = vec![None; optimizer.rows.len()];
+        let mut constraints = Vec::with_capacity(optimizer.rows.len());
+
+        for (i, row) in optimizer.rows.into_iter().enumerate() {
+            if let Some(row) = row {
+                indexes[i] = Some(constraints.len());
+                constraints.push(row);
+            }
+        }
+
+        let index = |i: usize| indexes[i].expect("the row is kept");
+
+        self.public_inputs = self
+            .public_inputs
+            .iter()
+            .map(|(i, pi)| (index(*i), *pi))
+            .collect();
+
+        self.widgets
+            .iter_mut()
+            .flat_map(|(_, rows)| rows)
+            .for_each(|i| *i = index(*i));
+
+        let mut perm = Permutation::with_capacity(self.witnesses.len());
+        self.witnesses.iter().for_each(|_| {
+            perm.new_witness();
+        });
+        constraints.iter().enumerate().for_each(|(i, row)| {
+            perm.add_witnesses_to_map(row.w_a, row.w_b, row.w_o, row.w_d, i)
+        });
+
+        self.constraints = constraints;
+        self.perm = perm;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::composer::Circuit;
+    use crate::error::Error;
+    use dusk_jubjub::{JubJubScalar, GENERATOR_EXTENDED};
+
+    // run the circuit, returning the builder before and after the
+    // optimization
+    fn run<C: Circuit>(circuit: &C) -> (Builder, Builder) {
+        let mut builder = Builder::initialized();
+        circuit.circuit(&mut builder).expect("circuit should run");
+
+        let mut optimized = builder.clone();
+        optimized.optimize();
+
+        (builder, optimized)
+    }
+
+    fn is_satisfied(builder: &Builder) -> bool {
+        let size = builder.constraints().next_power_of_two();
+        builder.check_constraints(size).is_ok()
+    }
+
+    // the witnesses are public, so the checks involving them are kept
+    struct ConstantCircuit(u64);
+
+    impl Circuit for ConstantCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_x = composer.append_public(self.0);
+            let w_five = composer.append_constant(5);
+            let w_other = composer.append_constant(5);
+            let w_one = composer.append_constant(1);
+
+            let constraint = Constraint::new().left(1).right(1);
+            let w_s = composer.gate_add(constraint.a(w_x).b(w_five));
+            let w_t = composer.gate_add(constraint.a(w_s).b(w_other));
+            let w_t =
+                composer.gate_mul(Constraint::new().mult(1).a(w_t).b(w_one));
+
+            composer.assert_equal_constant(w_t, 13, None);
+
+            Ok(())
+        }
+    }
+
+    impl Default for ConstantCircuit {
+        fn default() -> Self {
+            Self(3)
+        }
+    }
+
+    #[test]
+    fn fold_constants() {
+        let pinned = Builder::initialized().constraints();
+
+        let (builder, optimized) = run(&ConstantCircuit(3));
+        assert!(is_satisfied(&builder));
+        assert!(is_satisfied(&optimized));
+
+        // the circuit boils down to `x = 3`
+        assert_eq!(builder.constraints(), pinned + 8);
+        assert_eq!(optimized.constraints(), pinned + 2);
+
+        let (builder, optimized) = run(&ConstantCircuit(4));
+        assert!(!is_satisfied(&builder));
+        assert!(!is_satisfied(&optimized));
+    }
+
+    #[derive(Default)]
+    struct LinearCircuit([u64; 4]);
+
+    impl Circuit for LinearCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let [w_a, w_b, w_c, w_d] =
+                self.0.map(|v| composer.append_public(v));
+
+            let constraint = Constraint::new().left(1).right(1);
+            let w_t = composer.gate_add(constraint.a(w_a).b(w_b));
+            let w_u = composer.gate_add(constraint.a(w_t).b(w_c));
+            composer.assert_equal(w_u, w_d);
+
+            // unused outputs
+            let w_v =
+                composer.gate_mul(Constraint::new().mult(1).a(w_a).b(w_b));
+            composer.gate_add(constraint.a(w_v).b(w_c));
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn merge_linear() {
+        let (builder, optimized) = run(&LinearCircuit([1, 2, 3, 6]));
+        assert!(is_satisfied(&builder));
+        assert!(is_satisfied(&optimized));
+
+        // `a + b + c = d` fits a single gate
+        assert_eq!(builder.constraints(), optimized.constraints() + 4);
+
+        let (builder, optimized) = run(&LinearCircuit([1, 2, 3, 7]));
+        assert!(!is_satisfied(&builder));
+        assert!(!is_satisfied(&optimized));
+    }
+
+    // `z` is the constant `3` once the gates of `t` are merged, a duplicate
+    // of `c` whose gate is removed since `c` isn't used anywhere else
+    struct DuplicateCircuit {
+        c: BlsScalar,
+        z: BlsScalar,
+        t: BlsScalar,
+        y: BlsScalar,
+    }
+
+    impl DuplicateCircuit {
+        fn new(c: u64, z: u64, t: u64) -> Self {
+            let z = BlsScalar::from(z);
+            let y = BlsScalar::from(6) * z.invert().expect("non zero");
+
+            Self {
+                c: BlsScalar::from(c),
+                z,
+                t: BlsScalar::from(t),
+                y,
+            }
+        }
+    }
+
+    impl Default for DuplicateCircuit {
+        fn default() -> Self {
+            Self::new(3, 3, 7)
+        }
+    }
+
+    impl Circuit for DuplicateCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_c = composer.append_witness(self.c);
+            composer.assert_equal_constant(w_c, 3, None);
+
+            let w_z = composer.append_witness(self.z);
+            let w_t = composer.append_witness(self.t);
+            let w_y = composer.append_witness(self.y);
+
+            let constraint = Constraint::new().right(1).a(w_z).b(w_t);
+            let constraint = constraint.left(1).constant(-BlsScalar::from(10));
+            composer.append_gate(constraint);
+            let constraint = Constraint::new().right(1).a(w_z).b(w_t);
+            let constraint = constraint.left(2).constant(-BlsScalar::from(13));
+            composer.append_gate(constraint);
+
+            // gates with public inputs are never rewritten
+            let constraint = Constraint::new().mult(1).a(w_z).b(w_y);
+            composer.append_gate(constraint.public(-BlsScalar::from(6)));
+            composer.component_range::<2>(w_z);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn removed_constants() {
+        let (builder, optimized) = run(&DuplicateCircuit::new(3, 3, 7));
+        assert!(is_satisfied(&builder));
+        assert!(is_satisfied(&optimized));
+
+        // `z` must not be rewired to `c`, which is no longer constrained
+        let (builder, optimized) = run(&DuplicateCircuit::new(5, 5, 5));
+        assert!(!is_satisfied(&builder));
+        assert!(!is_satisfied(&optimized));
+    }
+
+    #[derive(Default)]
+    struct GatesCircuit;
+
+    impl Circuit for GatesCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::from(0xa5));
+            let w_b = composer.append_witness(BlsScalar::from(0x3c));
+
+            composer.component_range::<4>(w_a);
+            let w_xor = composer.append_logic_xor::<4>(w_a, w_b);
+            let w_expected = composer.append_constant(0xa5 ^ 0x3c);
+            composer.assert_equal(w_xor, w_expected);
+
+            let scalar = JubJubScalar::from(0xc0ffeeu64);
+            let w_scalar = composer.append_witness(scalar);
+            let w_point = composer
+                .component_mul_generator(w_scalar, GENERATOR_EXTENDED)?;
+            let w_point = composer.component_add_point(w_point, w_point);
+            let point = GENERATOR_EXTENDED * scalar;
+            composer.assert_equal_public_point(w_point, point + point);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn keep_next_rows() {
+        let (builder, optimized) = run(&GatesCircuit);
+        assert!(is_satisfied(&builder));
+        assert!(is_satisfied(&optimized));
+
+        assert!(optimized.constraints() < builder.constraints());
+        assert_eq!(optimized.public_inputs(), builder.public_inputs());
+    }
+}
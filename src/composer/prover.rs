@@ -4,39 +4,93 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::ops;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Serializable};
 use ff::Field;
-use merlin::Transcript;
 use rand_core::{CryptoRng, RngCore};
 
 use crate::commitment_scheme::CommitKey;
 use crate::error::Error;
 use crate::fft::{EvaluationDomain, Polynomial};
-use crate::proof_system::proof::Proof;
+use crate::lookup;
+use crate::proof_system::proof::{LookupProof, Proof};
 use crate::proof_system::{
-    linearization_poly, quotient_poly, ProverKey, VerifierKey,
+    linearization_poly, quotient_poly, widget, ProverKey, VerifierKey,
 };
-use crate::transcript::TranscriptProtocol;
+use crate::runtime::Observer;
+use crate::transcript::{Merlin, TranscriptExt, TranscriptProtocol};
 
-use super::{Builder, Circuit, Composer};
+use super::{Builder, Circuit, CustomWidget};
+
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+
+/// Blinding scalars of a proof
+///
+/// The scalars are sampled before the rounds of the proof, in the order the
+/// rounds consume them, so the rounds don't depend on the random number
+/// generator and can run on the pool of the prover.
+struct Blinding(alloc::vec::IntoIter<BlsScalar>);
+
+impl Blinding {
+    /// Scalars blinding a hiding proof: two for each wire polynomial, three
+    /// for the permutation polynomial and three for the quotient polynomial
+    const SCALARS: usize = 4 * 2 + 3 + 3;
+
+    /// Scalars blinding the lookup argument of a hiding proof: two for the
+    /// query and the second sorted polynomial, three for the first sorted
+    /// polynomial and three for the lookup permutation polynomial
+    const LOOKUP_SCALARS: usize = 2 * 2 + 3 + 3;
+
+    fn new<R>(rng: &mut R, hiding: bool, lookup: bool) -> Self
+    where
+        R: RngCore + CryptoRng,
+    {
+        let scalars = match (hiding, lookup) {
+            (true, true) => Self::SCALARS + Self::LOOKUP_SCALARS,
+            (true, false) => Self::SCALARS,
+            (false, _) => 0,
+        };
+
+        let scalars: Vec<_> =
+            (0..scalars).map(|_| BlsScalar::random(&mut *rng)).collect();
+
+        Self(scalars.into_iter())
+    }
+
+    fn next(&mut self) -> BlsScalar {
+        self.0
+            .next()
+            .expect("the blinding scalars are sampled up front")
+    }
+}
 
 /// Turbo Prover with processed keys
+///
+/// The challenges of the proofs are derived from the transcript `T`, which is
+/// [`Merlin`] by default.
 #[derive(Clone)]
-pub struct Prover {
+pub struct Prover<T = Merlin> {
     label: Vec<u8>,
     pub(crate) prover_key: ProverKey,
     pub(crate) commit_key: CommitKey,
     pub(crate) verifier_key: VerifierKey,
-    pub(crate) transcript: Transcript,
+    pub(crate) custom_prover_key: widget::custom::ProverKey,
+    pub(crate) custom_verifier_key: widget::custom::VerifierKey,
+    pub(crate) transcript: T,
     pub(crate) size: usize,
     pub(crate) constraints: usize,
+    check_constraints: bool,
+    #[cfg(feature = "std")]
+    pool: Option<Arc<rayon::ThreadPool>>,
+    observers: Vec<Arc<dyn Observer>>,
 }
 
-impl ops::Deref for Prover {
+impl<T> ops::Deref for Prover<T> {
     type Target = ProverKey;
 
     fn deref(&self) -> &Self::Target {
@@ -44,7 +98,7 @@ impl ops::Deref for Prover {
     }
 }
 
-impl Prover {
+impl<T: TranscriptProtocol> Prover<T> {
     pub(crate) fn new(
         label: Vec<u8>,
         prover_key: ProverKey,
@@ -53,18 +107,129 @@ impl Prover {
         size: usize,
         constraints: usize,
     ) -> Self {
-        let transcript =
-            Transcript::base(label.as_slice(), &verifier_key, constraints);
+        let transcript = T::base(label.as_slice(), &verifier_key, constraints);
 
         Self {
             label,
             prover_key,
             commit_key,
             verifier_key,
+            custom_prover_key: widget::custom::ProverKey::default(),
+            custom_verifier_key: widget::custom::VerifierKey::default(),
             transcript,
             size,
             constraints,
+            check_constraints: cfg!(debug_assertions),
+            #[cfg(feature = "std")]
+            pool: None,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Set the keys of the custom gates of the circuit
+    pub(crate) fn with_widgets(
+        mut self,
+        custom_prover_key: widget::custom::ProverKey,
+        custom_verifier_key: widget::custom::VerifierKey,
+    ) -> Self {
+        custom_verifier_key.seed_transcript(&mut self.transcript);
+
+        self.custom_prover_key = custom_prover_key;
+        self.custom_verifier_key = custom_verifier_key;
+
+        self
+    }
+
+    /// Use the transcript `U` to derive the challenges of the proofs
+    ///
+    /// The proofs can only be verified by a [`Verifier`](super::Verifier)
+    /// using the same transcript.
+    pub fn with_transcript<U: TranscriptProtocol>(self) -> Prover<U> {
+        let prover = Prover::new(
+            self.label,
+            self.prover_key,
+            self.commit_key,
+            self.verifier_key,
+            self.size,
+            self.constraints,
+        )
+        .with_widgets(self.custom_prover_key, self.custom_verifier_key);
+
+        Prover {
+            check_constraints: self.check_constraints,
+            #[cfg(feature = "std")]
+            pool: self.pool,
+            observers: self.observers,
+            ..prover
+        }
+    }
+
+    /// Check the constraints of the circuit before proving it
+    ///
+    /// With the check, [`Self::prove`] evaluates the gates of every row of
+    /// the circuit before the first commitment, and fails with
+    /// [`Error::UnsatisfiedConstraint`] on the first one that isn't satisfied
+    /// by the witnesses, instead of computing an invalid quotient. The check
+    /// is on by default in debug builds only.
+    pub fn with_constraint_check(mut self, check: bool) -> Self {
+        self.check_constraints = check;
+        self
+    }
+
+    /// Report the events of the circuit of every proof to `observer`
+    ///
+    /// The circuit is appended to a new builder on every call to
+    /// [`Self::prove`], and `observer` receives its events from the
+    /// initialization of the builder to the
+    /// [`ProofFinished`](crate::runtime::RuntimeEvent::ProofFinished) event,
+    /// after the observers registered before it. The observers are shared
+    /// by the clones of the prover.
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Cap the number of threads of every proof
+    ///
+    /// The independent commitments, IFFTs and coset FFTs of the rounds of a
+    /// proof run concurrently. By default, they share the global pool of
+    /// rayon with anything else running in the process; with a cap, the
+    /// rounds of every call to [`Self::prove`] run on a pool of `threads`
+    /// threads owned by the prover, so many proofs can run side by side
+    /// without competing for all the cores. The pool is shared by the clones
+    /// of the prover.
+    ///
+    /// Return [`Error::InvalidThreadCount`] if `threads` is zero, and
+    /// [`Error::ThreadPoolBuildFailed`] if the threads can't be spawned.
+    #[cfg(feature = "std")]
+    pub fn with_threads(mut self, threads: usize) -> Result<Self, Error> {
+        if threads == 0 {
+            return Err(Error::InvalidThreadCount);
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|_| Error::ThreadPoolBuildFailed)?;
+
+        self.pool = Some(Arc::new(pool));
+
+        Ok(self)
+    }
+
+    /// Run `op` on the pool of the prover, or on the current thread without
+    /// a pool
+    fn install<OP, U>(&self, op: OP) -> U
+    where
+        OP: FnOnce() -> U + Send,
+        U: Send,
+    {
+        #[cfg(feature = "std")]
+        if let Some(pool) = &self.pool {
+            return pool.install(op);
         }
+
+        op()
     }
 
     /// adds blinding scalars to a witness vector
@@ -74,43 +239,107 @@ impl Prover {
     /// if hiding degree = 1: (b2*X^(n+1) + b1*X^n - b2*X - b1) + witnesses
     /// if hiding degree = 2: (b3*X^(n+2) + b2*X^(n+1) + b1*X^n - b3*X^2 - b2*X
     /// - b1) + witnesses
-    fn blind_poly<R>(
-        rng: &mut R,
+    ///
+    /// with `hiding degree + 1` blinding scalars
+    fn blind_poly(
         witnesses: &[BlsScalar],
-        hiding_degree: usize,
+        blinding_scalars: &[BlsScalar],
         domain: &EvaluationDomain,
-    ) -> Polynomial
-    where
-        R: RngCore + CryptoRng,
-    {
+    ) -> Polynomial {
         let mut w_vec_inverse = domain.ifft(witnesses);
 
-        for i in 0..hiding_degree + 1 {
-            let blinding_scalar = BlsScalar::random(&mut *rng);
-
+        for (i, blinding_scalar) in blinding_scalars.iter().enumerate() {
             w_vec_inverse[i] -= blinding_scalar;
-            w_vec_inverse.push(blinding_scalar);
+            w_vec_inverse.push(*blinding_scalar);
         }
 
         Polynomial::from_coefficients_vec(w_vec_inverse)
     }
 
+    /// blinds each of the witness vectors with its hiding degree, unless the
+    /// proofs are non-hiding
+    ///
+    /// the blinding scalars are taken in order, and the IFFTs run
+    /// concurrently
+    fn blind_polys<const N: usize>(
+        &self,
+        blinding: &mut Blinding,
+        witnesses: [(&[BlsScalar], usize); N],
+        domain: &EvaluationDomain,
+    ) -> [Polynomial; N] {
+        let hiding = self.prover_key.hiding;
+        let blinded = witnesses.map(|(witnesses, hiding_degree)| {
+            let blinding_scalars: Vec<_> = if hiding {
+                (0..hiding_degree + 1).map(|_| blinding.next()).collect()
+            } else {
+                Vec::new()
+            };
+
+            (witnesses, blinding_scalars)
+        });
+
+        #[cfg(not(feature = "std"))]
+        let blinded = blinded.iter();
+
+        #[cfg(feature = "std")]
+        let blinded = blinded.par_iter();
+
+        let polys: Vec<_> = blinded
+            .map(|(witnesses, blinding_scalars)| {
+                Self::blind_poly(witnesses, blinding_scalars, domain)
+            })
+            .collect();
+
+        polys
+            .try_into()
+            .expect("there is a polynomial per witness vector")
+    }
+
+    /// Whether the proofs hide the witnesses
+    ///
+    /// The proofs are non-hiding if the circuit was compiled with
+    /// [`Compiler::compile_non_hiding`](super::Compiler::compile_non_hiding).
+    pub fn is_hiding(&self) -> bool {
+        self.prover_key.hiding
+    }
+
+    /// Whether the gates of the proofs are optimized
+    ///
+    /// The gates are optimized if the circuit was compiled with
+    /// [`Compiler::compile_optimized`](super::Compiler::compile_optimized).
+    pub fn is_optimized(&self) -> bool {
+        self.prover_key.optimized
+    }
+
     fn prepare_serialize(
         &self,
-    ) -> (usize, Vec<u8>, Vec<u8>, [u8; VerifierKey::SIZE]) {
+    ) -> (usize, Vec<u8>, Vec<u8>, [u8; VerifierKey::SIZE], Vec<u8>) {
         let prover_key = self.prover_key.to_var_bytes();
         let commit_key = self.commit_key.to_raw_var_bytes();
         let verifier_key = self.verifier_key.to_bytes();
 
+        // the keys of the custom gates are only appended if there are any, so
+        // the provers without them keep their representation
+        let mut custom_keys = Vec::new();
+        if !self.custom_prover_key.widgets.is_empty() {
+            custom_keys.extend(self.custom_prover_key.to_var_bytes());
+            custom_keys.extend(self.custom_verifier_key.to_var_bytes());
+        }
+
         let label_len = self.label.len();
         let prover_key_len = prover_key.len();
         let commit_key_len = commit_key.len();
         let verifier_key_len = verifier_key.len();
+        let custom_keys_len = custom_keys.len();
 
-        let size =
-            48 + label_len + prover_key_len + commit_key_len + verifier_key_len;
+        let size = 48
+            + label_len
+            + prover_key_len
+            + commit_key_len
+            + verifier_key_len
+            + custom_keys_len;
 
-        (size, prover_key, commit_key, verifier_key)
+        (size, prover_key, commit_key, verifier_key, custom_keys)
     }
 
     /// Serialized size in bytes
@@ -120,7 +349,7 @@ impl Prover {
 
     /// Serialize the prover into bytes
     pub fn to_bytes(&self) -> Vec<u8> {
-        let (size, prover_key, commit_key, verifier_key) =
+        let (size, prover_key, commit_key, verifier_key, custom_keys) =
             self.prepare_serialize();
         let mut bytes = Vec::with_capacity(size);
 
@@ -142,85 +371,11 @@ impl Prover {
         bytes.extend(prover_key);
         bytes.extend(commit_key);
         bytes.extend(verifier_key);
+        bytes.extend(custom_keys);
 
         bytes
     }
 
-    /// Attempt to deserialize the prover from bytes generated via
-    /// [`Self::to_bytes`]
-    pub fn try_from_bytes<B>(bytes: B) -> Result<Self, Error>
-    where
-        B: AsRef<[u8]>,
-    {
-        let mut bytes = bytes.as_ref();
-
-        if bytes.len() < 48 {
-            return Err(Error::NotEnoughBytes);
-        }
-
-        let label_len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let label_len = u64::from_be_bytes(label_len) as usize;
-        bytes = &bytes[8..];
-
-        let prover_key_len =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let prover_key_len = u64::from_be_bytes(prover_key_len) as usize;
-        bytes = &bytes[8..];
-
-        let commit_key_len =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let commit_key_len = u64::from_be_bytes(commit_key_len) as usize;
-        bytes = &bytes[8..];
-
-        let verifier_key_len =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let verifier_key_len = u64::from_be_bytes(verifier_key_len) as usize;
-        bytes = &bytes[8..];
-
-        let size = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let size = u64::from_be_bytes(size) as usize;
-        bytes = &bytes[8..];
-
-        let constraints =
-            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
-        let constraints = u64::from_be_bytes(constraints) as usize;
-        bytes = &bytes[8..];
-
-        if bytes.len()
-            < label_len + prover_key_len + commit_key_len + verifier_key_len
-        {
-            return Err(Error::NotEnoughBytes);
-        }
-
-        let label = &bytes[..label_len];
-        bytes = &bytes[label_len..];
-
-        let prover_key = &bytes[..prover_key_len];
-        bytes = &bytes[prover_key_len..];
-
-        let commit_key = &bytes[..commit_key_len];
-        bytes = &bytes[commit_key_len..];
-
-        let verifier_key = &bytes[..verifier_key_len];
-
-        let label = label.to_vec();
-        let prover_key = ProverKey::from_slice(prover_key)?;
-
-        // Safety: checked len
-        let commit_key = unsafe { CommitKey::from_slice_unchecked(commit_key) };
-
-        let verifier_key = VerifierKey::from_slice(verifier_key)?;
-
-        Ok(Self::new(
-            label,
-            prover_key,
-            commit_key,
-            verifier_key,
-            size,
-            constraints,
-        ))
-    }
-
     /// Prove the circuit
     pub fn prove<C, R>(
         &self,
@@ -231,12 +386,37 @@ impl Prover {
         C: Circuit,
         R: RngCore + CryptoRng,
     {
-        let prover = Builder::prove(self.constraints, circuit)?;
+        let prover = Builder::prove_observed(
+            self.constraints,
+            circuit,
+            &self.observers,
+            self.prover_key.optimized,
+        )?;
+
+        if self.check_constraints {
+            prover.check_constraints(self.size)?;
+        }
+
+        let blinding = Blinding::new(
+            rng,
+            self.prover_key.hiding,
+            self.prover_key.lookup.is_some(),
+        );
+
+        self.install(|| self.prove_rounds(&prover, blinding))
+    }
+
+    /// Run the rounds of the proof of the circuit appended to `prover`
+    fn prove_rounds(
+        &self,
+        prover: &Builder,
+        mut blinding: Blinding,
+    ) -> Result<(Proof, Vec<BlsScalar>), Error> {
+        let commit_key = &self.commit_key;
 
-        let constraints = self.constraints;
         let size = self.size;
 
-        let domain = EvaluationDomain::new(constraints)?;
+        let domain = EvaluationDomain::new(size)?;
 
         let mut transcript = self.transcript.clone();
 
@@ -266,17 +446,22 @@ impl Prover {
             d_w_scalar[i] = prover[c.w_d];
         });
 
-        let a_w_poly = Self::blind_poly(rng, &a_w_scalar, 1, &domain);
-        let b_w_poly = Self::blind_poly(rng, &b_w_scalar, 1, &domain);
-        let o_w_poly = Self::blind_poly(rng, &o_w_scalar, 1, &domain);
-        let d_w_poly = Self::blind_poly(rng, &d_w_scalar, 1, &domain);
+        let [a_w_poly, b_w_poly, o_w_poly, d_w_poly] = self.blind_polys(
+            &mut blinding,
+            [
+                (a_w_scalar.as_slice(), 1),
+                (b_w_scalar.as_slice(), 1),
+                (o_w_scalar.as_slice(), 1),
+                (d_w_scalar.as_slice(), 1),
+            ],
+            &domain,
+        );
 
         // commit to wire polynomials
         // ([a(x)]_1, [b(x)]_1, [c(x)]_1, [d(x)]_1)
-        let a_w_poly_commit = self.commit_key.commit(&a_w_poly)?;
-        let b_w_poly_commit = self.commit_key.commit(&b_w_poly)?;
-        let o_w_poly_commit = self.commit_key.commit(&o_w_poly)?;
-        let d_w_poly_commit = self.commit_key.commit(&d_w_poly)?;
+        let [a_w_poly_commit, b_w_poly_commit, o_w_poly_commit, d_w_poly_commit] =
+            commit_key
+                .commit_each([&a_w_poly, &b_w_poly, &o_w_poly, &d_w_poly])?;
 
         // Add wire polynomial commitments to transcript
         transcript.append_commitment(b"a_w", &a_w_poly_commit);
@@ -284,12 +469,80 @@ impl Prover {
         transcript.append_commitment(b"c_w", &o_w_poly_commit);
         transcript.append_commitment(b"d_w", &d_w_poly_commit);
 
+        // lookup compression challenge, for circuits with lookups
+        let lookup_vecs = self.prover_key.lookup.as_ref().map(|lookup_key| {
+            let zeta = transcript.challenge_scalar(b"zeta");
+            transcript.append_scalar(b"zeta", &zeta);
+
+            // compress the table of the prover key, so the circuit can't
+            // replace the table it was compiled with, and the queries, padding
+            // the rows without a lookup gate with the first row of the table
+            let table = domain.fft(&lookup_key.compute_table_poly(&zeta));
+
+            let mut f_scalar = vec![table[0]; size];
+            prover.constraints.iter().enumerate().for_each(|(i, c)| {
+                if c.q_lookup != BlsScalar::zero() {
+                    f_scalar[i] = lookup::compress(
+                        &a_w_scalar[i],
+                        &b_w_scalar[i],
+                        &o_w_scalar[i],
+                        &c.q_c,
+                        &zeta,
+                    );
+                }
+            });
+
+            let (h_1_scalar, h_2_scalar) =
+                lookup::compute_sorted_vecs(&f_scalar, &table);
+
+            (zeta, table, f_scalar, h_1_scalar, h_2_scalar)
+        });
+
+        let lookup_polys = match &lookup_vecs {
+            Some((_, _, f_scalar, h_1_scalar, h_2_scalar)) => {
+                let [f_poly, h_1_poly, h_2_poly] = self.blind_polys(
+                    &mut blinding,
+                    [
+                        (f_scalar.as_slice(), 1),
+                        (h_1_scalar.as_slice(), 2),
+                        (h_2_scalar.as_slice(), 1),
+                    ],
+                    &domain,
+                );
+
+                // commit to the query and sorted polynomials
+                let [f_poly_commit, h_1_poly_commit, h_2_poly_commit] =
+                    commit_key.commit_each([&f_poly, &h_1_poly, &h_2_poly])?;
+
+                transcript.append_commitment(b"f", &f_poly_commit);
+                transcript.append_commitment(b"h_1", &h_1_poly_commit);
+                transcript.append_commitment(b"h_2", &h_2_poly_commit);
+
+                Some((
+                    [f_poly, h_1_poly, h_2_poly],
+                    [f_poly_commit, h_1_poly_commit, h_2_poly_commit],
+                ))
+            }
+            None => None,
+        };
+
         // round 2
         // permutation challenges
         let beta = transcript.challenge_scalar(b"beta");
         transcript.append_scalar(b"beta", &beta);
 
         let gamma = transcript.challenge_scalar(b"gamma");
+
+        // lookup permutation challenges
+        let delta_epsilon = lookup_vecs.as_ref().map(|_| {
+            let delta = transcript.challenge_scalar(b"delta");
+            transcript.append_scalar(b"delta", &delta);
+
+            let epsilon = transcript.challenge_scalar(b"epsilon");
+
+            (delta, epsilon)
+        });
+
         let sigma = [
             &self.prover_key.permutation.s_sigma_1.0,
             &self.prover_key.permutation.s_sigma_2.0,
@@ -306,10 +559,38 @@ impl Prover {
             .perm
             .compute_permutation_vec(&domain, wires, &beta, &gamma, sigma);
 
-        let z_poly = Self::blind_poly(rng, &permutation, 2, &domain);
-        let z_poly_commit = self.commit_key.commit(&z_poly)?;
+        let [z_poly] = self.blind_polys(
+            &mut blinding,
+            [(permutation.as_slice(), 2)],
+            &domain,
+        );
+
+        let [z_poly_commit] = commit_key.commit_each([&z_poly])?;
         transcript.append_commitment(b"z", &z_poly_commit);
 
+        let lookup_perm = match lookup_vecs.as_ref().zip(delta_epsilon) {
+            Some((
+                (_, table, f_scalar, h_1_scalar, h_2_scalar),
+                (delta, epsilon),
+            )) => {
+                let lookup_permutation = lookup::compute_permutation_vec(
+                    f_scalar, table, h_1_scalar, h_2_scalar, &delta, &epsilon,
+                );
+
+                let [p_poly] = self.blind_polys(
+                    &mut blinding,
+                    [(lookup_permutation.as_slice(), 2)],
+                    &domain,
+                );
+
+                let [p_poly_commit] = commit_key.commit_each([&p_poly])?;
+                transcript.append_commitment(b"p", &p_poly_commit);
+
+                Some((p_poly, p_poly_commit))
+            }
+            None => None,
+        };
+
         // round 3
         // compute quotient challenge alpha
         let alpha = transcript.challenge_scalar(b"alpha");
@@ -321,12 +602,29 @@ impl Prover {
             transcript.challenge_scalar(b"fixed base separation challenge");
         let var_base_sep_challenge =
             transcript.challenge_scalar(b"variable base separation challenge");
-
-        // compute public inputs polynomial
-        let pi_poly = domain.ifft(&dense_public_inputs);
-        let pi_poly = Polynomial::from_coefficients_vec(pi_poly);
+        let lookup_sep_challenge = lookup_vecs.as_ref().map(|_| {
+            transcript.challenge_scalar(b"lookup separation challenge")
+        });
+        let custom_sep_challenges = self
+            .custom_verifier_key
+            .separation_challenges(&mut transcript);
+
+        // gather the lookup challenges, for circuits with lookups
+        let lookup_challenges = lookup_vecs
+            .as_ref()
+            .zip(lookup_sep_challenge)
+            .zip(delta_epsilon)
+            .map(|(((zeta, ..), lookup_sep_challenge), (delta, epsilon))| {
+                (lookup_sep_challenge, delta, epsilon, *zeta)
+            });
+        let lookup = lookup_polys
+            .as_ref()
+            .zip(lookup_perm.as_ref())
+            .zip(lookup_challenges.as_ref());
 
         // compute quotient polynomial
+        let prover_key = &self.prover_key;
+        let custom_prover_key = &self.custom_prover_key;
         let wires = (&a_w_poly, &b_w_poly, &o_w_poly, &d_w_poly);
         let args = &(
             alpha,
@@ -337,11 +635,24 @@ impl Prover {
             fixed_base_sep_challenge,
             var_base_sep_challenge,
         );
+        // compute public inputs polynomial
+        let pi_poly = domain.ifft(&dense_public_inputs);
+        let pi_poly = Polynomial::from_coefficients_vec(pi_poly);
+
         let t_poly = quotient_poly::compute(
             &domain,
-            &self.prover_key,
+            prover_key,
+            (custom_prover_key, &custom_sep_challenges),
             &z_poly,
             wires,
+            lookup.map(
+                |(
+                    (([f_poly, h_1_poly, h_2_poly], _), (p_poly, _)),
+                    challenges,
+                )| {
+                    ((f_poly, h_1_poly, h_2_poly, p_poly), challenges)
+                },
+            ),
             &pi_poly,
             args,
         )?;
@@ -349,29 +660,39 @@ impl Prover {
         // split quotient polynomial into 4 degree `n` polynomials
         let domain_size = domain.size();
 
-        let mut t_low_vec = t_poly[0..domain_size].to_vec();
-        let mut t_mid_vec = t_poly[domain_size..2 * domain_size].to_vec();
-        let mut t_high_vec = t_poly[2 * domain_size..3 * domain_size].to_vec();
-        let mut t_4_vec = t_poly[3 * domain_size..].to_vec();
+        // without blinding factors, the degree of the quotient polynomial
+        // may be lower than 3n
+        let mut t_coeffs = t_poly.to_vec();
+        if t_coeffs.len() < 3 * domain_size {
+            t_coeffs.resize(3 * domain_size, BlsScalar::zero());
+        }
 
-        // select 3 blinding factors for the quotient splitted polynomials
-        let b_10 = BlsScalar::random(&mut *rng);
-        let b_11 = BlsScalar::random(&mut *rng);
-        let b_12 = BlsScalar::random(&mut *rng);
+        let mut t_low_vec = t_coeffs[0..domain_size].to_vec();
+        let mut t_mid_vec = t_coeffs[domain_size..2 * domain_size].to_vec();
+        let mut t_high_vec =
+            t_coeffs[2 * domain_size..3 * domain_size].to_vec();
+        let mut t_4_vec = t_coeffs[3 * domain_size..].to_vec();
 
-        // t_low'(X) + b_10*X^n
-        t_low_vec.push(b_10);
+        if self.prover_key.hiding {
+            // select 3 blinding factors for the quotient splitted polynomials
+            let b_10 = blinding.next();
+            let b_11 = blinding.next();
+            let b_12 = blinding.next();
 
-        // t_mid'(X) - b_10 + b_11*X^n
-        t_mid_vec[0] -= b_10;
-        t_mid_vec.push(b_11);
+            // t_low'(X) + b_10*X^n
+            t_low_vec.push(b_10);
 
-        // t_high'(X) - b_11 + b_12*X^n
-        t_high_vec[0] -= b_11;
-        t_high_vec.push(b_12);
+            // t_mid'(X) - b_10 + b_11*X^n
+            t_mid_vec[0] -= b_10;
+            t_mid_vec.push(b_11);
 
-        // t_4'(X) - b_12
-        t_4_vec[0] -= b_12;
+            // t_high'(X) - b_11 + b_12*X^n
+            t_high_vec[0] -= b_11;
+            t_high_vec.push(b_12);
+
+            // t_4'(X) - b_12
+            t_4_vec[0] -= b_12;
+        }
 
         let t_low_poly = Polynomial::from_coefficients_vec(t_low_vec);
         let t_mid_poly = Polynomial::from_coefficients_vec(t_mid_vec);
@@ -379,10 +700,13 @@ impl Prover {
         let t_4_poly = Polynomial::from_coefficients_vec(t_4_vec);
 
         // commit to split quotient polynomial
-        let t_low_commit = self.commit_key.commit(&t_low_poly)?;
-        let t_mid_commit = self.commit_key.commit(&t_mid_poly)?;
-        let t_high_commit = self.commit_key.commit(&t_high_poly)?;
-        let t_4_commit = self.commit_key.commit(&t_4_poly)?;
+        let [t_low_commit, t_mid_commit, t_high_commit, t_4_commit] =
+            commit_key.commit_each([
+                &t_low_poly,
+                &t_mid_poly,
+                &t_high_poly,
+                &t_4_poly,
+            ])?;
 
         // add quotient polynomial commitments to transcript
         transcript.append_commitment(b"t_low", &t_low_commit);
@@ -396,9 +720,14 @@ impl Prover {
 
         // round 5
         // compute linearization polynomial
+        let table_poly =
+            self.prover_key.lookup.as_ref().zip(lookup_challenges).map(
+                |(lookup_key, (.., zeta))| lookup_key.compute_table_poly(&zeta),
+            );
         let (r_poly, evaluations) = linearization_poly::compute(
             &domain,
             &self.prover_key,
+            (&self.custom_prover_key, &custom_sep_challenges),
             &(
                 alpha,
                 beta,
@@ -415,6 +744,20 @@ impl Prover {
             &d_w_poly,
             &t_poly,
             &z_poly,
+            lookup.zip(table_poly.as_ref()).map(
+                |(
+                    (
+                        (([f_poly, h_1_poly, h_2_poly], _), (p_poly, _)),
+                        challenges,
+                    ),
+                    table_poly,
+                )| {
+                    (
+                        (f_poly, h_1_poly, h_2_poly, table_poly, p_poly),
+                        challenges,
+                    )
+                },
+            ),
         );
 
         // add evaluations to transcript.
@@ -446,6 +789,25 @@ impl Prover {
         transcript.append_scalar(b"q_l_eval", &evaluations.proof.q_l_eval);
         transcript.append_scalar(b"q_r_eval", &evaluations.proof.q_r_eval);
         transcript.append_scalar(b"perm_eval", &evaluations.proof.perm_eval);
+        if let Some(lookup_evaluations) = &evaluations.lookup {
+            transcript.append_scalar(b"f_eval", &lookup_evaluations.f_eval);
+            transcript.append_scalar(b"h_1_eval", &lookup_evaluations.h_1_eval);
+            transcript.append_scalar(
+                b"h_1_next_eval",
+                &lookup_evaluations.h_1_next_eval,
+            );
+            transcript.append_scalar(b"h_2_eval", &lookup_evaluations.h_2_eval);
+            transcript
+                .append_scalar(b"table_eval", &lookup_evaluations.table_eval);
+            transcript.append_scalar(
+                b"table_next_eval",
+                &lookup_evaluations.table_next_eval,
+            );
+            transcript.append_scalar(
+                b"lookup_perm_eval",
+                &lookup_evaluations.lookup_perm_eval,
+            );
+        }
         transcript.append_scalar(b"t_eval", &evaluations.t_eval);
         transcript.append_scalar(b"r_eval", &evaluations.proof.r_poly_eval);
 
@@ -462,35 +824,78 @@ impl Prover {
 
         let quot = &abc + &d;
 
+        // the lookup polynomials are opened along with the others, for
+        // circuits with lookups
+        let lookup = lookup_polys
+            .zip(lookup_perm)
+            .zip(table_poly)
+            .zip(evaluations.lookup);
+
         // compute aggregate witness to polynomials evaluated at the evaluation
         // challenge z. The challenge v is selected inside
-        let aggregate_witness = self.commit_key.compute_aggregate_witness(
-            &[
-                quot,
-                r_poly,
-                a_w_poly.clone(),
-                b_w_poly.clone(),
-                o_w_poly,
-                d_w_poly.clone(),
-                self.prover_key.permutation.s_sigma_1.0.clone(),
-                self.prover_key.permutation.s_sigma_2.0.clone(),
-                self.prover_key.permutation.s_sigma_3.0.clone(),
-            ],
+        let mut polynomials = vec![
+            quot,
+            r_poly,
+            a_w_poly.clone(),
+            b_w_poly.clone(),
+            o_w_poly,
+            d_w_poly.clone(),
+            self.prover_key.permutation.s_sigma_1.0.clone(),
+            self.prover_key.permutation.s_sigma_2.0.clone(),
+            self.prover_key.permutation.s_sigma_3.0.clone(),
+            self.prover_key.arithmetic.q_c.0.clone(),
+        ];
+        let mut shifted_polynomials =
+            vec![z_poly, a_w_poly, b_w_poly, d_w_poly];
+
+        if let Some(((((polys, _), (p_poly, _)), table_poly), _)) = &lookup {
+            let [f_poly, h_1_poly, h_2_poly] = polys;
+
+            polynomials.extend([
+                f_poly.clone(),
+                h_1_poly.clone(),
+                h_2_poly.clone(),
+                table_poly.clone(),
+            ]);
+            shifted_polynomials.extend([
+                p_poly.clone(),
+                h_1_poly.clone(),
+                table_poly.clone(),
+            ]);
+        }
+
+        let aggregate_witness = commit_key.compute_aggregate_witness(
+            &polynomials,
             &z_challenge,
             &mut transcript,
         );
-        let w_z_chall_comm = self.commit_key.commit(&aggregate_witness)?;
 
         // compute aggregate witness to polynomials evaluated at the shifted
         // evaluation challenge
-        let shifted_aggregate_witness =
-            self.commit_key.compute_aggregate_witness(
-                &[z_poly, a_w_poly, b_w_poly, d_w_poly],
-                &(z_challenge * domain.group_gen),
-                &mut transcript,
-            );
-        let w_z_chall_w_comm =
-            self.commit_key.commit(&shifted_aggregate_witness)?;
+        let shifted_aggregate_witness = commit_key.compute_aggregate_witness(
+            &shifted_polynomials,
+            &(z_challenge * domain.group_gen),
+            &mut transcript,
+        );
+
+        // commit to both aggregate witnesses
+        let [w_z_chall_comm, w_z_chall_w_comm] = commit_key
+            .commit_each([&aggregate_witness, &shifted_aggregate_witness])?;
+
+        let lookup = lookup.map(
+            |(
+                (((_, [f_comm, h_1_comm, h_2_comm]), (_, p_comm)), _),
+                evaluations,
+            )| {
+                LookupProof {
+                    f_comm,
+                    h_1_comm,
+                    h_2_comm,
+                    p_comm,
+                    evaluations,
+                }
+            },
+        );
 
         let proof = Proof {
             a_comm: a_w_poly_commit,
@@ -509,8 +914,119 @@ impl Prover {
             w_z_chall_w_comm,
 
             evaluations: evaluations.proof,
+
+            lookup,
         };
 
         Ok((proof, public_inputs))
     }
 }
+
+impl Prover {
+    /// Attempt to deserialize the prover from bytes generated via
+    /// [`Self::to_bytes`]
+    ///
+    /// The prover uses the [`Merlin`] transcript, and can be switched to
+    /// another one with [`Self::with_transcript`]. The provers of circuits
+    /// with custom gates are deserialized with
+    /// [`Self::try_from_bytes_with_gates`].
+    pub fn try_from_bytes<B>(bytes: B) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        Self::try_from_bytes_with_gates(bytes, &[])
+    }
+
+    /// Attempt to deserialize the prover of a circuit with custom gates from
+    /// bytes generated via [`Self::to_bytes`]
+    ///
+    /// The selectors of the gates are part of the bytes, but their
+    /// constraints are not, so every gate of the circuit must be registered
+    /// in `gates`, as created by [`CustomWidget::new`]. Otherwise,
+    /// [`Error::UnregisteredCustomGate`] is returned.
+    pub fn try_from_bytes_with_gates<B>(
+        bytes: B,
+        gates: &[CustomWidget],
+    ) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        let mut bytes = bytes.as_ref();
+
+        if bytes.len() < 48 {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let label_len = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let label_len = u64::from_be_bytes(label_len) as usize;
+        bytes = &bytes[8..];
+
+        let prover_key_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let prover_key_len = u64::from_be_bytes(prover_key_len) as usize;
+        bytes = &bytes[8..];
+
+        let commit_key_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let commit_key_len = u64::from_be_bytes(commit_key_len) as usize;
+        bytes = &bytes[8..];
+
+        let verifier_key_len =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let verifier_key_len = u64::from_be_bytes(verifier_key_len) as usize;
+        bytes = &bytes[8..];
+
+        let size = <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let size = u64::from_be_bytes(size) as usize;
+        bytes = &bytes[8..];
+
+        let constraints =
+            <[u8; 8]>::try_from(&bytes[..8]).expect("checked len");
+        let constraints = u64::from_be_bytes(constraints) as usize;
+        bytes = &bytes[8..];
+
+        if bytes.len()
+            < label_len + prover_key_len + commit_key_len + verifier_key_len
+        {
+            return Err(Error::NotEnoughBytes);
+        }
+
+        let label = &bytes[..label_len];
+        bytes = &bytes[label_len..];
+
+        let prover_key = &bytes[..prover_key_len];
+        bytes = &bytes[prover_key_len..];
+
+        let commit_key = &bytes[..commit_key_len];
+        bytes = &bytes[commit_key_len..];
+
+        let verifier_key = &bytes[..verifier_key_len];
+        bytes = &bytes[verifier_key_len..];
+
+        let (custom_prover_key, custom_verifier_key) = match bytes.is_empty() {
+            true => Default::default(),
+            false => (
+                widget::custom::ProverKey::from_reader(&mut bytes, gates)?,
+                widget::custom::VerifierKey::from_reader(&mut bytes, gates)?,
+            ),
+        };
+
+        let label = label.to_vec();
+        let prover_key = ProverKey::from_slice(prover_key)?;
+
+        // Safety: checked len
+        let commit_key = unsafe { CommitKey::from_slice_unchecked(commit_key) };
+
+        let verifier_key = VerifierKey::from_slice(verifier_key)?;
+
+        Ok(Self::new(
+            label,
+            prover_key,
+            commit_key,
+            verifier_key,
+            size,
+            constraints,
+        )
+        .with_widgets(custom_prover_key, custom_verifier_key))
+    }
+}
@@ -0,0 +1,188 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Cost report of a circuit
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use dusk_bls12_381::BlsScalar;
+use hashbrown::HashMap;
+
+use crate::proof_system::widget::GateType;
+
+use super::{Builder, Composer};
+
+/// Call sites displayed by a [`CircuitReport`]
+const DISPLAYED_CALL_SITES: usize = 10;
+
+/// Source location appending constraints to a circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// Location of the call, as `path:line:column`
+    pub source: String,
+    /// Constraints appended from the location
+    pub constraints: usize,
+}
+
+/// Cost of a circuit, as reported by
+/// [`Compiler::report`](super::Compiler::report)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitReport {
+    /// Constraints of the circuit
+    pub constraints: usize,
+    /// Rows enabling each gate, for the gates enabled on at least one row
+    pub gates: Vec<(GateType, usize)>,
+    /// Public inputs of the circuit
+    pub public_inputs: usize,
+    /// Witnesses of the circuit, including the constants of the builder
+    pub witnesses: usize,
+    /// Cycles of the copy constraints, one per witness wired to more than
+    /// one wire
+    pub copy_cycles: usize,
+    /// Rows of the domain of the circuit, padded to a power of two, which
+    /// also fits the rows of the lookup tables
+    pub domain_size: usize,
+    /// Sources of the constraints, by decreasing count of constraints
+    ///
+    /// The sources are resolved by the debugger, so they are only reported
+    /// with the `debug` feature.
+    pub call_sites: Vec<CallSite>,
+}
+
+impl CircuitReport {
+    /// Rows enabling `gate`
+    pub fn gate(&self, gate: GateType) -> usize {
+        self.gates
+            .iter()
+            .find_map(|(g, count)| (*g == gate).then_some(*count))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for CircuitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} constraints, padded to a domain of {} rows",
+            self.constraints, self.domain_size
+        )?;
+
+        self.gates.iter().try_for_each(|(gate, count)| {
+            writeln!(f, "  {} gates: {}", gate, count)
+        })?;
+
+        writeln!(f, "{} public inputs", self.public_inputs)?;
+        writeln!(f, "{} witnesses", self.witnesses)?;
+        write!(f, "{} copy constraint cycles", self.copy_cycles)?;
+
+        if !self.call_sites.is_empty() {
+            write!(f, "\ntop call sites:")?;
+        }
+
+        self.call_sites
+            .iter()
+            .take(DISPLAYED_CALL_SITES)
+            .try_for_each(|site| {
+                write!(f, "\n  {}: {}", site.source, site.constraints)
+            })
+    }
+}
+
+impl Builder {
+    /// Report the cost of the circuit appended to the builder
+    pub(crate) fn report(&self) -> CircuitReport {
+        let constraints = self.constraints();
+
+        // rows enabling each selector of the gates of the composer
+        let mut enabled = [0; 6];
+        self.constraints.iter().for_each(|row| {
+            let selectors = [
+                row.q_arith,
+                row.q_range,
+                row.q_logic,
+                row.q_fixed_group_add,
+                row.q_variable_group_add,
+                row.q_lookup,
+            ];
+
+            selectors
+                .iter()
+                .zip(enabled.iter_mut())
+                .filter(|(q, _)| **q != BlsScalar::zero())
+                .for_each(|(_, count)| *count += 1);
+        });
+
+        let gates = [
+            GateType::Arithmetic,
+            GateType::Range,
+            GateType::Logic,
+            GateType::FixedBaseAddition,
+            GateType::VariableBaseAddition,
+            GateType::Lookup,
+        ];
+
+        let gates = gates
+            .into_iter()
+            .zip(enabled)
+            .chain(self.widgets.iter().map(|(widget, rows)| {
+                (GateType::Custom(widget.selector()), rows.len())
+            }))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        let copy_cycles = self
+            .perm
+            .witness_map
+            .values()
+            .filter(|wires| wires.len() > 1)
+            .count();
+
+        CircuitReport {
+            constraints,
+            gates,
+            public_inputs: self.public_inputs.len(),
+            witnesses: self.witnesses.len(),
+            copy_cycles,
+            domain_size: self.domain_size(),
+            call_sites: self.call_sites(),
+        }
+    }
+
+    fn call_sites(&self) -> Vec<CallSite> {
+        let mut counts = HashMap::<String, usize>::new();
+
+        (0..self.constraints())
+            .filter_map(|i| self.source(i))
+            .for_each(|source| *counts.entry(source).or_default() += 1);
+
+        let mut call_sites: Vec<_> = counts
+            .into_iter()
+            .map(|(source, constraints)| CallSite {
+                source,
+                constraints,
+            })
+            .collect();
+
+        call_sites.sort_by(|a, b| {
+            b.constraints
+                .cmp(&a.constraints)
+                .then_with(|| a.source.cmp(&b.source))
+        });
+
+        call_sites
+    }
+
+    #[allow(unused_variables)]
+    fn source(&self, index: usize) -> Option<String> {
+        #[cfg(feature = "debug")]
+        return self.runtime.constraint_source(index);
+
+        #[cfg(not(feature = "debug"))]
+        None
+    }
+}
@@ -6,29 +6,36 @@
 
 use alloc::vec::Vec;
 
-use dusk_bls12_381::BlsScalar;
+use dusk_bls12_381::{BlsScalar, G1Projective};
 use dusk_bytes::{DeserializableSlice, Serializable};
-use merlin::Transcript;
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
 
-use crate::commitment_scheme::OpeningKey;
+use crate::commitment_scheme::{Accumulator, OpeningKey};
 use crate::error::Error;
-use crate::proof_system::{Proof, VerifierKey};
-use crate::transcript::TranscriptProtocol;
+use crate::proof_system::{widget, Proof, VerifierKey};
+use crate::transcript::{Merlin, TranscriptExt, TranscriptProtocol};
 
-use super::Builder;
+use super::{Builder, CustomWidget};
+
+mod solidity;
 
 /// Verify proofs of a given circuit
-pub struct Verifier {
+///
+/// The challenges of the proofs are derived from the transcript `T`, which is
+/// [`Merlin`] by default.
+pub struct Verifier<T = Merlin> {
     label: Vec<u8>,
     verifier_key: VerifierKey,
+    custom_verifier_key: widget::custom::VerifierKey,
     opening_key: OpeningKey,
     public_input_indexes: Vec<usize>,
-    transcript: Transcript,
+    transcript: T,
     size: usize,
     constraints: usize,
 }
 
-impl Verifier {
+impl<T: TranscriptProtocol> Verifier<T> {
     pub(crate) fn new(
         label: Vec<u8>,
         verifier_key: VerifierKey,
@@ -37,12 +44,12 @@ impl Verifier {
         size: usize,
         constraints: usize,
     ) -> Self {
-        let transcript =
-            Transcript::base(label.as_slice(), &verifier_key, constraints);
+        let transcript = T::base(label.as_slice(), &verifier_key, constraints);
 
         Self {
             label,
             verifier_key,
+            custom_verifier_key: widget::custom::VerifierKey::default(),
             opening_key,
             public_input_indexes,
             transcript,
@@ -51,24 +58,66 @@ impl Verifier {
         }
     }
 
+    /// Set the key of the custom gates of the circuit
+    pub(crate) fn with_widgets(
+        mut self,
+        custom_verifier_key: widget::custom::VerifierKey,
+    ) -> Self {
+        custom_verifier_key.seed_transcript(&mut self.transcript);
+
+        self.custom_verifier_key = custom_verifier_key;
+
+        self
+    }
+
+    /// Use the transcript `U` to derive the challenges of the proofs
+    ///
+    /// Only the proofs of a [`Prover`](super::Prover) using the same
+    /// transcript can be verified.
+    pub fn with_transcript<U: TranscriptProtocol>(self) -> Verifier<U> {
+        Verifier::new(
+            self.label,
+            self.verifier_key,
+            self.opening_key,
+            self.public_input_indexes,
+            self.size,
+            self.constraints,
+        )
+        .with_widgets(self.custom_verifier_key)
+    }
+
     fn prepare_serialize(
         &self,
-    ) -> (usize, [u8; VerifierKey::SIZE], [u8; OpeningKey::SIZE]) {
+    ) -> (
+        usize,
+        [u8; VerifierKey::SIZE],
+        [u8; OpeningKey::SIZE],
+        Vec<u8>,
+    ) {
         let verifier_key = self.verifier_key.to_bytes();
         let opening_key = self.opening_key.to_bytes();
 
+        // the key of the custom gates is only appended if there are any, so
+        // the verifiers without them keep their representation
+        let custom_key = match self.custom_verifier_key.widgets.is_empty() {
+            true => Vec::new(),
+            false => self.custom_verifier_key.to_var_bytes(),
+        };
+
         let label_len = self.label.len();
         let verifier_key_len = verifier_key.len();
         let opening_key_len = opening_key.len();
         let public_input_indexes_len = self.public_input_indexes.len() * 8;
+        let custom_key_len = custom_key.len();
 
         let size = 48
             + label_len
             + verifier_key_len
             + opening_key_len
-            + public_input_indexes_len;
+            + public_input_indexes_len
+            + custom_key_len;
 
-        (size, verifier_key, opening_key)
+        (size, verifier_key, opening_key, custom_key)
     }
 
     /// Serialized size in bytes
@@ -78,7 +127,8 @@ impl Verifier {
 
     /// Serialize the verifier into bytes
     pub fn to_bytes(&self) -> Vec<u8> {
-        let (size, verifier_key, opening_key) = self.prepare_serialize();
+        let (size, verifier_key, opening_key, custom_key) =
+            self.prepare_serialize();
         let mut bytes = Vec::with_capacity(size);
 
         let label_len = self.label.len() as u64;
@@ -105,12 +155,126 @@ impl Verifier {
             .map(u64::to_be_bytes)
             .for_each(|i| bytes.extend(i));
 
+        bytes.extend(custom_key);
+
         bytes
     }
 
+    /// Verify a generated proof
+    pub fn verify(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let (total_w, total_c) = self.accumulate(proof, public_inputs)?;
+
+        self.opening_key
+            .pairing_check(&total_w, &total_c)
+            .map_err(|_| Error::ProofVerificationError)
+    }
+
+    /// Perform every check of the verification of a generated proof but the
+    /// final pairing, returning the [`Accumulator`] to be checked later
+    pub fn verify_deferred(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<Accumulator, Error> {
+        let (total_w, total_c) = self.accumulate(proof, public_inputs)?;
+
+        Ok(Accumulator::new(total_w, total_c, &self.opening_key))
+    }
+
+    /// Verify a batch of generated proofs with a single pairing check
+    ///
+    /// The pairing checks of the proofs are folded with a random linear
+    /// combination sampled from `rng`. If the batch is invalid, every proof is
+    /// verified on its own so the first invalid one is reported with
+    /// [`Error::InvalidProofInBatch`].
+    pub fn verify_batch<R>(
+        &self,
+        proofs: &[(Proof, Vec<BlsScalar>)],
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let batch: Vec<_> = proofs
+            .iter()
+            .map(|(proof, public_inputs)| {
+                (self, proof, public_inputs.as_slice())
+            })
+            .collect();
+
+        verify_batch(&batch, rng)
+    }
+
+    pub(crate) fn opening_key(&self) -> &OpeningKey {
+        &self.opening_key
+    }
+
+    /// Perform every check of the verification of a proof but the final
+    /// pairing, returning the points to be checked by the opening key
+    fn accumulate(
+        &self,
+        proof: &Proof,
+        public_inputs: &[BlsScalar],
+    ) -> Result<(G1Projective, G1Projective), Error> {
+        if public_inputs.len() != self.public_input_indexes.len() {
+            return Err(Error::InconsistentPublicInputsLen {
+                expected: self.public_input_indexes.len(),
+                provided: public_inputs.len(),
+            });
+        }
+
+        let mut transcript = self.transcript.clone();
+
+        public_inputs
+            .iter()
+            .for_each(|pi| transcript.append_scalar(b"pi", pi));
+
+        let dense_public_inputs = Builder::dense_public_inputs(
+            &self.public_input_indexes,
+            public_inputs,
+            self.size,
+        );
+
+        proof.accumulate(
+            &self.verifier_key,
+            &self.custom_verifier_key,
+            &mut transcript,
+            &self.opening_key,
+            &dense_public_inputs,
+        )
+    }
+}
+
+impl Verifier {
     /// Attempt to deserialize the prover from bytes generated via
     /// [`Self::to_bytes`]
+    ///
+    /// The verifier uses the [`Merlin`] transcript, and can be switched to
+    /// another one with [`Self::with_transcript`]. The verifiers of circuits
+    /// with custom gates are deserialized with
+    /// [`Self::try_from_bytes_with_gates`].
     pub fn try_from_bytes<B>(bytes: B) -> Result<Self, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        Self::try_from_bytes_with_gates(bytes, &[])
+    }
+
+    /// Attempt to deserialize the verifier of a circuit with custom gates
+    /// from bytes generated via [`Self::to_bytes`]
+    ///
+    /// The selector commitments of the gates are part of the bytes, but their
+    /// constraints are not, so every gate of the circuit must be registered
+    /// in `gates`, as created by [`CustomWidget::new`]. Otherwise,
+    /// [`Error::UnregisteredCustomGate`] is returned.
+    pub fn try_from_bytes_with_gates<B>(
+        bytes: B,
+        gates: &[CustomWidget],
+    ) -> Result<Self, Error>
     where
         B: AsRef<[u8]>,
     {
@@ -168,6 +332,14 @@ impl Verifier {
         bytes = &bytes[opening_key_len..];
 
         let public_input_indexes = &bytes[..public_input_indexes_len * 8];
+        bytes = &bytes[public_input_indexes_len * 8..];
+
+        let custom_verifier_key = match bytes.is_empty() {
+            true => widget::custom::VerifierKey::default(),
+            false => {
+                widget::custom::VerifierKey::from_reader(&mut bytes, gates)?
+            }
+        };
 
         let label = label.to_vec();
         let verifier_key = VerifierKey::from_slice(verifier_key)?;
@@ -186,39 +358,67 @@ impl Verifier {
             public_input_indexes,
             size,
             constraints,
-        ))
+        )
+        .with_widgets(custom_verifier_key))
     }
+}
 
-    /// Verify a generated proof
-    pub fn verify(
-        &self,
-        proof: &Proof,
-        public_inputs: &[BlsScalar],
-    ) -> Result<(), Error> {
-        if public_inputs.len() != self.public_input_indexes.len() {
-            return Err(Error::InconsistentPublicInputsLen {
-                expected: self.public_input_indexes.len(),
-                provided: public_inputs.len(),
-            });
-        }
+/// Verify a batch of proofs of different circuits with a single pairing check
+///
+/// Every verifier of the batch must share the same opening key, meaning their
+/// circuits were compiled from the same [`PublicParameters`], otherwise
+/// [`Error::InconsistentOpeningKeys`] is returned.
+///
+/// The pairing checks of the proofs are folded with a random linear
+/// combination sampled from `rng`. If the batch is invalid, every proof is
+/// verified on its own so the first invalid one is reported with
+/// [`Error::InvalidProofInBatch`].
+///
+/// [`PublicParameters`]: crate::commitment_scheme::PublicParameters
+pub fn verify_batch<T, R>(
+    proofs: &[(&Verifier<T>, &Proof, &[BlsScalar])],
+    rng: &mut R,
+) -> Result<(), Error>
+where
+    T: TranscriptProtocol,
+    R: RngCore + CryptoRng,
+{
+    let opening_key = match proofs.first() {
+        Some((verifier, _, _)) => &verifier.opening_key,
+        None => return Ok(()),
+    };
+
+    if proofs
+        .iter()
+        .any(|(verifier, _, _)| &verifier.opening_key != opening_key)
+    {
+        return Err(Error::InconsistentOpeningKeys);
+    }
 
-        let mut transcript = self.transcript.clone();
+    let batch = proofs.iter().try_fold(
+        (G1Projective::identity(), G1Projective::identity()),
+        |(total_w, total_c), (verifier, proof, public_inputs)| {
+            let (w, c) = verifier.accumulate(proof, public_inputs)?;
+            let r = BlsScalar::random(&mut *rng);
 
-        public_inputs
-            .iter()
-            .for_each(|pi| transcript.append_scalar(b"pi", pi));
+            Ok::<_, Error>((total_w + w * r, total_c + c * r))
+        },
+    );
 
-        let dense_public_inputs = Builder::dense_public_inputs(
-            &self.public_input_indexes,
-            public_inputs,
-            self.size,
-        );
+    let valid = batch
+        .and_then(|(total_w, total_c)| {
+            opening_key.pairing_check(&total_w, &total_c)
+        })
+        .is_ok();
 
-        proof.verify(
-            &self.verifier_key,
-            &mut transcript,
-            &self.opening_key,
-            &dense_public_inputs,
-        )
+    if valid {
+        return Ok(());
+    }
+
+    match proofs.iter().position(|(verifier, proof, public_inputs)| {
+        verifier.verify(proof, public_inputs).is_err()
+    }) {
+        Some(index) => Err(Error::InvalidProofInBatch { index }),
+        None => Err(Error::ProofVerificationError),
     }
 }
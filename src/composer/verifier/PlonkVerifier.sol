@@ -0,0 +1,964 @@
+// SPDX-License-Identifier: MPL-2.0
+//
+// Generated by dusk-plonk. The constants below are bound to a single circuit,
+// any change to the circuit requires the contract to be generated again.
+
+pragma solidity ^0.8.20;
+
+/// @title Verifier of the PLONK proofs of a single circuit
+/// @notice Relies on the BLS12-381 precompiles of EIP-2537
+contract PlonkVerifier {
+    // Order of the scalar field of BLS12-381
+    uint256 internal constant R =
+        0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001;
+    // 2^256 mod r
+    uint256 internal constant R_256 = {{r_256}};
+
+    // Coset generators of the permutation argument
+    uint256 internal constant K1 = 7;
+    uint256 internal constant K2 = 13;
+    uint256 internal constant K3 = 17;
+
+    // Parameter `d` of the twisted Edwards curve Jubjub
+    uint256 internal constant EDWARDS_D = {{edwards_d}};
+
+    // Size, inverse of the size and generator of the evaluation domain
+    uint256 internal constant N = {{n}};
+    uint256 internal constant N_INV = {{n_inv}};
+    uint256 internal constant OMEGA = {{omega}};
+
+    // Number of public inputs of the circuit
+    uint256 internal constant PUBLIC_INPUTS = {{public_inputs}};
+
+    // Keccak transcript seeded with the label and the verifier key
+    bytes32 internal constant TRANSCRIPT = {{transcript}};
+
+    // Whether the circuit has lookups, whose proofs carry the commitments and
+    // the evaluations of the lookup argument
+    bool internal constant LOOKUP = {{lookup}};
+
+    // Layout of the proof: the points of 128 bytes followed by the scalars of
+    // 32 bytes, all of them big endian
+    uint256 internal constant POINT = 128;
+    uint256 internal constant POINTS = {{points}};
+    uint256 internal constant SCALARS = {{scalars}};
+    uint256 internal constant EVALUATIONS = POINTS * POINT;
+    uint256 internal constant PROOF_SIZE = EVALUATIONS + SCALARS * 32;
+
+    // Indexes of the points of the proof, the ones of the lookup argument
+    // being last
+    uint256 internal constant A_COMM = 0;
+    uint256 internal constant Z_COMM = 4;
+    uint256 internal constant T_LOW_COMM = 5;
+    uint256 internal constant W_Z_COMM = 9;
+    uint256 internal constant F_COMM = 11;
+    uint256 internal constant P_COMM = 14;
+
+    // Indexes of the evaluations of the proof
+    uint256 internal constant A_EVAL = 0;
+    uint256 internal constant B_EVAL = 1;
+    uint256 internal constant C_EVAL = 2;
+    uint256 internal constant D_EVAL = 3;
+    uint256 internal constant A_NEXT_EVAL = 4;
+    uint256 internal constant B_NEXT_EVAL = 5;
+    uint256 internal constant D_NEXT_EVAL = 6;
+    uint256 internal constant S_SIGMA_1_EVAL = 7;
+    uint256 internal constant S_SIGMA_2_EVAL = 8;
+    uint256 internal constant S_SIGMA_3_EVAL = 9;
+    uint256 internal constant Q_ARITH_EVAL = 10;
+    uint256 internal constant Q_C_EVAL = 11;
+    uint256 internal constant Q_L_EVAL = 12;
+    uint256 internal constant Q_R_EVAL = 13;
+    uint256 internal constant PERM_EVAL = 14;
+    uint256 internal constant R_EVAL = 15;
+    uint256 internal constant F_EVAL = 16;
+    uint256 internal constant H_1_EVAL = 17;
+    uint256 internal constant H_1_NEXT_EVAL = 18;
+    uint256 internal constant H_2_EVAL = 19;
+    uint256 internal constant TABLE_EVAL = 20;
+    uint256 internal constant TABLE_NEXT_EVAL = 21;
+    uint256 internal constant LOOKUP_PERM_EVAL = 22;
+
+    // Entries of the multiscalar multiplication, a point followed by its
+    // scalar: the verifier key, the generator of G1, the points of the proof
+    // and the lookup part of the verifier key
+    uint256 internal constant MSM_ENTRY = 160;
+    uint256 internal constant Q_M = 0;
+    uint256 internal constant Q_L = 1;
+    uint256 internal constant Q_R = 2;
+    uint256 internal constant Q_O = 3;
+    uint256 internal constant Q_4 = 4;
+    uint256 internal constant Q_C = 5;
+    uint256 internal constant Q_RANGE = 6;
+    uint256 internal constant Q_LOGIC = 7;
+    uint256 internal constant Q_FIXED_GROUP_ADD = 8;
+    uint256 internal constant Q_VARIABLE_GROUP_ADD = 9;
+    uint256 internal constant S_SIGMA_1 = 10;
+    uint256 internal constant S_SIGMA_4 = 13;
+    uint256 internal constant G = 14;
+    uint256 internal constant PROOF = 15;
+    uint256 internal constant Q_LOOKUP = 30;
+    uint256 internal constant TABLE_1 = 31;
+    uint256 internal constant MSM_SIZE = {{msm_size}};
+
+    // BLS12-381 precompiles of EIP-2537
+    uint256 internal constant BLS12_G1MSM = 0x0c;
+    uint256 internal constant BLS12_PAIRING_CHECK = 0x0f;
+
+    struct Challenges {
+        uint256 zeta;
+        uint256 beta;
+        uint256 gamma;
+        uint256 delta;
+        uint256 epsilon;
+        uint256 alpha;
+        uint256 range;
+        uint256 logic;
+        uint256 fixedBase;
+        uint256 variableBase;
+        uint256 lookup;
+        uint256 z;
+        uint256 v;
+        uint256 vShifted;
+        uint256 u;
+    }
+
+    struct Evaluations {
+        uint256[23] proof;
+        uint256 zN;
+        uint256 zH;
+        uint256 l1;
+        uint256 t;
+    }
+
+    /// @notice Verify a proof generated with the Keccak transcript
+    /// @param proof The proof, as encoded by `Proof::to_calldata`
+    /// @param publicInputs The public inputs of the circuit, in order
+    /// @return valid Whether the proof is valid
+    function verify(bytes calldata proof, uint256[] calldata publicInputs)
+        external
+        view
+        returns (bool valid)
+    {
+        require(proof.length == PROOF_SIZE, "PlonkVerifier: proof size");
+        require(
+            publicInputs.length == PUBLIC_INPUTS,
+            "PlonkVerifier: public inputs"
+        );
+
+        Challenges memory ch;
+        Evaluations memory ev;
+
+        for (uint256 i = 0; i < SCALARS; i++) {
+            uint256 offset = EVALUATIONS + i * 32;
+            ev.proof[i] = uint256(bytes32(proof[offset:offset + 32]));
+            require(ev.proof[i] < R, "PlonkVerifier: evaluation");
+        }
+
+        bytes32 state = _challenges(proof, publicInputs, ch);
+
+        _quotientEvaluation(publicInputs, ch, ev);
+
+        _openingChallenges(state, proof, ch, ev);
+
+        return _pairing(proof, ch, ev);
+    }
+
+    /// Replay the transcript of the proof up to the evaluation challenge
+    function _challenges(
+        bytes calldata proof,
+        uint256[] calldata publicInputs,
+        Challenges memory ch
+    ) private pure returns (bytes32 state) {
+        state = TRANSCRIPT;
+
+        for (uint256 i = 0; i < publicInputs.length; i++) {
+            require(publicInputs[i] < R, "PlonkVerifier: public input");
+            state = _appendScalar(state, publicInputs[i]);
+        }
+
+        // a, b, c, d
+        for (uint256 i = A_COMM; i < A_COMM + 4; i++) {
+            state = _appendPoint(state, proof, i);
+        }
+
+        if (LOOKUP) {
+            (state, ch.zeta) = _challenge(state, "zeta");
+            state = _appendScalar(state, ch.zeta);
+
+            // f, h_1, h_2
+            for (uint256 i = F_COMM; i < F_COMM + 3; i++) {
+                state = _appendPoint(state, proof, i);
+            }
+        }
+
+        (state, ch.beta) = _challenge(state, "beta");
+        state = _appendScalar(state, ch.beta);
+        (state, ch.gamma) = _challenge(state, "gamma");
+
+        if (LOOKUP) {
+            (state, ch.delta) = _challenge(state, "delta");
+            state = _appendScalar(state, ch.delta);
+            (state, ch.epsilon) = _challenge(state, "epsilon");
+        }
+
+        // z, p
+        state = _appendPoint(state, proof, Z_COMM);
+        if (LOOKUP) {
+            state = _appendPoint(state, proof, P_COMM);
+        }
+
+        (state, ch.alpha) = _challenge(state, "alpha");
+        (state, ch.range) = _challenge(state, "range separation challenge");
+        (state, ch.logic) = _challenge(state, "logic separation challenge");
+        (state, ch.fixedBase) =
+            _challenge(state, "fixed base separation challenge");
+        (state, ch.variableBase) =
+            _challenge(state, "variable base separation challenge");
+        if (LOOKUP) {
+            (state, ch.lookup) =
+                _challenge(state, "lookup separation challenge");
+        }
+
+        // t_low, t_mid, t_high, t_4
+        for (uint256 i = T_LOW_COMM; i < T_LOW_COMM + 4; i++) {
+            state = _appendPoint(state, proof, i);
+        }
+
+        (state, ch.z) = _challenge(state, "z_challenge");
+    }
+
+    /// Replay the transcript of the proof from the evaluations to the
+    /// batching challenge
+    function _openingChallenges(
+        bytes32 state,
+        bytes calldata proof,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private pure {
+        for (uint256 i = A_EVAL; i < R_EVAL; i++) {
+            state = _appendScalar(state, ev.proof[i]);
+        }
+        for (uint256 i = R_EVAL + 1; i < SCALARS; i++) {
+            state = _appendScalar(state, ev.proof[i]);
+        }
+        state = _appendScalar(state, ev.t);
+        state = _appendScalar(state, ev.proof[R_EVAL]);
+
+        (state, ch.v) = _challenge(state, "v_challenge");
+        (state, ch.vShifted) = _challenge(state, "v_challenge");
+
+        // w_z, w_z_w
+        state = _appendPoint(state, proof, W_Z_COMM);
+        state = _appendPoint(state, proof, W_Z_COMM + 1);
+
+        (state, ch.u) = _challenge(state, "batch");
+    }
+
+    /// Evaluate the quotient polynomial at the evaluation challenge
+    function _quotientEvaluation(
+        uint256[] calldata publicInputs,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private view {
+        ev.zN = ch.z;
+        for (uint256 i = 1; i < N; i *= 2) {
+            ev.zN = mulmod(ev.zN, ev.zN, R);
+        }
+        ev.zH = _sub(ev.zN, 1);
+        ev.l1 = mulmod(ev.zH, _inverse(mulmod(N, _sub(ch.z, 1), R)), R);
+
+        // r + PI(z)
+        uint256 t = mulmod(
+            mulmod(ev.zH, N_INV, R),
+            _publicInputs(publicInputs, ch.z),
+            R
+        );
+        t = addmod(ev.proof[R_EVAL], t, R);
+
+        // (a + beta * sigma_1 + gamma)(b + beta * sigma_2 + gamma)
+        // (c + beta * sigma_3 + gamma)(d + gamma) * z_hat * alpha
+        uint256 b = mulmod(
+            addmod(ev.proof[D_EVAL], ch.gamma, R),
+            ev.proof[PERM_EVAL],
+            R
+        );
+        t = _sub(t, mulmod(_sigmaProduct(ev.proof, ch), b, R));
+
+        // l_1(z) * alpha^2
+        t = _sub(t, mulmod(ev.l1, mulmod(ch.alpha, ch.alpha, R), R));
+
+        if (LOOKUP) {
+            t = _sub(t, _lookupQuotient(ev.proof, ch, ev.l1));
+        }
+
+        ev.t = mulmod(t, _inverse(ev.zH), R);
+    }
+
+    /// Compute `(l_1(z) * kappa + d_0 * kappa^2) * lookup_sep`, where `d_0` is
+    /// `p(z * omega) * (epsilon * (1 + delta) + h_1 + delta * h_2)
+    /// * (epsilon * (1 + delta) + h_2 + delta * h_1(z * omega))`
+    function _lookupQuotient(
+        uint256[23] memory e,
+        Challenges memory ch,
+        uint256 l1
+    ) private pure returns (uint256 d) {
+        uint256 kappa = mulmod(ch.lookup, ch.lookup, R);
+        uint256 epsilonOnePlusDelta =
+            mulmod(ch.epsilon, addmod(1, ch.delta, R), R);
+
+        d = addmod(
+            addmod(epsilonOnePlusDelta, e[H_1_EVAL], R),
+            mulmod(ch.delta, e[H_2_EVAL], R),
+            R
+        );
+        d = mulmod(d, e[LOOKUP_PERM_EVAL], R);
+        d = mulmod(
+            d,
+            addmod(
+                addmod(epsilonOnePlusDelta, e[H_2_EVAL], R),
+                mulmod(ch.delta, e[H_1_NEXT_EVAL], R),
+                R
+            ),
+            R
+        );
+
+        d = mulmod(addmod(l1, mulmod(d, kappa, R), R), kappa, R);
+        d = mulmod(d, ch.lookup, R);
+    }
+
+    /// Evaluate the public input polynomial at `z`, up to the factor
+    /// `(z^n - 1) / n`
+    function _publicInputs(uint256[] calldata publicInputs, uint256 z)
+        private
+        view
+        returns (uint256 sum)
+    {
+{{public_input_evaluation}}
+    }
+
+    /// Compute `(a + beta * sigma_1 + gamma)(b + beta * sigma_2 + gamma)
+    /// (c + beta * sigma_3 + gamma) * alpha`
+    function _sigmaProduct(uint256[23] memory e, Challenges memory ch)
+        private
+        pure
+        returns (uint256 p)
+    {
+        p = ch.alpha;
+        for (uint256 i = 0; i < 3; i++) {
+            uint256 sigma = mulmod(ch.beta, e[S_SIGMA_1_EVAL + i], R);
+            uint256 term = addmod(addmod(e[A_EVAL + i], sigma, R), ch.gamma, R);
+            p = mulmod(p, term, R);
+        }
+    }
+
+    /// Fold every opening of the proof into a pair of points and check their
+    /// pairing
+    function _pairing(
+        bytes calldata proof,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private view returns (bool) {
+        bytes memory msm = new bytes(MSM_SIZE * MSM_ENTRY);
+        _verifierKey(msm);
+
+        for (uint256 i = 0; i < POINTS; i++) {
+            uint256 offset = i * POINT;
+            bytes calldata point = proof[offset:offset + POINT];
+            uint256 entry = (PROOF + i) * MSM_ENTRY;
+            assembly {
+                calldatacopy(add(add(msm, 0x20), entry), point.offset, POINT)
+            }
+        }
+
+        _linearizationScalars(msm, ch, ev);
+        _openingScalars(msm, ch, ev);
+
+        // e(W, -beta_h) * e(C, h) == 1
+        bytes memory input = new bytes(2 * (POINT + 2 * POINT));
+        _openingKey(input);
+
+        uint256 u = ch.u;
+        bool ok;
+        assembly {
+            let entries := add(msm, 0x20)
+            let pairing := add(input, 0x20)
+
+            // C
+            ok := staticcall(
+                gas(),
+                BLS12_G1MSM,
+                entries,
+                mul(MSM_SIZE, MSM_ENTRY),
+                add(pairing, mul(3, POINT)),
+                POINT
+            )
+
+            // W = w_z + u * w_z_w, reusing the entries of the proof
+            let w := add(entries, mul(add(PROOF, W_Z_COMM), MSM_ENTRY))
+            mstore(add(w, POINT), 1)
+            mstore(add(add(w, MSM_ENTRY), POINT), u)
+            ok := and(
+                ok,
+                staticcall(
+                    gas(),
+                    BLS12_G1MSM,
+                    w,
+                    mul(2, MSM_ENTRY),
+                    pairing,
+                    POINT
+                )
+            )
+
+            let out := mload(0x40)
+            ok := and(
+                ok,
+                staticcall(
+                    gas(),
+                    BLS12_PAIRING_CHECK,
+                    pairing,
+                    mload(input),
+                    out,
+                    0x20
+                )
+            )
+            ok := and(ok, eq(mload(out), 1))
+        }
+
+        return ok;
+    }
+
+    /// Set the scalars of the linearization polynomial, scaled by `v`
+    function _linearizationScalars(
+        bytes memory msm,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private pure {
+        uint256[23] memory e = ev.proof;
+        uint256 v = ch.v;
+        uint256 qArith = mulmod(e[Q_ARITH_EVAL], v, R);
+
+        _setScalar(
+            msm,
+            Q_M,
+            mulmod(mulmod(e[A_EVAL], e[B_EVAL], R), qArith, R)
+        );
+        _setScalar(msm, Q_L, mulmod(e[A_EVAL], qArith, R));
+        _setScalar(msm, Q_R, mulmod(e[B_EVAL], qArith, R));
+        _setScalar(msm, Q_O, mulmod(e[C_EVAL], qArith, R));
+        _setScalar(msm, Q_4, mulmod(e[D_EVAL], qArith, R));
+        _setScalar(msm, Q_C, qArith);
+
+        _setScalar(msm, Q_RANGE, mulmod(_range(e, ch.range), v, R));
+        _setScalar(msm, Q_LOGIC, mulmod(_logic(e, ch.logic), v, R));
+        _setScalar(
+            msm,
+            Q_FIXED_GROUP_ADD,
+            mulmod(_fixedBase(e, ch.fixedBase), v, R)
+        );
+        _setScalar(
+            msm,
+            Q_VARIABLE_GROUP_ADD,
+            mulmod(_variableBase(e, ch.variableBase), v, R)
+        );
+
+        // -(a + beta * sigma_1 + gamma)(b + beta * sigma_2 + gamma)
+        // (c + beta * sigma_3 + gamma) * beta * z_hat * alpha
+        uint256 y = mulmod(
+            _sigmaProduct(e, ch),
+            mulmod(ch.beta, e[PERM_EVAL], R),
+            R
+        );
+        _setScalar(msm, S_SIGMA_4, mulmod(R - y, v, R));
+
+        _setScalar(
+            msm,
+            PROOF + Z_COMM,
+            mulmod(
+                addmod(
+                    _permutation(e, ch),
+                    mulmod(ev.l1, mulmod(ch.alpha, ch.alpha, R), R),
+                    R
+                ),
+                v,
+                R
+            )
+        );
+
+        if (LOOKUP) {
+            (uint256 q, uint256 p) = _lookup(e, ch, ev.l1);
+            _setScalar(msm, Q_LOOKUP, mulmod(q, v, R));
+            _setScalar(msm, PROOF + P_COMM, mulmod(p, v, R));
+        }
+    }
+
+    /// Set the scalars of the openings at `z` and `z * omega`, batched by `u`
+    function _openingScalars(
+        bytes memory msm,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private pure {
+        // quotient: t_low + z^n * t_mid + z^2n * t_high + z^3n * t_4
+        uint256 power = 1;
+        for (uint256 i = 0; i < 4; i++) {
+            _setScalar(msm, PROOF + T_LOW_COMM + i, power);
+            power = mulmod(power, ev.zN, R);
+        }
+
+        (uint256 eval, uint256 table) = _openingAtZ(msm, ch, ev);
+        (uint256 shiftedEval, uint256 shiftedTable) =
+            _openingAtShiftedZ(msm, ch, ev);
+
+        // table: t_1 + zeta * t_2 + zeta^2 * t_3 + zeta^3 * t_4
+        if (LOOKUP) {
+            table = addmod(table, shiftedTable, R);
+            for (uint256 i = 0; i < 4; i++) {
+                _setScalar(msm, TABLE_1 + i, table);
+                table = mulmod(table, ch.zeta, R);
+            }
+        }
+
+        // witnesses: z * w_z + u * z * omega * w_z_w
+        _setScalar(msm, PROOF + W_Z_COMM, ch.z);
+        _setScalar(
+            msm,
+            PROOF + W_Z_COMM + 1,
+            mulmod(ch.u, mulmod(ch.z, OMEGA, R), R)
+        );
+
+        // generator: -(eval + u * shifted eval)
+        _setScalar(msm, G, R - addmod(eval, shiftedEval, R));
+    }
+
+    /// Add the powers of `v` to the scalars of the polynomials opened at `z`,
+    /// returning the aggregated evaluation and the scalar of the table
+    ///
+    /// The quotient and the linearization polynomials, scaled by one and by
+    /// `v` respectively, are already set.
+    function _openingAtZ(
+        bytes memory msm,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private pure returns (uint256 eval, uint256 table) {
+        uint256[23] memory e = ev.proof;
+
+        uint256[8] memory points = [
+            PROOF + A_COMM,
+            PROOF + A_COMM + 1,
+            PROOF + A_COMM + 2,
+            PROOF + A_COMM + 3,
+            S_SIGMA_1,
+            S_SIGMA_1 + 1,
+            S_SIGMA_1 + 2,
+            Q_C
+        ];
+        uint256[8] memory evals = [
+            e[A_EVAL],
+            e[B_EVAL],
+            e[C_EVAL],
+            e[D_EVAL],
+            e[S_SIGMA_1_EVAL],
+            e[S_SIGMA_2_EVAL],
+            e[S_SIGMA_3_EVAL],
+            e[Q_C_EVAL]
+        ];
+
+        eval = addmod(ev.t, mulmod(e[R_EVAL], ch.v, R), R);
+
+        uint256 power = ch.v;
+        for (uint256 i = 0; i < 8; i++) {
+            power = mulmod(power, ch.v, R);
+            eval = addmod(eval, mulmod(evals[i], power, R), R);
+            _addScalar(msm, points[i], power);
+        }
+
+        if (LOOKUP) {
+            uint256[4] memory lookupEvals = [
+                e[F_EVAL],
+                e[H_1_EVAL],
+                e[H_2_EVAL],
+                e[TABLE_EVAL]
+            ];
+
+            // f, h_1, h_2 and the table
+            for (uint256 i = 0; i < 4; i++) {
+                power = mulmod(power, ch.v, R);
+                eval = addmod(eval, mulmod(lookupEvals[i], power, R), R);
+                if (i < 3) {
+                    _addScalar(msm, PROOF + F_COMM + i, power);
+                }
+            }
+            table = power;
+        }
+    }
+
+    /// Add the powers of the shifted `v`, batched by `u`, to the scalars of
+    /// the polynomials opened at `z * omega`, returning the aggregated
+    /// evaluation and the scalar of the table
+    function _openingAtShiftedZ(
+        bytes memory msm,
+        Challenges memory ch,
+        Evaluations memory ev
+    ) private pure returns (uint256 eval, uint256 table) {
+        uint256[23] memory e = ev.proof;
+
+        uint256[4] memory points = [
+            PROOF + Z_COMM,
+            PROOF + A_COMM,
+            PROOF + A_COMM + 1,
+            PROOF + A_COMM + 3
+        ];
+        uint256[4] memory evals = [
+            e[PERM_EVAL],
+            e[A_NEXT_EVAL],
+            e[B_NEXT_EVAL],
+            e[D_NEXT_EVAL]
+        ];
+
+        uint256 power = ch.u;
+        for (uint256 i = 0; i < 4; i++) {
+            eval = addmod(eval, mulmod(evals[i], power, R), R);
+            _addScalar(msm, points[i], power);
+            power = mulmod(power, ch.vShifted, R);
+        }
+
+        if (LOOKUP) {
+            uint256[2] memory lookupPoints = [
+                PROOF + P_COMM,
+                PROOF + F_COMM + 1
+            ];
+            uint256[3] memory lookupEvals = [
+                e[LOOKUP_PERM_EVAL],
+                e[H_1_NEXT_EVAL],
+                e[TABLE_NEXT_EVAL]
+            ];
+
+            // p, h_1 and the table
+            for (uint256 i = 0; i < 3; i++) {
+                eval = addmod(eval, mulmod(lookupEvals[i], power, R), R);
+                if (i < 2) {
+                    _addScalar(msm, lookupPoints[i], power);
+                    power = mulmod(power, ch.vShifted, R);
+                }
+            }
+            table = power;
+        }
+    }
+
+    /// Scalar of `q_range`
+    function _range(uint256[23] memory e, uint256 sep)
+        private
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+
+        sum = _delta(_sub(e[D_NEXT_EVAL], mulmod(4, e[A_EVAL], R)));
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _delta(_sub(e[A_EVAL], mulmod(4, e[B_EVAL], R))),
+            R
+        );
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _delta(_sub(e[B_EVAL], mulmod(4, e[C_EVAL], R))),
+            R
+        );
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _delta(_sub(e[C_EVAL], mulmod(4, e[D_EVAL], R))),
+            R
+        );
+        sum = mulmod(sum, sep, R);
+    }
+
+    /// Scalar of `q_logic`
+    function _logic(uint256[23] memory e, uint256 sep)
+        private
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+
+        uint256 a = _sub(e[A_NEXT_EVAL], mulmod(4, e[A_EVAL], R));
+        uint256 b = _sub(e[B_NEXT_EVAL], mulmod(4, e[B_EVAL], R));
+        uint256 d = _sub(e[D_NEXT_EVAL], mulmod(4, e[D_EVAL], R));
+        uint256 w = e[C_EVAL];
+
+        sum = _deltaXorAnd(a, b, w, d, e[Q_C_EVAL]);
+        sum = addmod(mulmod(sum, kappa, R), _sub(w, mulmod(a, b, R)), R);
+        sum = addmod(mulmod(sum, kappa, R), _delta(d), R);
+        sum = addmod(mulmod(sum, kappa, R), _delta(b), R);
+        sum = addmod(mulmod(sum, kappa, R), _delta(a), R);
+        sum = mulmod(sum, sep, R);
+    }
+
+    /// Scalar of `q_fixed_group_add`
+    function _fixedBase(uint256[23] memory e, uint256 sep)
+        private
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+
+        uint256 bit = _sub(e[D_NEXT_EVAL], mulmod(2, e[D_EVAL], R));
+        uint256 yAlpha = addmod(
+            mulmod(mulmod(bit, bit, R), _sub(e[Q_R_EVAL], 1), R),
+            1,
+            R
+        );
+        uint256 xAlpha = mulmod(e[Q_L_EVAL], bit, R);
+        uint256 xyD = mulmod(
+            mulmod(e[C_EVAL], mulmod(e[A_EVAL], e[B_EVAL], R), R),
+            EDWARDS_D,
+            R
+        );
+
+        // y accumulator consistency
+        sum = _sub(
+            _sub(e[B_NEXT_EVAL], mulmod(e[B_NEXT_EVAL], xyD, R)),
+            addmod(
+                mulmod(xAlpha, e[A_EVAL], R),
+                mulmod(yAlpha, e[B_EVAL], R),
+                R
+            )
+        );
+        // x accumulator consistency
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _sub(
+                addmod(e[A_NEXT_EVAL], mulmod(e[A_NEXT_EVAL], xyD, R), R),
+                addmod(
+                    mulmod(xAlpha, e[B_EVAL], R),
+                    mulmod(yAlpha, e[A_EVAL], R),
+                    R
+                )
+            ),
+            R
+        );
+        // xy_alpha consistency
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _sub(mulmod(bit, e[Q_C_EVAL], R), e[C_EVAL]),
+            R
+        );
+        // bit consistency
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            mulmod(mulmod(bit, _sub(bit, 1), R), addmod(bit, 1, R), R),
+            R
+        );
+        sum = mulmod(sum, sep, R);
+    }
+
+    /// Scalar of `q_variable_group_add`
+    function _variableBase(uint256[23] memory e, uint256 sep)
+        private
+        pure
+        returns (uint256 sum)
+    {
+        uint256 kappa = mulmod(sep, sep, R);
+
+        // x_1 = a, y_1 = b, x_2 = c, y_2 = d, x_1 * y_2 = d_next
+        uint256 x1y2 = e[D_NEXT_EVAL];
+        uint256 y1x2 = mulmod(e[B_EVAL], e[C_EVAL], R);
+        uint256 dxy = mulmod(mulmod(EDWARDS_D, x1y2, R), y1x2, R);
+
+        // y_3 consistency
+        sum = addmod(
+            mulmod(e[B_EVAL], e[D_EVAL], R),
+            mulmod(e[A_EVAL], e[C_EVAL], R),
+            R
+        );
+        sum = _sub(sum, _sub(e[B_NEXT_EVAL], mulmod(e[B_NEXT_EVAL], dxy, R)));
+        // x_3 consistency
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _sub(
+                addmod(x1y2, y1x2, R),
+                addmod(e[A_NEXT_EVAL], mulmod(e[A_NEXT_EVAL], dxy, R), R)
+            ),
+            R
+        );
+        // x_1 * y_2 consistency
+        sum = addmod(
+            mulmod(sum, kappa, R),
+            _sub(mulmod(e[A_EVAL], e[D_EVAL], R), x1y2),
+            R
+        );
+        sum = mulmod(sum, sep, R);
+    }
+
+    /// Scalar of the permutation polynomial, without the first lagrange term
+    function _permutation(uint256[23] memory e, Challenges memory ch)
+        private
+        pure
+        returns (uint256 p)
+    {
+        uint256 betaZ = mulmod(ch.beta, ch.z, R);
+        uint256[4] memory k = [uint256(1), K1, K2, K3];
+
+        p = ch.alpha;
+        for (uint256 i = 0; i < 4; i++) {
+            uint256 term = addmod(
+                addmod(e[A_EVAL + i], mulmod(betaZ, k[i], R), R),
+                ch.gamma,
+                R
+            );
+            p = mulmod(p, term, R);
+        }
+    }
+
+    /// Scalars of `q_lookup` and of the lookup permutation polynomial
+    function _lookup(uint256[23] memory e, Challenges memory ch, uint256 l1)
+        private
+        pure
+        returns (uint256 q, uint256 p)
+    {
+        uint256 kappa = mulmod(ch.lookup, ch.lookup, R);
+        uint256 onePlusDelta = addmod(1, ch.delta, R);
+
+        // a + zeta * (b + zeta * (c + zeta * q_c)) - f
+        q = mulmod(e[Q_C_EVAL], ch.zeta, R);
+        q = mulmod(addmod(e[C_EVAL], q, R), ch.zeta, R);
+        q = mulmod(addmod(e[B_EVAL], q, R), ch.zeta, R);
+        q = _sub(addmod(e[A_EVAL], q, R), e[F_EVAL]);
+        q = mulmod(q, ch.lookup, R);
+
+        // l_1(z) * kappa + (1 + delta) * (epsilon + f)
+        // * (epsilon * (1 + delta) + t + delta * t(z * omega)) * kappa^2
+        p = mulmod(
+            mulmod(onePlusDelta, addmod(ch.epsilon, e[F_EVAL], R), R),
+            addmod(
+                addmod(
+                    mulmod(ch.epsilon, onePlusDelta, R),
+                    e[TABLE_EVAL],
+                    R
+                ),
+                mulmod(ch.delta, e[TABLE_NEXT_EVAL], R),
+                R
+            ),
+            R
+        );
+        p = mulmod(addmod(l1, mulmod(p, kappa, R), R), kappa, R);
+        p = mulmod(p, ch.lookup, R);
+    }
+
+    /// Write the points of the verifier key and the generator of G1 to the
+    /// entries of the multiscalar multiplication
+    function _verifierKey(bytes memory msm) private pure {
+        assembly {
+            let entries := add(msm, 0x20)
+{{verifier_key}}
+        }
+    }
+
+    /// Write `-beta_h` and `h` to the input of the pairing check, after the
+    /// slots of `W` and `C` respectively
+    function _openingKey(bytes memory input) private pure {
+        assembly {
+            let pairing := add(input, 0x20)
+{{opening_key}}
+        }
+    }
+
+    function _appendScalar(bytes32 state, uint256 s)
+        private
+        pure
+        returns (bytes32)
+    {
+        return keccak256(abi.encodePacked(state, s));
+    }
+
+    function _appendPoint(bytes32 state, bytes calldata proof, uint256 i)
+        private
+        pure
+        returns (bytes32)
+    {
+        return keccak256(abi.encodePacked(state, proof[i * POINT:(i + 1) * POINT]));
+    }
+
+    function _challenge(bytes32 state, bytes memory label)
+        private
+        pure
+        returns (bytes32 next, uint256 c)
+    {
+        next = keccak256(abi.encodePacked(state, label));
+        uint256 low = uint256(keccak256(abi.encodePacked(next)));
+        c = addmod(mulmod(uint256(next), R_256, R), low, R);
+    }
+
+    function _setScalar(bytes memory msm, uint256 entry, uint256 s)
+        private
+        pure
+    {
+        uint256 offset = entry * MSM_ENTRY + POINT;
+        assembly {
+            mstore(add(add(msm, 0x20), offset), s)
+        }
+    }
+
+    function _addScalar(bytes memory msm, uint256 entry, uint256 s)
+        private
+        pure
+    {
+        uint256 offset = entry * MSM_ENTRY + POINT;
+        assembly {
+            let slot := add(add(msm, 0x20), offset)
+            mstore(slot, addmod(mload(slot), s, R))
+        }
+    }
+
+    function _sub(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a, R - b, R);
+    }
+
+    /// Compute `f(f - 1)(f - 2)(f - 3)`
+    function _delta(uint256 f) private pure returns (uint256) {
+        uint256 f1 = _sub(f, 1);
+        uint256 f2 = _sub(f, 2);
+        uint256 f3 = _sub(f, 3);
+        return mulmod(mulmod(f, f1, R), mulmod(f2, f3, R), R);
+    }
+
+    /// Identity of the xor and and gates over the quads `a`, `b` and `c`
+    /// with their output `w`
+    function _deltaXorAnd(
+        uint256 a,
+        uint256 b,
+        uint256 w,
+        uint256 c,
+        uint256 qC
+    ) private pure returns (uint256) {
+        uint256 ab = addmod(a, b, R);
+
+        // w[w(4w - 18(a + b) + 81) + 18(a^2 + b^2) - 81(a + b) + 83]
+        uint256 f = addmod(_sub(mulmod(4, w, R), mulmod(18, ab, R)), 81, R);
+        f = mulmod(w, f, R);
+        f = addmod(
+            f,
+            mulmod(18, addmod(mulmod(a, a, R), mulmod(b, b, R), R), R),
+            R
+        );
+        f = addmod(_sub(f, mulmod(81, ab, R)), 83, R);
+        f = mulmod(w, f, R);
+
+        // 3(a + b + c) - 2f
+        uint256 e = _sub(mulmod(3, addmod(ab, c, R), R), mulmod(2, f, R));
+        // q_c[9c - 3(a + b)]
+        uint256 q = mulmod(qC, _sub(mulmod(9, c, R), mulmod(3, ab, R)), R);
+
+        return addmod(q, e, R);
+    }
+
+    function _inverse(uint256 x) private view returns (uint256 y) {
+        require(x != 0, "PlonkVerifier: inverse of zero");
+
+        (bool ok, bytes memory out) = address(0x05).staticcall(
+            abi.encodePacked(uint256(32), uint256(32), uint256(32), x, R - 2, R)
+        );
+        require(ok, "PlonkVerifier: modexp");
+
+        y = abi.decode(out, (uint256));
+    }
+}
@@ -0,0 +1,433 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Export of a [`Verifier`] to a Solidity contract relying on the BLS12-381
+//! precompiles of EIP-2537.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use dusk_bls12_381::{BlsScalar, G2Affine};
+use dusk_jubjub::EDWARDS_D;
+
+use crate::error::Error;
+use crate::fft::EvaluationDomain;
+use crate::proof_system::Proof;
+use crate::transcript::{encode_point, encode_scalar, keccak256, Keccak};
+
+use super::Verifier;
+
+const TEMPLATE: &str = include_str!("PlonkVerifier.sol");
+
+/// Signature of the function of the contract verifying a proof
+const VERIFY: &[u8] = b"verify(bytes,uint256[])";
+
+/// Size in bytes of a point of G1 in the encoding of EIP-2537
+const POINT: usize = 128;
+
+/// Size in bytes of a point of G2 in the encoding of EIP-2537
+const POINT_G2: usize = 256;
+
+/// Number of points and of scalars of a proof encoded for the contract
+const PROOF: (usize, usize) = (11, 16);
+
+/// Number of points and of scalars of the lookup argument of a proof encoded
+/// for the contract
+const LOOKUP_PROOF: (usize, usize) = (4, 7);
+
+/// Entries of the multiscalar multiplication of the contract preceding the
+/// points of the proof: the verifier key and the generator of G1
+const MSM_PROOF: usize = 15;
+
+/// Entry of the multiscalar multiplication of the contract holding the first
+/// point of the lookup part of the verifier key
+const MSM_LOOKUP: usize = 30;
+
+impl Verifier<Keccak> {
+    /// Generate the source of a Solidity contract verifying the proofs of
+    /// the circuit
+    ///
+    /// The contract embeds the verifier key, the opening key and the base
+    /// transcript of the circuit, and exposes a single function
+    /// `verify(bytes proof, uint256[] publicInputs) returns (bool)`, whose
+    /// calldata is encoded by [`Proof::to_calldata`]. The challenges are
+    /// derived with the [`Keccak`] transcript, so only the proofs of a
+    /// [`Prover<Keccak>`](crate::composer::Prover) can be verified.
+    ///
+    /// The identities of the custom gates can't be expressed in Solidity, so
+    /// the circuits using them return [`Error::CustomGatesNotSerializable`].
+    pub fn to_solidity(&self) -> Result<String, Error> {
+        if !self.custom_verifier_key.widgets.is_empty() {
+            return Err(Error::CustomGatesNotSerializable);
+        }
+
+        let domain = EvaluationDomain::new(self.verifier_key.n)?;
+
+        let lookup = self.verifier_key.lookup.is_some();
+        let (points, scalars) = proof_layout(lookup);
+        let msm_size = match lookup {
+            true => MSM_LOOKUP + 5,
+            false => MSM_PROOF + points,
+        };
+
+        // 2^256, as a 512 bit little endian integer
+        let mut wide = [0u8; 64];
+        wide[32] = 1;
+        let r_256 = BlsScalar::from_bytes_wide(&wide);
+
+        let replacements = [
+            ("{{r_256}}", scalar(&r_256)),
+            ("{{edwards_d}}", scalar(&EDWARDS_D)),
+            ("{{n}}", format!("{}", domain.size())),
+            ("{{n_inv}}", scalar(&domain.size_inv)),
+            ("{{omega}}", scalar(&domain.group_gen)),
+            ("{{lookup}}", format!("{lookup}")),
+            ("{{points}}", format!("{points}")),
+            ("{{scalars}}", format!("{scalars}")),
+            ("{{msm_size}}", format!("{msm_size}")),
+            (
+                "{{public_inputs}}",
+                format!("{}", self.public_input_indexes.len()),
+            ),
+            ("{{transcript}}", hex(self.transcript.state())),
+            (
+                "{{public_input_evaluation}}",
+                self.public_input_evaluation(&domain),
+            ),
+            ("{{verifier_key}}", self.verifier_key_points()),
+            ("{{opening_key}}", self.opening_key_points()),
+        ];
+
+        Ok(replacements
+            .iter()
+            .fold(String::from(TEMPLATE), |source, (pattern, value)| {
+                source.replace(pattern, value)
+            }))
+    }
+
+    /// Statements summing `pi_i / (omega^-i * z - 1)` over the public inputs
+    fn public_input_evaluation(&self, domain: &EvaluationDomain) -> String {
+        let mut code = String::new();
+
+        if self.public_input_indexes.is_empty() {
+            code.push_str("        // the circuit has no public inputs");
+        }
+
+        self.public_input_indexes
+            .iter()
+            .enumerate()
+            .for_each(|(i, index)| {
+                let basis = domain.group_gen_inv.pow(&[*index as u64, 0, 0, 0]);
+
+                if i > 0 {
+                    code.push('\n');
+                }
+                let _ = write!(
+                    code,
+                    "        // public input of the gate {index}
+        sum = addmod(
+            sum,
+            mulmod(
+                publicInputs[{i}],
+                _inverse(_sub(mulmod({basis}, z, R), 1)),
+                R
+            ),
+            R
+        );",
+                    basis = scalar(&basis),
+                );
+            });
+
+        code
+    }
+
+    /// Statements writing the points of the verifier key, followed by the
+    /// generator of G1 and the lookup part of the verifier key, to the
+    /// entries of the multiscalar multiplication
+    fn verifier_key_points(&self) -> String {
+        let vk = &self.verifier_key;
+
+        let points = [
+            ("q_m", vk.arithmetic.q_m.0),
+            ("q_l", vk.arithmetic.q_l.0),
+            ("q_r", vk.arithmetic.q_r.0),
+            ("q_o", vk.arithmetic.q_o.0),
+            ("q_4", vk.arithmetic.q_4.0),
+            ("q_c", vk.arithmetic.q_c.0),
+            ("q_range", vk.range.q_range.0),
+            ("q_logic", vk.logic.q_logic.0),
+            ("q_fixed_group_add", vk.fixed_base.q_fixed_group_add.0),
+            (
+                "q_variable_group_add",
+                vk.variable_base.q_variable_group_add.0,
+            ),
+            ("s_sigma_1", vk.permutation.s_sigma_1.0),
+            ("s_sigma_2", vk.permutation.s_sigma_2.0),
+            ("s_sigma_3", vk.permutation.s_sigma_3.0),
+            ("s_sigma_4", vk.permutation.s_sigma_4.0),
+            ("g", self.opening_key.g),
+        ];
+
+        let lookup_points = vk.lookup.iter().flat_map(|lookup| {
+            [
+                ("q_lookup", lookup.q_lookup.0),
+                ("table_1", lookup.table_1.0),
+                ("table_2", lookup.table_2.0),
+                ("table_3", lookup.table_3.0),
+                ("table_4", lookup.table_4.0),
+            ]
+        });
+
+        let mut code = String::new();
+        points
+            .into_iter()
+            .enumerate()
+            .chain((MSM_LOOKUP..).zip(lookup_points))
+            .for_each(|(i, (name, point))| {
+                // an entry is a point followed by its scalar
+                let offset = i * (POINT + 32);
+                mstore(
+                    &mut code,
+                    "entries",
+                    name,
+                    offset,
+                    &encode_point(&point),
+                );
+            });
+
+        code.pop();
+        code
+    }
+
+    /// Statements writing `-beta_h` and `h` to the input of the pairing check
+    fn opening_key_points(&self) -> String {
+        let neg_beta_h = encode_point_g2(&-self.opening_key.beta_h);
+        let h = encode_point_g2(&self.opening_key.h);
+
+        let mut code = String::new();
+        mstore(&mut code, "pairing", "-beta_h", POINT, &neg_beta_h);
+        mstore(&mut code, "pairing", "h", 2 * POINT + POINT_G2, &h);
+
+        code.pop();
+        code
+    }
+}
+
+impl Proof {
+    /// Encode the proof and its public inputs as the calldata of the function
+    /// `verify(bytes,uint256[])` of the contract generated by
+    /// [`Verifier::to_solidity`]
+    ///
+    /// The proof is encoded as its 11 commitments, as points of EIP-2537,
+    /// followed by its 16 evaluations, as 32 byte big endian integers, in the
+    /// order they are appended to the transcript with the linearization
+    /// polynomial last. The proofs of circuits with lookups append the 4
+    /// commitments of the lookup argument to the commitments, and its 7
+    /// evaluations to the evaluations.
+    pub fn to_calldata(&self, public_inputs: &[BlsScalar]) -> Vec<u8> {
+        let proof = self.to_evm_bytes();
+        let proof_size = proof.len();
+
+        let mut calldata = Vec::with_capacity(
+            4 + 4 * 32 + proof_size + 32 * public_inputs.len(),
+        );
+
+        calldata.extend(&keccak256(VERIFY)[..4]);
+
+        // offsets of the arguments, the proof being a multiple of 32 bytes
+        calldata.extend(word(2 * 32));
+        calldata.extend(word(3 * 32 + proof_size));
+
+        calldata.extend(word(proof_size));
+        calldata.extend(proof);
+
+        calldata.extend(word(public_inputs.len()));
+        public_inputs
+            .iter()
+            .for_each(|pi| calldata.extend(encode_scalar(pi)));
+
+        calldata
+    }
+
+    fn to_evm_bytes(&self) -> Vec<u8> {
+        let (points, scalars) = proof_layout(self.lookup.is_some());
+        let mut bytes = Vec::with_capacity(points * POINT + scalars * 32);
+
+        let commitments = [
+            &self.a_comm,
+            &self.b_comm,
+            &self.c_comm,
+            &self.d_comm,
+            &self.z_comm,
+            &self.t_low_comm,
+            &self.t_mid_comm,
+            &self.t_high_comm,
+            &self.t_4_comm,
+            &self.w_z_chall_comm,
+            &self.w_z_chall_w_comm,
+        ];
+        let lookup_commitments = self.lookup.iter().flat_map(|lookup| {
+            [
+                &lookup.f_comm,
+                &lookup.h_1_comm,
+                &lookup.h_2_comm,
+                &lookup.p_comm,
+            ]
+        });
+
+        commitments
+            .into_iter()
+            .chain(lookup_commitments)
+            .for_each(|commitment| bytes.extend(encode_point(&commitment.0)));
+
+        let evaluations = &self.evaluations;
+        let evals = [
+            &evaluations.a_eval,
+            &evaluations.b_eval,
+            &evaluations.c_eval,
+            &evaluations.d_eval,
+            &evaluations.a_next_eval,
+            &evaluations.b_next_eval,
+            &evaluations.d_next_eval,
+            &evaluations.s_sigma_1_eval,
+            &evaluations.s_sigma_2_eval,
+            &evaluations.s_sigma_3_eval,
+            &evaluations.q_arith_eval,
+            &evaluations.q_c_eval,
+            &evaluations.q_l_eval,
+            &evaluations.q_r_eval,
+            &evaluations.perm_eval,
+            &evaluations.r_poly_eval,
+        ];
+        let lookup_evals = self.lookup.iter().flat_map(|lookup| {
+            let evaluations = &lookup.evaluations;
+            [
+                &evaluations.f_eval,
+                &evaluations.h_1_eval,
+                &evaluations.h_1_next_eval,
+                &evaluations.h_2_eval,
+                &evaluations.table_eval,
+                &evaluations.table_next_eval,
+                &evaluations.lookup_perm_eval,
+            ]
+        });
+
+        evals
+            .into_iter()
+            .chain(lookup_evals)
+            .for_each(|eval| bytes.extend(encode_scalar(eval)));
+
+        bytes
+    }
+}
+
+/// Number of points and of scalars of a proof encoded for the contract
+fn proof_layout(lookup: bool) -> (usize, usize) {
+    match lookup {
+        true => (PROOF.0 + LOOKUP_PROOF.0, PROOF.1 + LOOKUP_PROOF.1),
+        false => PROOF,
+    }
+}
+
+/// Append the assembly statements writing the non-zero words of `bytes` at
+/// `offset` of the memory pointed by `pointer`
+fn mstore(
+    code: &mut String,
+    pointer: &str,
+    name: &str,
+    offset: usize,
+    bytes: &[u8],
+) {
+    let _ = writeln!(code, "            // {name}");
+
+    bytes
+        .chunks_exact(32)
+        .enumerate()
+        .filter(|(_, word)| word.iter().any(|b| *b != 0))
+        .for_each(|(i, word)| {
+            let _ = writeln!(
+                code,
+                "            mstore(add({pointer}, {:#06x}), {})",
+                offset + i * 32,
+                hex(word),
+            );
+        });
+}
+
+/// Encode a point of G2 as the 256 byte big endian `(x_0, x_1, y_0, y_1)`
+/// coordinates of EIP-2537, each padded to 64 bytes, with the identity
+/// encoded as all zeroes
+fn encode_point_g2(point: &G2Affine) -> [u8; POINT_G2] {
+    let mut uncompressed = point.to_uncompressed();
+
+    // the coordinates are smaller than 2^381, so the three most significant
+    // bits only carry the flags of the encoding
+    uncompressed[0] &= 0x1f;
+
+    // the uncompressed encoding is `(x_1, x_0, y_1, y_0)`
+    let mut bytes = [0u8; POINT_G2];
+    [1, 0, 3, 2].iter().enumerate().for_each(|(i, c)| {
+        bytes[i * 64 + 16..(i + 1) * 64]
+            .copy_from_slice(&uncompressed[c * 48..(c + 1) * 48]);
+    });
+    bytes
+}
+
+/// Encode an integer as a 32 byte big endian word of the ABI
+fn word(n: usize) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&(n as u64).to_be_bytes());
+    bytes
+}
+
+fn scalar(s: &BlsScalar) -> String {
+    hex(&encode_scalar(s))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + 2 * bytes.len());
+    hex.push_str("0x");
+    bytes.iter().for_each(|b| {
+        let _ = write!(hex, "{b:02x}");
+    });
+    hex
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dusk_bls12_381::G2Projective;
+
+    #[test]
+    fn encodings() {
+        assert_eq!(encode_point_g2(&G2Affine::identity()), [0u8; POINT_G2]);
+
+        let h = G2Affine::generator();
+        let uncompressed = h.to_uncompressed();
+        let bytes = encode_point_g2(&h);
+        assert_eq!(&bytes[16..64], &uncompressed[48..96]);
+        assert_eq!(&bytes[80..128], &uncompressed[..48]);
+        assert_eq!(&bytes[144..192], &uncompressed[144..]);
+        assert_eq!(&bytes[208..], &uncompressed[96..144]);
+        assert!(bytes
+            .chunks_exact(64)
+            .all(|c| c[..16].iter().all(|b| *b == 0)));
+
+        let p = G2Affine::from(G2Projective::generator() * BlsScalar::from(3));
+        assert_ne!(encode_point_g2(&p), bytes);
+
+        assert_eq!(word(0x0102)[30..], [0x01, 0x02]);
+        assert_eq!(hex(&[0x00, 0xab]), "0x00ab");
+    }
+
+    #[test]
+    fn selector() {
+        // selector of the ERC-20 transfer
+        let transfer = keccak256(b"transfer(address,uint256)");
+        assert_eq!(hex(&transfer[..4]), "0xa9059cbb");
+    }
+}
@@ -36,6 +36,8 @@ pub(crate) enum Selector {
     GroupAddFixedBase = 0x0a,
     /// Curve addition with variable base coefficient (internal use)
     GroupAddVariableBase = 0x0b,
+    /// Lookup coefficient (internal use)
+    Lookup = 0x0c,
 }
 
 /// Wire used to address a witness inside of a [`Constraint`]
@@ -94,7 +96,7 @@ impl AsRef<[BlsScalar]> for Constraint {
 
 impl Constraint {
     /// Internal coefficients count.
-    pub const COEFFICIENTS: usize = 12;
+    pub const COEFFICIENTS: usize = 13;
 
     /// Internal witnesses count.
     pub const WITNESSES: usize = 4;
@@ -257,4 +259,8 @@ impl Constraint {
     pub(crate) fn group_add_variable_base(s: &Self) -> Self {
         Self::from_external(s).set(Selector::GroupAddVariableBase, 1)
     }
+
+    pub(crate) fn lookup(s: &Self) -> Self {
+        Self::from_external(s).set(Selector::Lookup, 1)
+    }
 }
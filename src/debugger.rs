@@ -15,6 +15,7 @@ use dusk_cdf::{
     Encoder, EncoderContextFileProvider, Polynomial, Selectors, WiredWitnesses,
 };
 
+use crate::composer::Builder;
 use crate::constraint_system::{Constraint, Selector, WiredWitness, Witness};
 use crate::runtime::RuntimeEvent;
 
@@ -31,25 +32,29 @@ impl Debugger {
         let mut source = None;
 
         backtrace::trace(|frame| {
-            // Resolve this instruction pointer to a symbol name
+            // Resolve this instruction pointer to a symbol name. The symbols
+            // of a frame start from the innermost inlined function, so the
+            // first match is the caller. Symbols whose source isn't on disk,
+            // as the ones of the precompiled standard library, are skipped
+            // since the CDF output embeds the source files
             backtrace::resolve_frame(frame, |symbol| {
-                if symbol
-                    .name()
-                    .map(|n| n.to_string())
-                    .filter(|s| !s.starts_with("backtrace::"))
-                    .filter(|s| !s.starts_with("dusk_plonk::"))
-                    .filter(|s| !s.starts_with("core::"))
-                    .filter(|s| !s.starts_with("std::"))
-                    .is_some()
+                if source.is_none()
+                    && symbol
+                        .name()
+                        .map(|n| Self::symbol_path(&n.to_string()))
+                        .filter(|s| !s.starts_with("backtrace::"))
+                        .filter(|s| !s.starts_with("dusk_plonk::"))
+                        .filter(|s| !s.starts_with("alloc::"))
+                        .filter(|s| !s.starts_with("core::"))
+                        .filter(|s| !s.starts_with("std::"))
+                        .is_some()
                 {
-                    if let Some(path) = symbol.filename() {
+                    if let Some(path) =
+                        symbol.filename().and_then(|p| p.canonicalize().ok())
+                    {
                         let line = symbol.lineno().unwrap_or_default() as u64;
                         let col = symbol.colno().unwrap_or_default() as u64;
-                        let path = path
-                            .canonicalize()
-                            .unwrap_or_default()
-                            .display()
-                            .to_string();
+                        let path = path.display().to_string();
 
                         source.replace(EncodableSource::new(line, col, path));
                     }
@@ -62,7 +67,28 @@ impl Debugger {
         source.unwrap_or_default()
     }
 
-    fn write_output(&self) {
+    /// Path of a demangled symbol, without the leading `<` of the trait
+    /// implementations and the crate disambiguators, as in
+    /// `<backtrace[1f2e3d4c5b6a7980]::...`
+    fn symbol_path(name: &str) -> String {
+        let mut path = String::with_capacity(name.len());
+        let mut disambiguator = false;
+
+        name.trim_start_matches('<').chars().for_each(|c| match c {
+            '[' => disambiguator = true,
+            ']' if disambiguator => disambiguator = false,
+            c if !disambiguator => path.push(c),
+            _ => (),
+        });
+
+        path
+    }
+
+    /// Output the recorded circuit to the file at `CDF_OUTPUT`, if set
+    ///
+    /// The constraints are evaluated over the rows of `builder`, the builder
+    /// the events were recorded from.
+    pub(crate) fn write_output(&self, builder: &Builder) {
         let path = match env::var("CDF_OUTPUT") {
             Ok(path) => PathBuf::from(path),
             Err(env::VarError::NotPresent) => return (),
@@ -80,6 +106,11 @@ impl Debugger {
             EncodableWitness::new(id, None, value, source)
         });
 
+        // the next row of the last constraint is the first padding row of the
+        // domain, if any, as when proving
+        let size = builder.domain_size();
+        let widget_rows = builder.widget_rows();
+
         let constraints =
             self.constraints
                 .iter()
@@ -108,40 +139,10 @@ impl Debugger {
                         o: c.witness(WiredWitness::O).index(),
                     };
 
-                    let wa = self
-                        .witnesses
-                        .get(witnesses.a)
-                        .map(|(_, _, v)| *v)
-                        .unwrap_or_default();
-
-                    let wb = self
-                        .witnesses
-                        .get(witnesses.b)
-                        .map(|(_, _, v)| *v)
-                        .unwrap_or_default();
-
-                    let wd = self
-                        .witnesses
-                        .get(witnesses.d)
-                        .map(|(_, _, v)| *v)
-                        .unwrap_or_default();
-
-                    let wo = self
-                        .witnesses
-                        .get(witnesses.o)
-                        .map(|(_, _, v)| *v)
-                        .unwrap_or_default();
-
-                    // TODO check arith, range, logic & ecc wires
-                    let evaluation = qm * wa * wb
-                        + ql * wa
-                        + qr * wb
-                        + qd * wd
-                        + qo * wo
-                        + qc
-                        + pi;
-
-                    let evaluation = evaluation == BlsScalar::zero();
+                    let wires = builder.gate_wires(id, size);
+                    let evaluation = builder
+                        .unsatisfied_gate(id, &wires, &widget_rows)
+                        .is_none();
 
                     let selectors = Selectors {
                         qm: qm.to_bytes().into(),
@@ -194,6 +195,18 @@ impl Debugger {
         }
     }
 
+    /// Source location of the constraint appended at `index`, if it was
+    /// resolved
+    pub(crate) fn constraint_source(&self, index: usize) -> Option<String> {
+        self.constraints
+            .get(index)
+            .map(|(source, _)| source)
+            .filter(|source| !source.path().is_empty())
+            .map(|source| {
+                format!("{}:{}:{}", source.path(), source.line(), source.col())
+            })
+    }
+
     pub(crate) fn event(&mut self, event: RuntimeEvent) {
         match event {
             RuntimeEvent::WitnessAppended { w, v } => {
@@ -204,9 +217,15 @@ impl Debugger {
                 self.constraints.push((Self::resolve_caller(), c));
             }
 
-            RuntimeEvent::ProofFinished => {
-                self.write_output();
-            }
+            // the witnesses of a CDF file have no name, the names and
+            // namespaces are kept by the runtime, and the output is written
+            // by the builder of the proof
+            RuntimeEvent::WitnessNamed { .. }
+            | RuntimeEvent::NamespaceEntered { .. }
+            | RuntimeEvent::NamespaceExited
+            | RuntimeEvent::GadgetEntered { .. }
+            | RuntimeEvent::GadgetExited { .. }
+            | RuntimeEvent::ProofFinished => (),
         }
     }
 }
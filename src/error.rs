@@ -8,6 +8,8 @@
 
 use dusk_bytes::Error as DuskBytesError;
 
+use crate::proof_system::widget::GateType;
+
 /// Defines all possible errors that can be encountered in PLONK.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Error {
@@ -35,6 +37,15 @@ pub enum Error {
     /// This error occurs when the Prover structure already contains a
     /// preprocessed circuit inside, but you call preprocess again.
     CircuitAlreadyPreprocessed,
+    /// This error occurs when the verification of a batch of proofs fails,
+    /// reporting the first proof of the batch that is invalid.
+    InvalidProofInBatch {
+        /// Index of the invalid proof in the batch
+        index: usize,
+    },
+    /// This error occurs when the verifiers of a batch of proofs don't share
+    /// the same opening key.
+    InconsistentOpeningKeys,
     /// This error occurs when the circuit for the proof has a different size
     /// than the prover circuit description
     InvalidCircuitSize,
@@ -57,9 +68,6 @@ pub enum Error {
     /// This error occurs when the user tries to commit to a polynomial whose
     /// degree is larger than the supported degree for that proving key.
     PolynomialDegreeTooLarge,
-    /// This error occurs when the user tries to commit to a polynomial whose
-    /// degree is zero.
-    PolynomialDegreeIsZero,
     /// This error occurs when the pairing check fails at being equal to the
     /// Identity point.
     PairingCheckFailure,
@@ -94,6 +102,41 @@ pub enum Error {
     },
     /// The provided compressed circuit bytes representation is invalid.
     InvalidCompressedCircuit,
+    /// This error occurs when the verifier of a circuit with custom gates is
+    /// exported to Solidity, since the constraints of the gates can't be
+    /// represented outside of the crate.
+    CustomGatesNotSerializable,
+    /// This error occurs when a prover, a verifier or a compressed circuit
+    /// with custom gates is deserialized without the implementation of one
+    /// of its gates.
+    UnregisteredCustomGate,
+    /// This error occurs when a custom gate is appended to a circuit with the
+    /// selector label of another gate of the circuit, or with a label the
+    /// built-in gates append to the transcript.
+    DuplicateCustomGateSelector {
+        /// Label of the selector of the gate
+        selector: &'static [u8],
+    },
+    /// This error occurs when the number of threads of a prover is zero.
+    InvalidThreadCount,
+    /// This error occurs when the threads of the pool of a prover can't be
+    /// spawned.
+    ThreadPoolBuildFailed,
+    /// This error occurs when the prover checks the constraints of a circuit
+    /// before proving it, and a gate isn't satisfied by the witnesses.
+    UnsatisfiedConstraint {
+        /// Index of the unsatisfied constraint
+        index: usize,
+        /// Gate of the constraint that isn't satisfied
+        gate_type: GateType,
+    },
+    /// This error occurs when the witnesses given to a gadget make it
+    /// unsatisfiable, such as a zero divisor or two equal witnesses asserted
+    /// to be different.
+    UnsatisfiableGadget {
+        /// Name of the gadget
+        gadget: &'static str,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -112,6 +155,12 @@ impl std::fmt::Display for Error {
             Self::ProofVerificationError => {
                 write!(f, "proof verification failed")
             }
+            Self::InvalidProofInBatch { index } => {
+                write!(f, "proof {} of the batch failed verification", index)
+            }
+            Self::InconsistentOpeningKeys => {
+                write!(f, "the verifiers of the batch have different opening keys")
+            }
             Self::CircuitInputsNotFound => {
                 write!(f, "circuit inputs not found")
             }
@@ -144,9 +193,6 @@ impl std::fmt::Display for Error {
                 f,
                 "proving key is not large enough to commit to said polynomial"
             ),
-            Self::PolynomialDegreeIsZero => {
-                write!(f, "cannot commit to polynomial of zero degree")
-            }
             Self::PairingCheckFailure => write!(f, "pairing check failed"),
             Self::NotEnoughBytes => write!(f, "not enough bytes left to read"),
             Self::PointMalformed => write!(f, "BLS point bytes malformed"),
@@ -164,6 +210,13 @@ impl std::fmt::Display for Error {
                 expected, provided,
             } => write!(f, "The provided public inputs set of length {} doesn't match the processed verifier: {}", provided, expected),
             Self::InvalidCompressedCircuit => write!(f, "invalid compressed circuit"),
+            Self::CustomGatesNotSerializable => write!(f, "circuits with custom gates can't be serialized"),
+            Self::UnregisteredCustomGate => write!(f, "the implementation of a custom gate wasn't registered"),
+            Self::DuplicateCustomGateSelector { selector } => write!(f, "the selector {} of a custom gate is already used by another gate", String::from_utf8_lossy(selector)),
+            Self::InvalidThreadCount => write!(f, "a prover needs at least one thread"),
+            Self::ThreadPoolBuildFailed => write!(f, "the threads of the prover couldn't be spawned"),
+            Self::UnsatisfiedConstraint { index, gate_type } => write!(f, "constraint {} fails its {} gate", index, gate_type),
+            Self::UnsatisfiableGadget { gadget } => write!(f, "the witnesses of {} can't satisfy it", gadget),
         }
     }
 }
@@ -0,0 +1,322 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Hades252 permutation of width 5 and a sponge construction on top of it.
+//!
+//! The native functions mirror the circuit gadgets of
+//! [`Composer::component_hades_permutation`] and [`SpongeGadget`] so the
+//! witness values computed outside of a circuit line up with the ones
+//! computed inside of it.
+
+use alloc::boxed::Box;
+use dusk_bls12_381::BlsScalar;
+use once_cell::race::OnceBox;
+use sha2::{Digest, Sha512};
+
+use crate::composer::Composer;
+use crate::constraint_system::{Constraint, Witness};
+
+/// Width of the permutation state
+pub const WIDTH: usize = 5;
+
+/// Number of full rounds of the permutation, split equally before and after
+/// the partial rounds
+pub const FULL_ROUNDS: usize = 8;
+
+/// Number of partial rounds of the permutation
+pub const PARTIAL_ROUNDS: usize = 59;
+
+/// Number of elements absorbed or squeezed by the sponge per permutation. The
+/// first element of the state is the capacity of the sponge.
+pub const RATE: usize = WIDTH - 1;
+
+const CONSTANTS: usize = 960;
+
+// The round constants and the MDS matrix are generated on their first use,
+// and shared by every permutation from then on
+static ROUND_CONSTANTS: OnceBox<[BlsScalar; CONSTANTS]> = OnceBox::new();
+static MDS_MATRIX: OnceBox<[[BlsScalar; WIDTH]; WIDTH]> = OnceBox::new();
+
+/// Round constants of the permutation, in order of use
+pub(crate) fn constants() -> &'static [BlsScalar; CONSTANTS] {
+    ROUND_CONSTANTS.get_or_init(|| Box::new(generate_constants()))
+}
+
+/// MDS matrix of the permutation
+pub(crate) fn mds() -> &'static [[BlsScalar; WIDTH]; WIDTH] {
+    MDS_MATRIX.get_or_init(|| Box::new(generate_mds()))
+}
+
+// Extracted from
+// https://github.com/dusk-network/Hades252/blob/a4d55e06ee9ff7f549043582e8d194eb0a01bf24/assets/HOWTO.md
+
+fn generate_constants() -> [BlsScalar; CONSTANTS] {
+    let mut cnst = [BlsScalar::zero(); CONSTANTS];
+    let mut p = BlsScalar::one();
+    let mut bytes = b"poseidon-for-plonk".to_vec();
+
+    cnst.iter_mut().for_each(|c| {
+        bytes = Sha512::digest(bytes.as_slice()).to_vec();
+
+        let mut v = [0x00u8; 64];
+        v.copy_from_slice(&bytes[0..64]);
+
+        *c = BlsScalar::from_bytes_wide(&v) + p;
+        p = *c;
+    });
+
+    cnst
+}
+
+fn generate_mds() -> [[BlsScalar; WIDTH]; WIDTH] {
+    let mut matrix = [[BlsScalar::zero(); WIDTH]; WIDTH];
+    let mut xs = [BlsScalar::zero(); WIDTH];
+    let mut ys = [BlsScalar::zero(); WIDTH];
+
+    // Generate x and y values deterministically for the cauchy matrix
+    // where x[i] != y[i] to allow the values to be inverted
+    // and there are no duplicates in the x vector or y vector, so that the
+    // determinant is always non-zero [a b]
+    // [c d]
+    // det(M) = (ad - bc) ; if a == b and c == d => det(M) =0
+    // For an MDS matrix, every possible mxm submatrix, must have det(M) != 0
+    (0..WIDTH).for_each(|i| {
+        xs[i] = BlsScalar::from(i as u64);
+        ys[i] = BlsScalar::from((i + WIDTH) as u64);
+    });
+
+    let mut m = 0;
+    (0..WIDTH).for_each(|i| {
+        (0..WIDTH).for_each(|j| {
+            matrix[m][j] = (xs[i] + ys[j]).invert().unwrap();
+        });
+        m += 1;
+    });
+
+    matrix
+}
+
+/// Returns `true` if `round` is one of the full rounds of the permutation
+pub(crate) const fn is_full_round(round: usize) -> bool {
+    round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS
+}
+
+fn quintic_s_box(x: &BlsScalar) -> BlsScalar {
+    x.square().square() * x
+}
+
+/// Applies the Hades252 permutation to `state`.
+///
+/// Every round adds the round constants to the state, applies the quintic
+/// s-box to the whole state for the full rounds and to the last element for
+/// the partial rounds, and multiplies the result by the MDS matrix.
+pub fn permutation(state: &mut [BlsScalar; WIDTH]) {
+    let constants = constants();
+    let mds = mds();
+
+    let mut constants = constants.iter();
+
+    (0..FULL_ROUNDS + PARTIAL_ROUNDS).for_each(|round| {
+        state
+            .iter_mut()
+            .zip(&mut constants)
+            .for_each(|(s, c)| *s += c);
+
+        if is_full_round(round) {
+            state.iter_mut().for_each(|s| *s = quintic_s_box(s));
+        } else {
+            state[WIDTH - 1] = quintic_s_box(&state[WIDTH - 1]);
+        }
+
+        let mut result = [BlsScalar::zero(); WIDTH];
+        result.iter_mut().zip(mds.iter()).for_each(|(r, row)| {
+            *r = row.iter().zip(state.iter()).map(|(m, s)| m * s).sum();
+        });
+
+        *state = result;
+    });
+}
+
+/// Sponge over the Hades252 permutation.
+///
+/// Absorbs and squeezes [`RATE`] elements per permutation. The input is padded
+/// with a single `1` when switching from absorbing to squeezing, so inputs
+/// that differ only by trailing zeroes result in different outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sponge {
+    state: [BlsScalar; WIDTH],
+    position: usize,
+    squeezing: bool,
+}
+
+impl Default for Sponge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sponge {
+    /// Creates a new sponge with an empty state
+    pub const fn new() -> Self {
+        Self {
+            state: [BlsScalar::zero(); WIDTH],
+            position: 0,
+            squeezing: false,
+        }
+    }
+
+    /// Absorbs `input` into the sponge
+    pub fn absorb(&mut self, input: &[BlsScalar]) {
+        if self.squeezing {
+            self.squeezing = false;
+            self.position = RATE;
+        }
+
+        input.iter().for_each(|x| {
+            if self.position == RATE {
+                permutation(&mut self.state);
+                self.position = 0;
+            }
+
+            self.state[1 + self.position] += x;
+            self.position += 1;
+        });
+    }
+
+    /// Squeezes an element out of the sponge
+    pub fn squeeze(&mut self) -> BlsScalar {
+        if !self.squeezing {
+            if self.position == RATE {
+                permutation(&mut self.state);
+                self.position = 0;
+            }
+
+            self.state[1 + self.position] += BlsScalar::one();
+
+            permutation(&mut self.state);
+            self.position = 0;
+            self.squeezing = true;
+        }
+
+        if self.position == RATE {
+            permutation(&mut self.state);
+            self.position = 0;
+        }
+
+        let output = self.state[1 + self.position];
+        self.position += 1;
+
+        output
+    }
+}
+
+/// Circuit gadget of the [`Sponge`].
+///
+/// Each permutation is appended to the circuit via
+/// [`Composer::component_hades_permutation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpongeGadget {
+    state: [Witness; WIDTH],
+    position: usize,
+    squeezing: bool,
+}
+
+impl Default for SpongeGadget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpongeGadget {
+    /// Creates a new sponge gadget with an empty state
+    pub const fn new() -> Self {
+        Self {
+            state: [Witness::ZERO; WIDTH],
+            position: 0,
+            squeezing: false,
+        }
+    }
+
+    /// Absorbs `input` into the sponge, appending the required gates to
+    /// `composer`
+    pub fn absorb<C: Composer>(&mut self, composer: &mut C, input: &[Witness]) {
+        if self.squeezing {
+            self.squeezing = false;
+            self.position = RATE;
+        }
+
+        input.iter().for_each(|x| {
+            if self.position == RATE {
+                self.state = composer.component_hades_permutation(&self.state);
+                self.position = 0;
+            }
+
+            let s = &mut self.state[1 + self.position];
+            *s = composer
+                .gate_add(Constraint::new().left(1).a(*s).right(1).b(*x));
+            self.position += 1;
+        });
+    }
+
+    /// Squeezes a witness out of the sponge, appending the required gates to
+    /// `composer`
+    pub fn squeeze<C: Composer>(&mut self, composer: &mut C) -> Witness {
+        if !self.squeezing {
+            if self.position == RATE {
+                self.state = composer.component_hades_permutation(&self.state);
+                self.position = 0;
+            }
+
+            let s = &mut self.state[1 + self.position];
+            *s = composer.gate_add(Constraint::new().left(1).a(*s).constant(1));
+
+            self.state = composer.component_hades_permutation(&self.state);
+            self.position = 0;
+            self.squeezing = true;
+        }
+
+        if self.position == RATE {
+            self.state = composer.component_hades_permutation(&self.state);
+            self.position = 0;
+        }
+
+        let output = self.state[1 + self.position];
+        self.position += 1;
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn constants_are_generated_once() {
+        assert!(core::ptr::eq(constants(), constants()));
+        assert!(core::ptr::eq(mds(), mds()));
+
+        assert_eq!(*constants(), generate_constants());
+        assert_eq!(*mds(), generate_mds());
+    }
+
+    #[test]
+    fn sponge_pads_the_input() {
+        let mut a = Sponge::new();
+        let mut b = Sponge::new();
+
+        a.absorb(&[BlsScalar::from(7)]);
+        b.absorb(&[BlsScalar::from(7), BlsScalar::zero()]);
+
+        assert_ne!(a.squeeze(), b.squeeze());
+
+        // squeezing past the rate must keep producing fresh elements
+        let outputs: Vec<_> = (0..2 * RATE).map(|_| a.squeeze()).collect();
+        outputs.iter().enumerate().for_each(|(i, x)| {
+            assert!(!outputs[i + 1..].contains(x));
+        });
+    }
+}
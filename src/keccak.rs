@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Keccak-f\[1600\] permutation and the Keccak-256 hash used by Ethereum.
+//!
+//! The native functions are the reference of the circuit gadgets
+//! [`Composer::component_keccak_f1600`] and
+//! [`Composer::component_keccak256`]. The state is a set of 64 bit lanes,
+//! where the lane `(x, y)` is stored at the index `x + 5 · y`.
+
+use alloc::vec::Vec;
+
+use crate::composer::Composer;
+use crate::constraint_system::Witness;
+use crate::word;
+
+/// Number of lanes of the permutation state
+pub const LANES: usize = 25;
+
+/// Number of lanes absorbed per permutation by Keccak-256
+pub const RATE_LANES: usize = 17;
+
+/// Number of lanes of a Keccak-256 digest
+pub const DIGEST_LANES: usize = 4;
+
+const ROUNDS: usize = 24;
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// Rotation offsets of the lane `(x, y)`, indexed as `x + 5 · y`
+const ROTATIONS: [usize; LANES] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+];
+
+// Index of the lane `(y, 2x + 3y)` the lane `(x, y)` is moved to by the pi
+// step
+const fn pi(x: usize, y: usize) -> usize {
+    y + 5 * ((2 * x + 3 * y) % 5)
+}
+
+/// Applies the Keccak-f\[1600\] permutation to `state`
+pub fn permutation(state: &mut [u64; LANES]) {
+    ROUND_CONSTANTS.iter().for_each(|rc| {
+        // theta
+        let mut c = [0u64; 5];
+        c.iter_mut().enumerate().for_each(|(x, c)| {
+            *c = (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]);
+        });
+        (0..LANES).for_each(|i| {
+            let x = i % 5;
+            state[i] ^= c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+        });
+
+        // rho and pi
+        let mut b = [0u64; LANES];
+        (0..LANES).for_each(|i| {
+            b[pi(i % 5, i / 5)] = state[i].rotate_left(ROTATIONS[i] as u32);
+        });
+
+        // chi
+        (0..LANES).for_each(|i| {
+            let (x, y) = (i % 5, i / 5);
+            state[i] =
+                b[i] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+        });
+
+        // iota
+        state[0] ^= rc;
+    });
+}
+
+/// Pads `message` and splits it into blocks of little endian lanes.
+///
+/// The message is appended with the Keccak multi-rate padding, a `1` bit
+/// followed by the minimum amount of `0` bits and a final `1` bit, so the
+/// padded message is a multiple of the rate.
+pub fn pad(message: &[u8]) -> Vec<[u64; RATE_LANES]> {
+    let rate = RATE_LANES * 8;
+
+    let padding = rate - message.len() % rate;
+
+    let mut bytes = message.to_vec();
+    bytes.resize(message.len() + padding, 0x00);
+    bytes[message.len()] |= 0x01;
+    bytes[message.len() + padding - 1] |= 0x80;
+
+    bytes
+        .chunks(rate)
+        .map(|chunk| {
+            let mut block = [0u64; RATE_LANES];
+            block.iter_mut().zip(chunk.chunks(8)).for_each(|(l, b)| {
+                let mut lane = [0u8; 8];
+                lane.copy_from_slice(b);
+                *l = u64::from_le_bytes(lane);
+            });
+            block
+        })
+        .collect()
+}
+
+/// Computes the Keccak-256 digest of `message` as little endian lanes.
+///
+/// The bytes of the digest are the concatenation of the little endian bytes
+/// of the lanes.
+pub fn digest(message: &[u8]) -> [u64; DIGEST_LANES] {
+    let mut state = [0u64; LANES];
+    pad(message).iter().for_each(|block| {
+        state
+            .iter_mut()
+            .zip(block.iter())
+            .for_each(|(s, b)| *s ^= b);
+        permutation(&mut state);
+    });
+
+    let mut digest = [0u64; DIGEST_LANES];
+    digest.copy_from_slice(&state[..DIGEST_LANES]);
+    digest
+}
+
+// Bits of a lane
+const LANE_BITS: usize = 64;
+
+// `lane.rotate_left(n)`
+fn rotate_left<C: Composer>(
+    composer: &mut C,
+    lane: Witness,
+    n: usize,
+) -> Witness {
+    match n {
+        0 => lane,
+        _ => word::split(composer, lane, LANE_BITS, &[LANE_BITS - n])
+            .rotate_left(composer, n),
+    }
+}
+
+fn permutation_gadget<C: Composer>(
+    composer: &mut C,
+    state: &mut [Witness; LANES],
+) {
+    ROUND_CONSTANTS.iter().for_each(|rc| {
+        // theta
+        let mut c = [C::ZERO; 5];
+        c.iter_mut().enumerate().for_each(|(x, c)| {
+            *c = (1..5).fold(state[x], |c, y| {
+                word::xor(composer, c, state[x + 5 * y], LANE_BITS)
+            });
+        });
+
+        let mut d = [C::ZERO; 5];
+        d.iter_mut().enumerate().for_each(|(x, d)| {
+            let right = rotate_left(composer, c[(x + 1) % 5], 1);
+            *d = word::xor(composer, c[(x + 4) % 5], right, LANE_BITS);
+        });
+
+        state.iter_mut().enumerate().for_each(|(i, lane)| {
+            *lane = word::xor(composer, *lane, d[i % 5], LANE_BITS);
+        });
+
+        // rho and pi
+        let mut b = [C::ZERO; LANES];
+        state.iter().enumerate().for_each(|(i, lane)| {
+            b[pi(i % 5, i / 5)] = rotate_left(composer, *lane, ROTATIONS[i]);
+        });
+
+        // chi
+        state.iter_mut().enumerate().for_each(|(i, lane)| {
+            let (x, y) = (i % 5, i / 5);
+            let b1 = b[(x + 1) % 5 + 5 * y];
+            let b2 = b[(x + 2) % 5 + 5 * y];
+
+            let not_b1 = word::not(composer, b1, LANE_BITS);
+            let and_not = word::and(composer, not_b1, b2, LANE_BITS);
+            *lane = word::xor(composer, b[i], and_not, LANE_BITS);
+        });
+
+        // iota
+        let rc = composer.append_constant(*rc);
+        state[0] = word::xor(composer, state[0], rc, LANE_BITS);
+    });
+}
+
+pub(crate) fn keccak_f1600_gadget<C: Composer>(
+    composer: &mut C,
+    state: &[Witness; LANES],
+) -> [Witness; LANES] {
+    let mut state = *state;
+    permutation_gadget(composer, &mut state);
+    state
+}
+
+pub(crate) fn keccak256_gadget<C: Composer>(
+    composer: &mut C,
+    message: &[Witness],
+) -> [Witness; DIGEST_LANES] {
+    let mut lanes = message.to_vec();
+    let padding = RATE_LANES - lanes.len() % RATE_LANES;
+    (0..padding).for_each(|i| {
+        let mut lane = 0u64;
+        if i == 0 {
+            lane |= 0x01;
+        }
+        if i == padding - 1 {
+            lane |= 0x80 << 56;
+        }
+        let lane = match lane {
+            0 => C::ZERO,
+            _ => composer.append_constant(lane),
+        };
+        lanes.push(lane);
+    });
+
+    let mut state = [C::ZERO; LANES];
+    lanes.chunks(RATE_LANES).for_each(|block| {
+        block.iter().zip(state.iter_mut()).for_each(|(l, s)| {
+            *s = word::xor(composer, *s, *l, LANE_BITS);
+        });
+
+        permutation_gadget(composer, &mut state);
+    });
+
+    let mut digest = [C::ZERO; DIGEST_LANES];
+    digest.copy_from_slice(&state[..DIGEST_LANES]);
+    digest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn digest_test_vectors() {
+        let lanes = |hex: [u8; 32]| {
+            let mut digest = [0u64; DIGEST_LANES];
+            digest.iter_mut().zip(hex.chunks(8)).for_each(|(d, b)| {
+                let mut lane = [0u8; 8];
+                lane.copy_from_slice(b);
+                *d = u64::from_le_bytes(lane);
+            });
+            digest
+        };
+
+        assert_eq!(
+            digest(b""),
+            lanes([
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e,
+                0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53,
+                0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+                0xa4, 0x70,
+            ])
+        );
+        assert_eq!(
+            digest(b"abc"),
+            lanes([
+                0x4e, 0x03, 0x65, 0x7a, 0xea, 0x45, 0xa9, 0x4f, 0xc7, 0xd4,
+                0x7b, 0xa8, 0x26, 0xc8, 0xd6, 0x67, 0xc0, 0xd1, 0xe6, 0xe3,
+                0x3a, 0x64, 0xa0, 0x36, 0xec, 0x44, 0xf5, 0x8f, 0xa1, 0x2d,
+                0x6c, 0x45,
+            ])
+        );
+    }
+
+    #[test]
+    fn pad_fills_whole_blocks() {
+        assert_eq!(pad(b"").len(), 1);
+        assert_eq!(pad(&[0u8; 135]).len(), 1);
+        assert_eq!(pad(&[0u8; 136]).len(), 2);
+
+        // a single byte of padding holds both `1` bits
+        let blocks = pad(&[0u8; 135]);
+        assert_eq!(blocks[0][RATE_LANES - 1], 0x81 << 56);
+    }
+}
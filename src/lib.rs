@@ -63,12 +63,20 @@ if #[cfg(feature = "alloc")] {
     extern crate alloc;
 
     mod bit_iterator;
+    mod lookup;
+    mod msm;
     mod permutation;
     mod util;
-    mod transcript;
+    mod word;
 
     pub mod constraint_system;
     pub mod composer;
+    pub mod hades;
+    pub mod keccak;
+    pub mod merkle;
+    pub mod sha256;
+    pub mod signature;
+    pub mod transcript;
     pub mod runtime;
 });
 
@@ -0,0 +1,263 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Plookup tables and the helpers to build the sorted and grand product
+//! vectors of the lookup argument.
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use hashbrown::HashMap;
+
+use crate::util::batch_inversion;
+
+/// Set of lookup tables registered into a circuit.
+///
+/// Every table is identified by its id, which is appended as the fourth column
+/// of each row. This way, all the tables of a circuit are merged into a single
+/// table with unique rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct LookupTable {
+    tables: Vec<(BlsScalar, Vec<[BlsScalar; 3]>)>,
+}
+
+impl LookupTable {
+    /// Creates an empty set of tables.
+    pub(crate) const fn new() -> Self {
+        Self { tables: Vec::new() }
+    }
+
+    /// Appends `rows` under `id`.
+    ///
+    /// The call is ignored if a table with the same id was already appended.
+    pub(crate) fn append(&mut self, id: BlsScalar, rows: &[[BlsScalar; 3]]) {
+        if self.tables.iter().any(|(t, _)| t == &id) {
+            return;
+        }
+
+        self.tables.push((id, rows.to_vec()));
+    }
+
+    /// Iterates the registered tables in insertion order.
+    pub(crate) fn tables(
+        &self,
+    ) -> impl Iterator<Item = &(BlsScalar, Vec<[BlsScalar; 3]>)> {
+        self.tables.iter()
+    }
+
+    /// Returns `true` if `row` is a row of the table `id`.
+    pub(crate) fn contains(
+        &self,
+        id: &BlsScalar,
+        row: &[BlsScalar; 3],
+    ) -> bool {
+        self.tables
+            .iter()
+            .filter(|(t, _)| t == id)
+            .any(|(_, rows)| rows.contains(row))
+    }
+
+    /// Total number of rows of the merged table.
+    pub(crate) fn len(&self) -> usize {
+        self.tables.iter().map(|(_, rows)| rows.len()).sum()
+    }
+
+    /// Returns `true` if no table was registered.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Returns the merged table as four columns `(a, b, c, id)` padded to
+    /// `n` rows.
+    ///
+    /// The padding repeats the last row. If no table was registered, the
+    /// columns are filled with zeroes.
+    pub(crate) fn columns(&self, n: usize) -> [Vec<BlsScalar>; 4] {
+        let mut columns = [
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+            Vec::with_capacity(n),
+        ];
+
+        self.tables.iter().for_each(|(id, rows)| {
+            rows.iter().for_each(|row| {
+                columns[0].push(row[0]);
+                columns[1].push(row[1]);
+                columns[2].push(row[2]);
+                columns[3].push(*id);
+            })
+        });
+
+        columns.iter_mut().for_each(|column| {
+            let last = column.last().copied().unwrap_or_default();
+            column.resize(n, last);
+        });
+
+        columns
+    }
+}
+
+/// Compresses a row of the lookup table into a single scalar as
+/// `a + zeta · b + zeta^2 · c + zeta^3 · d`
+pub(crate) fn compress(
+    a: &BlsScalar,
+    b: &BlsScalar,
+    c: &BlsScalar,
+    d: &BlsScalar,
+    zeta: &BlsScalar,
+) -> BlsScalar {
+    a + zeta * (b + zeta * (c + zeta * d))
+}
+
+/// Sorts the concatenation of the queries `f` and the table `t` by the order
+/// of `t`, and splits the result into the alternating halves `(h_1, h_2)`.
+///
+/// A query that isn't part of the table is appended at the end of the sorted
+/// vector, which will cause the grand product to not be satisfied.
+pub(crate) fn compute_sorted_vecs(
+    f: &[BlsScalar],
+    t: &[BlsScalar],
+) -> (Vec<BlsScalar>, Vec<BlsScalar>) {
+    let mut queries: HashMap<BlsScalar, usize> = HashMap::new();
+    f.iter().for_each(|q| *queries.entry(*q).or_insert(0) += 1);
+
+    let mut s = Vec::with_capacity(f.len() + t.len());
+
+    t.iter().for_each(|t_i| {
+        s.push(*t_i);
+
+        if let Some(count) = queries.remove(t_i) {
+            s.extend((0..count).map(|_| *t_i));
+        }
+    });
+
+    queries
+        .into_iter()
+        .for_each(|(q, count)| s.extend((0..count).map(|_| q)));
+
+    let h_1 = s.iter().step_by(2).copied().collect();
+    let h_2 = s.iter().skip(1).step_by(2).copied().collect();
+
+    (h_1, h_2)
+}
+
+/// Computes the evaluations of the lookup grand product polynomial over the
+/// domain.
+///
+/// The accumulator is defined as `p_0 = 1` and
+///
+/// `p_{i+1} = p_i · (1 + δ)(ε + f_i)(ε(1 + δ) + t_i + δ t_{i+1}) /
+///     ((ε(1 + δ) + h1_i + δ h2_i)(ε(1 + δ) + h2_i + δ h1_{i+1}))`
+///
+/// where the indexes wrap around the domain.
+pub(crate) fn compute_permutation_vec(
+    f: &[BlsScalar],
+    t: &[BlsScalar],
+    h_1: &[BlsScalar],
+    h_2: &[BlsScalar],
+    delta: &BlsScalar,
+    epsilon: &BlsScalar,
+) -> Vec<BlsScalar> {
+    let n = f.len();
+
+    let one_plus_delta = BlsScalar::one() + delta;
+    let epsilon_one_plus_delta = epsilon * one_plus_delta;
+
+    let mut denominators: Vec<_> = (0..n)
+        .map(|i| {
+            let h_1_next = h_1[(i + 1) % n];
+
+            (epsilon_one_plus_delta + h_1[i] + delta * h_2[i])
+                * (epsilon_one_plus_delta + h_2[i] + delta * h_1_next)
+        })
+        .collect();
+    batch_inversion(&mut denominators);
+
+    let mut p = Vec::with_capacity(n);
+
+    // First element is one
+    let mut state = BlsScalar::one();
+    p.push(state);
+
+    // The last product closes the cycle and is not part of the vector
+    for i in 0..n - 1 {
+        let t_next = t[(i + 1) % n];
+
+        state *= one_plus_delta
+            * (epsilon + f[i])
+            * (epsilon_one_plus_delta + t[i] + delta * t_next)
+            * denominators[i];
+
+        p.push(state);
+    }
+
+    p
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn grand_product_closes_for_valid_queries() {
+        let n = 8;
+
+        let mut table = LookupTable::new();
+        let rows: Vec<_> = (0..5u64)
+            .map(|i| {
+                [
+                    BlsScalar::from(i),
+                    BlsScalar::from(i * i),
+                    -BlsScalar::one(),
+                ]
+            })
+            .collect();
+        table.append(BlsScalar::from(3), &rows);
+
+        let zeta = BlsScalar::random(&mut OsRng);
+        let delta = BlsScalar::random(&mut OsRng);
+        let epsilon = BlsScalar::random(&mut OsRng);
+
+        let [c1, c2, c3, c4] = table.columns(n);
+        let t: Vec<_> = (0..n)
+            .map(|i| compress(&c1[i], &c2[i], &c3[i], &c4[i], &zeta))
+            .collect();
+        let f: Vec<_> =
+            [4, 1, 1, 0, 3, 4, 2, 0].iter().map(|&i| t[i]).collect();
+
+        let (h_1, h_2) = compute_sorted_vecs(&f, &t);
+        let p = compute_permutation_vec(&f, &t, &h_1, &h_2, &delta, &epsilon);
+
+        // closing the cycle must result in one
+        let one_plus_delta = BlsScalar::one() + delta;
+        let epsilon_one_plus_delta = epsilon * one_plus_delta;
+        let last = n - 1;
+        let num = one_plus_delta
+            * (epsilon + f[last])
+            * (epsilon_one_plus_delta + t[last] + delta * t[0]);
+        let den = (epsilon_one_plus_delta + h_1[last] + delta * h_2[last])
+            * (epsilon_one_plus_delta + h_2[last] + delta * h_1[0]);
+
+        assert_eq!(p[last] * num, den);
+
+        // a query outside of the table must break the cycle
+        let mut f = f;
+        f[2] = BlsScalar::random(&mut OsRng);
+
+        let (h_1, h_2) = compute_sorted_vecs(&f, &t);
+        let p = compute_permutation_vec(&f, &t, &h_1, &h_2, &delta, &epsilon);
+
+        let num = one_plus_delta
+            * (epsilon + f[last])
+            * (epsilon_one_plus_delta + t[last] + delta * t[0]);
+        let den = (epsilon_one_plus_delta + h_1[last] + delta * h_2[last])
+            * (epsilon_one_plus_delta + h_2[last] + delta * h_1[0]);
+
+        assert_ne!(p[last] * num, den);
+    }
+}
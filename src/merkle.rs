@@ -0,0 +1,214 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Merkle tree openings of arity 2 and 4.
+//!
+//! The children of a node are hashed with a single [`hades::permutation`] of
+//! the state `[A, c_0, .., c_{A-1}, 0, ..]`, where the capacity element is
+//! the arity `A` of the tree, and the node is the second element of the
+//! permuted state. An arity of 4 fills the rate of the permutation.
+//!
+//! The native [`Opening`] is the reference of the circuit gadget
+//! [`Composer::component_merkle_opening`].
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+use crate::composer::Composer;
+use crate::constraint_system::Witness;
+use crate::hades;
+
+// Assertion of the supported arities, evaluated at compile time when an item
+// generic over the arity is instantiated
+struct Arity<const A: usize>;
+
+impl<const A: usize> Arity<A> {
+    const ASSERT: () =
+        assert!(A == 2 || A == 4, "the arity must be either 2 or 4");
+
+    // Bits of a position in the children of a node
+    const BITS: usize = A.trailing_zeros() as usize;
+}
+
+/// Hashes the `children` of a node of a tree of arity `A`
+pub fn hash<const A: usize>(children: &[BlsScalar; A]) -> BlsScalar {
+    let () = Arity::<A>::ASSERT;
+
+    let mut state = [BlsScalar::zero(); hades::WIDTH];
+    state[0] = BlsScalar::from(A as u64);
+    state[1..=A].copy_from_slice(children);
+
+    hades::permutation(&mut state);
+
+    state[1]
+}
+
+/// Opening of a leaf of a Merkle tree of arity `A`.
+///
+/// Every level of the branch holds the children of a node of the path from
+/// the leaf to the root, along with the position of the child the path goes
+/// through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening<const A: usize> {
+    branch: Vec<([BlsScalar; A], usize)>,
+}
+
+impl<const A: usize> Opening<A> {
+    /// Creates an opening from the children of the nodes of the path and the
+    /// positions of the path in them, starting from the level of the leaf.
+    ///
+    /// Panics if a position is not smaller than `A`.
+    pub fn new(branch: Vec<([BlsScalar; A], usize)>) -> Self {
+        let () = Arity::<A>::ASSERT;
+        branch
+            .iter()
+            .for_each(|(_, position)| assert!(*position < A));
+
+        Self { branch }
+    }
+
+    /// Children of the nodes of the path and the positions of the path in
+    /// them
+    pub fn branch(&self) -> &[([BlsScalar; A], usize)] {
+        &self.branch
+    }
+
+    /// Computes the root of the tree, or `None` if `leaf` isn't part of the
+    /// opening
+    pub fn root(&self, leaf: &BlsScalar) -> Option<BlsScalar> {
+        self.branch
+            .iter()
+            .try_fold(*leaf, |node, (children, position)| {
+                (children[*position] == node).then(|| hash(children))
+            })
+    }
+
+    /// Returns `true` if the opening proves `leaf` to be part of the tree
+    /// with the given `root`
+    pub fn verify(&self, leaf: &BlsScalar, root: &BlsScalar) -> bool {
+        self.root(leaf).as_ref() == Some(root)
+    }
+}
+
+/// Level of an [`Opening`] appended to a circuit
+///
+/// The level holds the siblings of the node of the path, in the order of the
+/// children, and the bits of the position of the node in the children, from
+/// the least significant.
+#[derive(Debug, Clone)]
+pub struct WitnessLevel<const A: usize> {
+    siblings: Vec<Witness>,
+    bits: Vec<Witness>,
+}
+
+impl<const A: usize> WitnessLevel<A> {
+    /// Creates a level from the `A - 1` `siblings` of the node of the path
+    /// and the `log2(A)` `bits` of its position, least significant first.
+    ///
+    /// Panics if the number of siblings or bits doesn't match the arity.
+    pub fn new(siblings: Vec<Witness>, bits: Vec<Witness>) -> Self {
+        let () = Arity::<A>::ASSERT;
+        assert_eq!(siblings.len(), A - 1, "a level has `A - 1` siblings");
+        assert_eq!(bits.len(), Arity::<A>::BITS, "a level has `log2(A)` bits");
+
+        Self { siblings, bits }
+    }
+
+    /// Appends the levels of `opening` to `composer` as witnesses
+    pub fn append<C: Composer>(
+        composer: &mut C,
+        opening: &Opening<A>,
+    ) -> Vec<Self> {
+        opening
+            .branch
+            .iter()
+            .map(|(children, position)| {
+                let siblings = children
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| i != position)
+                    .map(|(_, child)| composer.append_witness(*child))
+                    .collect();
+                let bits = (0..Arity::<A>::BITS)
+                    .map(|i| {
+                        composer.append_witness((position >> i) as u64 & 1)
+                    })
+                    .collect();
+
+                Self::new(siblings, bits)
+            })
+            .collect()
+    }
+
+    /// Siblings of the node of the path, in the order of the children
+    pub fn siblings(&self) -> &[Witness] {
+        &self.siblings
+    }
+
+    /// Bits of the position of the node in the children, least significant
+    /// first
+    pub fn bits(&self) -> &[Witness] {
+        &self.bits
+    }
+}
+
+pub(crate) fn opening_gadget<C: Composer, const A: usize>(
+    composer: &mut C,
+    leaf: Witness,
+    path: &[WitnessLevel<A>],
+) -> Witness {
+    let () = Arity::<A>::ASSERT;
+
+    let arity = composer.append_constant(A as u64);
+
+    path.iter().fold(leaf, |node, level| {
+        level
+            .bits
+            .iter()
+            .for_each(|bit| composer.component_boolean(*bit));
+
+        // the node is placed among the siblings at the position of the bits
+        let s = &level.siblings;
+        let children = match A {
+            2 => {
+                let b0 = level.bits[0];
+                [
+                    composer.component_select(b0, s[0], node),
+                    composer.component_select(b0, node, s[0]),
+                ]
+                .to_vec()
+            }
+            _ => {
+                let (b0, b1) = (level.bits[0], level.bits[1]);
+
+                // the pair of children holding the node, and the other pair
+                let mate = composer.component_select(b1, s[2], s[0]);
+                let pair = [
+                    composer.component_select(b0, mate, node),
+                    composer.component_select(b0, node, mate),
+                ];
+                let other = [
+                    composer.component_select(b1, s[0], s[1]),
+                    composer.component_select(b1, s[1], s[2]),
+                ];
+
+                [
+                    composer.component_select(b1, other[0], pair[0]),
+                    composer.component_select(b1, other[1], pair[1]),
+                    composer.component_select(b1, pair[0], other[0]),
+                    composer.component_select(b1, pair[1], other[1]),
+                ]
+                .to_vec()
+            }
+        };
+
+        let mut state = [C::ZERO; hades::WIDTH];
+        state[0] = arity;
+        state[1..=A].copy_from_slice(&children);
+
+        composer.component_hades_permutation(&state)[1]
+    })
+}
@@ -0,0 +1,324 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Multiscalar multiplication over G1.
+//!
+//! Commitments are computed with the bucket method of Pippenger over signed
+//! windows: every scalar is recoded once into digits in
+//! `(-2^(c-1), 2^(c-1)]`, so a window needs half the buckets of the unsigned
+//! recoding, the negative digits adding the negated point. The points of the
+//! public parameters are all multiples of the same generator, hence they are
+//! computed from a table of the multiples of every byte of a scalar.
+
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective};
+
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+
+// Number of bits of the scalar field modulus
+const SCALAR_BITS: usize = 255;
+
+// Window size of the multiplications by a fixed base
+const BYTE_WINDOWS: usize = 32;
+const BYTE_MULTIPLES: usize = 255;
+
+/// Window size for `len` terms, following the `ln(len) + 2` estimate of the
+/// bucket method, limited so the signed digits fit an `i16`
+fn window_size(len: usize) -> usize {
+    if len < 32 {
+        3
+    } else {
+        // ln(len) = log2(len) · ln(2)
+        let log2 = (usize::BITS - len.leading_zeros() - 1) as usize;
+        (log2 * 69 / 100 + 2).min(15)
+    }
+}
+
+/// Recode a scalar into `windows` signed digits of `c` bits, least
+/// significant first.
+///
+/// A digit greater than `2^(c-1)` is replaced by its difference with `2^c`,
+/// carrying one to the next window. Since the scalars are smaller than
+/// `2^255`, the last window absorbs the final carry.
+fn signed_digits(scalar: &BlsScalar, c: usize, digits: &mut [i16]) {
+    let bytes = scalar.to_bytes();
+    let mut limbs = [0u64; 4];
+    limbs
+        .iter_mut()
+        .zip(bytes.chunks_exact(8))
+        .for_each(|(limb, b)| {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(b);
+            *limb = u64::from_le_bytes(le);
+        });
+
+    let radix = 1i32 << c;
+    let half = radix >> 1;
+    let mask = (1u64 << c) - 1;
+
+    let mut carry = 0;
+    digits.iter_mut().enumerate().for_each(|(w, digit)| {
+        let offset = w * c;
+        let (limb, shift) = (offset / 64, offset % 64);
+
+        let mut window = limbs.get(limb).map(|l| l >> shift).unwrap_or(0);
+        if shift + c > 64 && limb + 1 < limbs.len() {
+            window |= limbs[limb + 1] << (64 - shift);
+        }
+
+        let value = (window & mask) as i32 + carry;
+        if value > half {
+            *digit = (value - radix) as i16;
+            carry = 1;
+        } else {
+            *digit = value as i16;
+            carry = 0;
+        }
+    });
+}
+
+/// Compute `Σ scalars[i] · points[i]`.
+///
+/// The terms are zipped, so the extra points or scalars are ignored.
+pub(crate) fn msm_variable_base(
+    points: &[G1Affine],
+    scalars: &[BlsScalar],
+) -> G1Projective {
+    let len = points.len().min(scalars.len());
+    if len == 0 {
+        return G1Projective::identity();
+    }
+
+    let (points, scalars) = (&points[..len], &scalars[..len]);
+
+    let c = window_size(len);
+    let windows = SCALAR_BITS / c + 1;
+
+    // the digits of the i-th scalar are at `digits[i * windows..]`
+    let mut digits = vec![0i16; len * windows];
+
+    #[cfg(not(feature = "std"))]
+    let chunks = digits.chunks_mut(windows);
+
+    #[cfg(feature = "std")]
+    let chunks = digits.par_chunks_mut(windows);
+
+    chunks
+        .zip(scalars)
+        .for_each(|(digits, scalar)| signed_digits(scalar, c, digits));
+
+    let window_sum = |w: usize| {
+        let mut buckets = vec![G1Projective::identity(); 1 << (c - 1)];
+
+        points
+            .iter()
+            .zip(digits.iter().skip(w).step_by(windows))
+            .for_each(|(point, digit)| match *digit {
+                0 => (),
+                d if d > 0 => {
+                    let bucket = &mut buckets[d as usize - 1];
+                    *bucket = bucket.add_mixed(point);
+                }
+                d => {
+                    let bucket = &mut buckets[d.unsigned_abs() as usize - 1];
+                    *bucket = bucket.add_mixed(&-point);
+                }
+            });
+
+        // Σ (j + 1) · buckets[j], as a sum of running sums
+        let mut running = G1Projective::identity();
+        let mut sum = G1Projective::identity();
+        buckets.iter().rev().for_each(|bucket| {
+            running += bucket;
+            sum += running;
+        });
+
+        sum
+    };
+
+    #[cfg(not(feature = "std"))]
+    let sums: Vec<_> = (0..windows).map(window_sum).collect();
+
+    #[cfg(feature = "std")]
+    let sums: Vec<_> = (0..windows).into_par_iter().map(window_sum).collect();
+
+    // Horner over the windows, most significant first
+    sums.iter()
+        .rev()
+        .fold(G1Projective::identity(), |acc, sum| {
+            (0..c).fold(acc, |acc, _| acc.double()) + sum
+        })
+}
+
+/// Compute `scalars[i] · base` for every scalar.
+///
+/// The multiples `b · 256^w · base` of every byte `b` of every window `w` are
+/// tabulated, so a multiplication takes at most one addition per byte of the
+/// scalar.
+pub(crate) fn mul_fixed_base(
+    scalars: &[BlsScalar],
+    base: G1Projective,
+) -> Vec<G1Projective> {
+    let mut table = Vec::with_capacity(BYTE_WINDOWS * BYTE_MULTIPLES);
+    let mut window_base = base;
+    (0..BYTE_WINDOWS).for_each(|_| {
+        let mut multiple = G1Projective::identity();
+        (0..BYTE_MULTIPLES).for_each(|_| {
+            multiple += window_base;
+            table.push(multiple);
+        });
+
+        // 256^(w + 1) · base
+        window_base = multiple + window_base;
+    });
+
+    let mut normalized = vec![G1Affine::identity(); table.len()];
+    G1Projective::batch_normalize(&table, &mut normalized);
+
+    let mul = |scalar: &BlsScalar| {
+        scalar
+            .to_bytes()
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b != 0)
+            .fold(G1Projective::identity(), |acc, (w, b)| {
+                acc.add_mixed(&normalized[w * BYTE_MULTIPLES + *b as usize - 1])
+            })
+    };
+
+    #[cfg(not(feature = "std"))]
+    let scalars = scalars.iter();
+
+    #[cfg(feature = "std")]
+    let scalars = scalars.par_iter();
+
+    scalars.map(mul).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn naive(points: &[G1Affine], scalars: &[BlsScalar]) -> G1Projective {
+        points.iter().zip(scalars).map(|(p, s)| p * s).sum()
+    }
+
+    #[test]
+    fn variable_base() {
+        let mut rng = StdRng::seed_from_u64(0xa11);
+
+        [0, 1, 2, 31, 32, 33, 100, 1000]
+            .into_iter()
+            .for_each(|len| {
+                let points: Vec<G1Affine> = (0..len)
+                    .map(|_| {
+                        (G1Affine::generator() * BlsScalar::random(&mut rng))
+                            .into()
+                    })
+                    .collect();
+                let mut scalars: Vec<BlsScalar> =
+                    (0..len).map(|_| BlsScalar::random(&mut rng)).collect();
+
+                // edge digits: zero, one, the largest scalar and repeated
+                // points
+                if len > 4 {
+                    scalars[0] = BlsScalar::zero();
+                    scalars[1] = BlsScalar::one();
+                    scalars[2] = -BlsScalar::one();
+                    scalars[3] = BlsScalar::from(u64::MAX);
+                }
+
+                assert_eq!(
+                    msm_variable_base(&points, &scalars),
+                    naive(&points, &scalars)
+                );
+
+                let doubled: Vec<G1Affine> =
+                    points.iter().chain(&points).copied().collect();
+                let repeated: Vec<BlsScalar> =
+                    scalars.iter().chain(&scalars).copied().collect();
+                assert_eq!(
+                    msm_variable_base(&doubled, &repeated),
+                    naive(&points, &scalars).double()
+                );
+            });
+    }
+
+    #[test]
+    fn variable_base_zip() {
+        let points = [G1Affine::generator(); 3];
+        let scalars = [BlsScalar::from(2), BlsScalar::from(5)];
+
+        assert_eq!(
+            msm_variable_base(&points, &scalars),
+            G1Affine::generator() * BlsScalar::from(7)
+        );
+        assert_eq!(
+            msm_variable_base(&points[..1], &scalars),
+            G1Affine::generator() * BlsScalar::from(2)
+        );
+        assert_eq!(msm_variable_base(&[], &scalars), G1Projective::identity());
+    }
+
+    #[test]
+    fn digits() {
+        let scalars = [
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::from(0x8421u64),
+            BlsScalar::from_raw([u64::MAX, u64::MAX, 0x1234, 0]),
+        ];
+
+        (1..=15).for_each(|c| {
+            let windows = SCALAR_BITS / c + 1;
+            let mut digits = vec![0i16; windows];
+            let radix = BlsScalar::from(1u64 << c);
+
+            scalars.iter().for_each(|scalar| {
+                signed_digits(scalar, c, &mut digits);
+
+                let recoded = digits.iter().rev().fold(
+                    BlsScalar::zero(),
+                    |acc, digit| {
+                        let d = BlsScalar::from(digit.unsigned_abs() as u64);
+                        let d = if *digit < 0 { -d } else { d };
+                        acc * radix + d
+                    },
+                );
+                assert_eq!(&recoded, scalar);
+
+                let half = 1i16 << (c - 1);
+                assert!(digits.iter().all(|d| -half < *d && *d <= half));
+            });
+        });
+    }
+
+    #[test]
+    fn fixed_base() {
+        let mut rng = StdRng::seed_from_u64(0xa12);
+        let base = G1Affine::generator() * BlsScalar::random(&mut rng);
+
+        let scalars: Vec<BlsScalar> = [
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::from(0xff00ff),
+        ]
+        .into_iter()
+        .chain((0..20).map(|_| BlsScalar::random(&mut rng)))
+        .collect();
+
+        mul_fixed_base(&scalars, base)
+            .iter()
+            .zip(&scalars)
+            .for_each(|(p, s)| assert_eq!(p, &(base * s)));
+    }
+}
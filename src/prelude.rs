@@ -11,8 +11,11 @@
 
 #[cfg(feature = "alloc")]
 pub use crate::{
-    commitment_scheme::PublicParameters,
-    composer::{Builder, Circuit, Compiler, Composer, Prover, Verifier},
+    commitment_scheme::{Accumulator, PublicParameters},
+    composer::{
+        verify_batch, Builder, Circuit, Compiler, Composer, CustomGate,
+        CustomWidget, GateWires, MockProver, Prover, Verifier,
+    },
     constraint_system::{Constraint, Witness, WitnessPoint},
 };
 
@@ -6,8 +6,9 @@
 
 #[cfg(feature = "alloc")]
 use crate::{
+    composer::GateWires,
     fft::{EvaluationDomain, Polynomial},
-    proof_system::ProverKey,
+    proof_system::{widget, ProverKey},
 };
 
 use dusk_bls12_381::BlsScalar;
@@ -25,6 +26,8 @@ use rkyv::{
 #[allow(dead_code)]
 pub(crate) struct Evaluations {
     pub(crate) proof: ProofEvaluations,
+    // Evaluations of the lookup argument, for circuits with lookups
+    pub(crate) lookup: Option<LookupEvaluations>,
     // Evaluation of the linearization sigma polynomial at `z`
     pub(crate) t_eval: BlsScalar,
 }
@@ -94,6 +97,42 @@ pub(crate) struct ProofEvaluations {
     pub(crate) perm_eval: BlsScalar,
 }
 
+/// Evaluations of the lookup argument, only added to the
+/// [`Proof`](super::Proof) of the circuits with lookups.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(Archive, Deserialize, Serialize),
+    archive(bound(serialize = "__S: Serializer + ScratchSpace")),
+    archive_attr(derive(CheckBytes))
+)]
+pub(crate) struct LookupEvaluations {
+    // Evaluation of the lookup query polynomial at `z`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) f_eval: BlsScalar,
+    // Evaluation of the first half of the sorted lookup polynomial at `z`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) h_1_eval: BlsScalar,
+    // (Shifted) Evaluation of the first half of the sorted lookup polynomial
+    // at `z * root of unity`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) h_1_next_eval: BlsScalar,
+    // Evaluation of the second half of the sorted lookup polynomial at `z`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) h_2_eval: BlsScalar,
+    // Evaluation of the compressed lookup table polynomial at `z`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_eval: BlsScalar,
+    // (Shifted) Evaluation of the compressed lookup table polynomial at
+    // `z * root of unity`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_next_eval: BlsScalar,
+    // (Shifted) Evaluation of the lookup permutation polynomial at `z * root
+    // of unity`
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) lookup_perm_eval: BlsScalar,
+}
+
 // The struct ProofEvaluations has 16 BlsScalars
 impl Serializable<{ 16 * BlsScalar::SIZE }> for ProofEvaluations {
     type Error = dusk_bytes::Error;
@@ -166,6 +205,51 @@ impl Serializable<{ 16 * BlsScalar::SIZE }> for ProofEvaluations {
     }
 }
 
+// The struct LookupEvaluations has 7 BlsScalars
+impl Serializable<{ 7 * BlsScalar::SIZE }> for LookupEvaluations {
+    type Error = dusk_bytes::Error;
+
+    #[allow(unused_must_use)]
+    fn to_bytes(&self) -> [u8; Self::SIZE] {
+        use dusk_bytes::Write;
+
+        let mut buf = [0u8; Self::SIZE];
+        let mut writer = &mut buf[..];
+        writer.write(&self.f_eval.to_bytes());
+        writer.write(&self.h_1_eval.to_bytes());
+        writer.write(&self.h_1_next_eval.to_bytes());
+        writer.write(&self.h_2_eval.to_bytes());
+        writer.write(&self.table_eval.to_bytes());
+        writer.write(&self.table_next_eval.to_bytes());
+        writer.write(&self.lookup_perm_eval.to_bytes());
+
+        buf
+    }
+
+    fn from_bytes(
+        buf: &[u8; Self::SIZE],
+    ) -> Result<LookupEvaluations, Self::Error> {
+        let mut buffer = &buf[..];
+        let f_eval = BlsScalar::from_reader(&mut buffer)?;
+        let h_1_eval = BlsScalar::from_reader(&mut buffer)?;
+        let h_1_next_eval = BlsScalar::from_reader(&mut buffer)?;
+        let h_2_eval = BlsScalar::from_reader(&mut buffer)?;
+        let table_eval = BlsScalar::from_reader(&mut buffer)?;
+        let table_next_eval = BlsScalar::from_reader(&mut buffer)?;
+        let lookup_perm_eval = BlsScalar::from_reader(&mut buffer)?;
+
+        Ok(LookupEvaluations {
+            f_eval,
+            h_1_eval,
+            h_1_next_eval,
+            h_2_eval,
+            table_eval,
+            table_next_eval,
+            lookup_perm_eval,
+        })
+    }
+}
+
 #[cfg(feature = "alloc")]
 
 /// Compute the linearization polynomial.
@@ -174,6 +258,7 @@ impl Serializable<{ 16 * BlsScalar::SIZE }> for ProofEvaluations {
 pub(crate) fn compute(
     domain: &EvaluationDomain,
     prover_key: &ProverKey,
+    (custom_key, custom_challenges): (&widget::custom::ProverKey, &[BlsScalar]),
     (
         alpha,
         beta,
@@ -199,6 +284,16 @@ pub(crate) fn compute(
     d_w_poly: &Polynomial,
     t_x_poly: &Polynomial,
     z_poly: &Polynomial,
+    lookup: Option<(
+        (
+            &Polynomial,
+            &Polynomial,
+            &Polynomial,
+            &Polynomial,
+            &Polynomial,
+        ),
+        &(BlsScalar, BlsScalar, BlsScalar, BlsScalar),
+    )>,
 ) -> (Polynomial, Evaluations) {
     // Compute evaluations
     let t_eval = t_x_poly.evaluate(z_challenge);
@@ -254,7 +349,63 @@ pub(crate) fn compute(
         z_poly,
     );
 
-    let r_poly = &f_1 + &f_2;
+    let f_4 = custom_key.compute_linearization(
+        custom_challenges,
+        &GateWires {
+            a: a_eval,
+            b: b_eval,
+            c: c_eval,
+            d: d_eval,
+            a_next: a_next_eval,
+            b_next: b_next_eval,
+            d_next: d_next_eval,
+        },
+    );
+
+    let mut r_poly = &(&f_1 + &f_2) + &f_4;
+
+    // The lookup argument is only proved for circuits with lookups
+    let lookup = match prover_key.lookup.as_ref().zip(lookup) {
+        Some((
+            lookup_key,
+            (
+                (f_poly, h_1_poly, h_2_poly, table_poly, p_poly),
+                (lookup_separation_challenge, delta, epsilon, zeta),
+            ),
+        )) => {
+            let shifted_z_challenge = z_challenge * domain.group_gen;
+
+            let evaluations = LookupEvaluations {
+                f_eval: f_poly.evaluate(z_challenge),
+                h_1_eval: h_1_poly.evaluate(z_challenge),
+                h_1_next_eval: h_1_poly.evaluate(&shifted_z_challenge),
+                h_2_eval: h_2_poly.evaluate(z_challenge),
+                table_eval: table_poly.evaluate(z_challenge),
+                table_next_eval: table_poly.evaluate(&shifted_z_challenge),
+                lookup_perm_eval: p_poly.evaluate(&shifted_z_challenge),
+            };
+
+            let l1_eval =
+                domain.evaluate_all_lagrange_coefficients(*z_challenge)[0];
+
+            let f_3 = lookup_key.compute_linearization(
+                lookup_separation_challenge,
+                (&a_eval, &b_eval, &c_eval, &q_c_eval),
+                (
+                    &evaluations.f_eval,
+                    &evaluations.table_eval,
+                    &evaluations.table_next_eval,
+                ),
+                &l1_eval,
+                (delta, epsilon, zeta),
+                p_poly,
+            );
+            r_poly += &f_3;
+
+            Some(evaluations)
+        }
+        None => None,
+    };
 
     // Evaluate linearization polynomial at challenge `z`
     let r_poly_eval = r_poly.evaluate(z_challenge);
@@ -280,6 +431,7 @@ pub(crate) fn compute(
                 r_poly_eval,
                 perm_eval,
             },
+            lookup,
             t_eval,
         },
     )
@@ -378,6 +530,12 @@ mod evaluations_tests {
         let bytes = proof_evals.to_bytes();
         let obtained_evals = ProofEvaluations::from_slice(&bytes)
             .expect("Deserialization error");
-        assert_eq!(proof_evals.to_bytes(), obtained_evals.to_bytes())
+        assert_eq!(proof_evals.to_bytes(), obtained_evals.to_bytes());
+
+        let lookup_evals = LookupEvaluations::default();
+        let bytes = lookup_evals.to_bytes();
+        let obtained_evals = LookupEvaluations::from_slice(&bytes)
+            .expect("Deserialization error");
+        assert_eq!(lookup_evals, obtained_evals)
     }
 }
@@ -7,8 +7,9 @@
 //! A Proof stores the commitments to all of the elements that
 //! are needed to univocally identify a prove of some statement.
 
-use super::linearization_poly::ProofEvaluations;
+use super::linearization_poly::{LookupEvaluations, ProofEvaluations};
 use crate::commitment_scheme::Commitment;
+use crate::error::Error;
 
 use dusk_bytes::{DeserializableSlice, Serializable};
 
@@ -32,6 +33,11 @@ use rkyv::{
 /// [`Verifier`](crate::prelude::Verifier) have in common succintly
 /// and without any capabilities of adquiring any kind of knowledge about the
 /// witness used to construct the Proof.
+///
+/// The proofs of circuits with lookups also carry the commitments and the
+/// evaluations of the lookup argument, so they are serialized with
+/// [`Proof::to_var_bytes`] to either [`Proof::SIZE`] or
+/// [`Proof::LOOKUP_SIZE`] bytes.
 #[derive(Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "rkyv-impl",
@@ -78,6 +84,35 @@ pub struct Proof {
     /// Subset of all of the evaluations added to the proof.
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) evaluations: ProofEvaluations,
+
+    /// Lookup argument, for circuits with lookups.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) lookup: Option<LookupProof>,
+}
+
+/// Commitments and evaluations of the lookup argument of a [`Proof`].
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(Archive, Deserialize, Serialize),
+    archive(bound(serialize = "__S: Serializer + ScratchSpace"))
+)]
+pub(crate) struct LookupProof {
+    /// Commitment to the lookup query polynomial.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) f_comm: Commitment,
+    /// Commitment to the first half of the sorted lookup polynomial.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) h_1_comm: Commitment,
+    /// Commitment to the second half of the sorted lookup polynomial.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) h_2_comm: Commitment,
+    /// Commitment to the lookup permutation polynomial.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) p_comm: Commitment,
+    /// Evaluations of the lookup argument.
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) evaluations: LookupEvaluations,
 }
 
 #[cfg(feature = "rkyv-impl")]
@@ -104,13 +139,33 @@ impl<C> CheckBytes<C> for ArchivedProof {
         check_field(&(*value).w_z_chall_w_comm, context, "w_z_chall_w_comm")?;
         check_field(&(*value).evaluations, context, "evaluations")?;
 
+        check_field(&(*value).lookup, context, "lookup")?;
+
         Ok(&*value)
     }
 }
 
-// The struct Proof has 11 commitments + 1 ProofEvaluations
-impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
-    for Proof
+#[cfg(feature = "rkyv-impl")]
+impl<C> CheckBytes<C> for ArchivedLookupProof {
+    type Error = StructCheckError;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        check_field(&(*value).f_comm, context, "f_comm")?;
+        check_field(&(*value).h_1_comm, context, "h_1_comm")?;
+        check_field(&(*value).h_2_comm, context, "h_2_comm")?;
+        check_field(&(*value).p_comm, context, "p_comm")?;
+        check_field(&(*value).evaluations, context, "evaluations")?;
+
+        Ok(&*value)
+    }
+}
+
+// The struct LookupProof has 4 commitments + 1 LookupEvaluations
+impl Serializable<{ 4 * Commitment::SIZE + LookupEvaluations::SIZE }>
+    for LookupProof
 {
     type Error = dusk_bytes::Error;
 
@@ -120,17 +175,10 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
 
         let mut buf = [0u8; Self::SIZE];
         let mut writer = &mut buf[..];
-        writer.write(&self.a_comm.to_bytes());
-        writer.write(&self.b_comm.to_bytes());
-        writer.write(&self.c_comm.to_bytes());
-        writer.write(&self.d_comm.to_bytes());
-        writer.write(&self.z_comm.to_bytes());
-        writer.write(&self.t_low_comm.to_bytes());
-        writer.write(&self.t_mid_comm.to_bytes());
-        writer.write(&self.t_high_comm.to_bytes());
-        writer.write(&self.t_4_comm.to_bytes());
-        writer.write(&self.w_z_chall_comm.to_bytes());
-        writer.write(&self.w_z_chall_w_comm.to_bytes());
+        writer.write(&self.f_comm.to_bytes());
+        writer.write(&self.h_1_comm.to_bytes());
+        writer.write(&self.h_2_comm.to_bytes());
+        writer.write(&self.p_comm.to_bytes());
         writer.write(&self.evaluations.to_bytes());
 
         buf
@@ -139,6 +187,54 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
     fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<Self, Self::Error> {
         let mut buffer = &buf[..];
 
+        let f_comm = Commitment::from_reader(&mut buffer)?;
+        let h_1_comm = Commitment::from_reader(&mut buffer)?;
+        let h_2_comm = Commitment::from_reader(&mut buffer)?;
+        let p_comm = Commitment::from_reader(&mut buffer)?;
+        let evaluations = LookupEvaluations::from_reader(&mut buffer)?;
+
+        Ok(LookupProof {
+            f_comm,
+            h_1_comm,
+            h_2_comm,
+            p_comm,
+            evaluations,
+        })
+    }
+}
+
+impl Proof {
+    /// Size in bytes of a serialized proof of a circuit without lookups: 11
+    /// commitments and its evaluations
+    pub const SIZE: usize = 11 * Commitment::SIZE + ProofEvaluations::SIZE;
+
+    /// Size in bytes of a serialized proof of a circuit with lookups, which
+    /// is followed by 4 commitments and the evaluations of the lookup
+    /// argument
+    pub const LOOKUP_SIZE: usize = Self::SIZE + LookupProof::SIZE;
+
+    /// Size in bytes of the proof once serialized, either [`Self::SIZE`] or
+    /// [`Self::LOOKUP_SIZE`]
+    pub const fn serialized_size(&self) -> usize {
+        match self.lookup {
+            Some(_) => Self::LOOKUP_SIZE,
+            None => Self::SIZE,
+        }
+    }
+
+    /// Deserializes a proof from the bytes generated by
+    /// [`Self::to_var_bytes`]
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != Self::SIZE && bytes.len() != Self::LOOKUP_SIZE {
+            return Err(dusk_bytes::Error::BadLength {
+                found: bytes.len(),
+                expected: Self::SIZE,
+            }
+            .into());
+        }
+
+        let mut buffer = bytes;
+
         let a_comm = Commitment::from_reader(&mut buffer)?;
         let b_comm = Commitment::from_reader(&mut buffer)?;
         let c_comm = Commitment::from_reader(&mut buffer)?;
@@ -152,6 +248,11 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
         let w_z_chall_w_comm = Commitment::from_reader(&mut buffer)?;
         let evaluations = ProofEvaluations::from_reader(&mut buffer)?;
 
+        let lookup = match buffer.is_empty() {
+            true => None,
+            false => Some(LookupProof::from_reader(&mut buffer)?),
+        };
+
         Ok(Proof {
             a_comm,
             b_comm,
@@ -165,6 +266,7 @@ impl Serializable<{ 11 * Commitment::SIZE + ProofEvaluations::SIZE }>
             w_z_chall_comm,
             w_z_chall_w_comm,
             evaluations,
+            lookup,
         })
     }
 }
@@ -176,30 +278,62 @@ pub(crate) mod alloc {
         commitment_scheme::{AggregateProof, OpeningKey},
         error::Error,
         fft::EvaluationDomain,
-        proof_system::widget::VerifierKey,
-        transcript::TranscriptProtocol,
+        msm::msm_variable_base,
+        proof_system::widget::{custom, lookup, VerifierKey},
+        transcript::{TranscriptExt, TranscriptProtocol},
         util::batch_inversion,
     };
     #[rustfmt::skip]
     use ::alloc::vec::Vec;
-    use dusk_bls12_381::{
-        multiscalar_mul::msm_variable_base, BlsScalar, G1Affine,
-    };
-    use merlin::Transcript;
+    use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective};
     #[cfg(feature = "std")]
     use rayon::prelude::*;
 
     impl Proof {
-        /// Performs the verification of a [`Proof`] returning a boolean result.
-        pub(crate) fn verify(
+        /// Serializes the proof into [`Self::serialized_size`] bytes
+        pub fn to_var_bytes(&self) -> Vec<u8> {
+            let mut bytes = Vec::with_capacity(self.serialized_size());
+
+            bytes.extend(self.a_comm.to_bytes());
+            bytes.extend(self.b_comm.to_bytes());
+            bytes.extend(self.c_comm.to_bytes());
+            bytes.extend(self.d_comm.to_bytes());
+            bytes.extend(self.z_comm.to_bytes());
+            bytes.extend(self.t_low_comm.to_bytes());
+            bytes.extend(self.t_mid_comm.to_bytes());
+            bytes.extend(self.t_high_comm.to_bytes());
+            bytes.extend(self.t_4_comm.to_bytes());
+            bytes.extend(self.w_z_chall_comm.to_bytes());
+            bytes.extend(self.w_z_chall_w_comm.to_bytes());
+            bytes.extend(self.evaluations.to_bytes());
+
+            if let Some(lookup) = &self.lookup {
+                bytes.extend(lookup.to_bytes());
+            }
+
+            bytes
+        }
+
+        /// Performs every check of the verification of a [`Proof`] but the
+        /// final pairing, returning the pair of points `(W, C)` to be checked
+        /// with [`OpeningKey::pairing_check`].
+        pub(crate) fn accumulate<T: TranscriptProtocol>(
             &self,
             verifier_key: &VerifierKey,
-            transcript: &mut Transcript,
+            custom_verifier_key: &custom::VerifierKey,
+            transcript: &mut T,
             opening_key: &OpeningKey,
             pub_inputs: &[BlsScalar],
-        ) -> Result<(), Error> {
+        ) -> Result<(G1Projective, G1Projective), Error> {
             let domain = EvaluationDomain::new(verifier_key.n)?;
 
+            // The proof must carry a lookup argument if and only if the
+            // circuit has lookups
+            if verifier_key.lookup.is_some() != self.lookup.is_some() {
+                return Err(Error::ProofVerificationError);
+            }
+            let lookup = verifier_key.lookup.as_ref().zip(self.lookup.as_ref());
+
             // Subgroup checks are done when the proof is deserialized.
 
             // In order for the Verifier and Prover to have the same view in the
@@ -215,13 +349,38 @@ pub(crate) mod alloc {
             transcript.append_commitment(b"c_w", &self.c_comm);
             transcript.append_commitment(b"d_w", &self.d_comm);
 
+            // Compute lookup compression challenge and add commitment to
+            // lookup query and sorted polynomials to transcript
+            let zeta = lookup.map(|(_, lookup_proof)| {
+                let zeta = transcript.challenge_scalar(b"zeta");
+                transcript.append_scalar(b"zeta", &zeta);
+
+                transcript.append_commitment(b"f", &lookup_proof.f_comm);
+                transcript.append_commitment(b"h_1", &lookup_proof.h_1_comm);
+                transcript.append_commitment(b"h_2", &lookup_proof.h_2_comm);
+
+                zeta
+            });
+
             // Compute beta and gamma challenges
             let beta = transcript.challenge_scalar(b"beta");
             transcript.append_scalar(b"beta", &beta);
             let gamma = transcript.challenge_scalar(b"gamma");
 
-            // Add commitment to permutation polynomial to transcript
+            // Compute delta and epsilon challenges
+            let delta_epsilon = lookup.map(|_| {
+                let delta = transcript.challenge_scalar(b"delta");
+                transcript.append_scalar(b"delta", &delta);
+                let epsilon = transcript.challenge_scalar(b"epsilon");
+
+                (delta, epsilon)
+            });
+
+            // Add commitment to permutation polynomials to transcript
             transcript.append_commitment(b"z", &self.z_comm);
+            if let Some((_, lookup_proof)) = lookup {
+                transcript.append_commitment(b"p", &lookup_proof.p_comm);
+            }
 
             // Compute quotient challenge
             let alpha = transcript.challenge_scalar(b"alpha");
@@ -233,6 +392,11 @@ pub(crate) mod alloc {
                 transcript.challenge_scalar(b"fixed base separation challenge");
             let var_base_sep_challenge = transcript
                 .challenge_scalar(b"variable base separation challenge");
+            let lookup_sep_challenge = lookup.map(|_| {
+                transcript.challenge_scalar(b"lookup separation challenge")
+            });
+            let custom_sep_challenges =
+                custom_verifier_key.separation_challenges(transcript);
 
             // Add commitment to quotient polynomial to transcript
             transcript.append_commitment(b"t_low", &self.t_low_comm);
@@ -253,6 +417,20 @@ pub(crate) mod alloc {
                 &z_challenge,
             );
 
+            // Gather the lookup challenges, for circuits with lookups
+            let lookup = lookup
+                .zip(zeta)
+                .zip(lookup_sep_challenge)
+                .zip(delta_epsilon)
+                .map(
+                    |(
+                        ((keys, zeta), lookup_sep_challenge),
+                        (delta, epsilon),
+                    )| {
+                        (keys, (lookup_sep_challenge, delta, epsilon, zeta))
+                    },
+                );
+
             // Compute quotient polynomial evaluated at challenge `z`
             let t_eval = self.compute_quotient_evaluation(
                 &domain,
@@ -264,8 +442,10 @@ pub(crate) mod alloc {
                 &z_h_eval,
                 &l1_eval,
                 &self.evaluations.perm_eval,
+                lookup.as_ref().map(|((_, lookup_proof), challenges)| {
+                    (&lookup_proof.evaluations, challenges)
+                }),
             );
-
             // Compute commitment to quotient polynomial
             // This method is necessary as we pass the `un-splitted` variation
             // to our commitment scheme
@@ -301,6 +481,26 @@ pub(crate) mod alloc {
             transcript.append_scalar(b"q_l_eval", &self.evaluations.q_l_eval);
             transcript.append_scalar(b"q_r_eval", &self.evaluations.q_r_eval);
             transcript.append_scalar(b"perm_eval", &self.evaluations.perm_eval);
+            if let Some(((_, lookup_proof), _)) = &lookup {
+                let evaluations = &lookup_proof.evaluations;
+                transcript.append_scalar(b"f_eval", &evaluations.f_eval);
+                transcript.append_scalar(b"h_1_eval", &evaluations.h_1_eval);
+                transcript.append_scalar(
+                    b"h_1_next_eval",
+                    &evaluations.h_1_next_eval,
+                );
+                transcript.append_scalar(b"h_2_eval", &evaluations.h_2_eval);
+                transcript
+                    .append_scalar(b"table_eval", &evaluations.table_eval);
+                transcript.append_scalar(
+                    b"table_next_eval",
+                    &evaluations.table_next_eval,
+                );
+                transcript.append_scalar(
+                    b"lookup_perm_eval",
+                    &evaluations.lookup_perm_eval,
+                );
+            }
             transcript.append_scalar(b"t_eval", &t_eval);
             transcript.append_scalar(b"r_eval", &self.evaluations.r_poly_eval);
 
@@ -315,9 +515,11 @@ pub(crate) mod alloc {
                     &fixed_base_sep_challenge,
                     &var_base_sep_challenge,
                 ),
+                lookup.as_ref(),
                 &z_challenge,
                 l1_eval,
                 verifier_key,
+                (custom_verifier_key, &custom_sep_challenges),
             );
 
             // Commitment Scheme
@@ -350,6 +552,33 @@ pub(crate) mod alloc {
                 self.evaluations.s_sigma_3_eval,
                 verifier_key.permutation.s_sigma_3,
             ));
+            aggregate_proof.add_part((
+                self.evaluations.q_c_eval,
+                verifier_key.arithmetic.q_c,
+            ));
+
+            // Compute commitment to the compressed lookup table
+            let table_comm = lookup.as_ref().map(
+                |((lookup_key, lookup_proof), (_, _, _, zeta))| {
+                    let evaluations = &lookup_proof.evaluations;
+                    let table_comm = lookup_key.compute_table_commitment(zeta);
+
+                    aggregate_proof
+                        .add_part((evaluations.f_eval, lookup_proof.f_comm));
+                    aggregate_proof.add_part((
+                        evaluations.h_1_eval,
+                        lookup_proof.h_1_comm,
+                    ));
+                    aggregate_proof.add_part((
+                        evaluations.h_2_eval,
+                        lookup_proof.h_2_comm,
+                    ));
+                    aggregate_proof
+                        .add_part((evaluations.table_eval, table_comm));
+
+                    table_comm
+                },
+            );
             // Flatten proof with opening challenge
             let flattened_proof_a = aggregate_proof.flatten(transcript);
 
@@ -364,27 +593,35 @@ pub(crate) mod alloc {
                 .add_part((self.evaluations.b_next_eval, self.b_comm));
             shifted_aggregate_proof
                 .add_part((self.evaluations.d_next_eval, self.d_comm));
+            if let Some((((_, lookup_proof), _), table_comm)) =
+                lookup.as_ref().zip(table_comm)
+            {
+                let evaluations = &lookup_proof.evaluations;
+                shifted_aggregate_proof.add_part((
+                    evaluations.lookup_perm_eval,
+                    lookup_proof.p_comm,
+                ));
+                shifted_aggregate_proof.add_part((
+                    evaluations.h_1_next_eval,
+                    lookup_proof.h_1_comm,
+                ));
+                shifted_aggregate_proof
+                    .add_part((evaluations.table_next_eval, table_comm));
+            }
 
             let flattened_proof_b = shifted_aggregate_proof.flatten(transcript);
             // Add commitment to openings to transcript
             transcript.append_commitment(b"w_z", &self.w_z_chall_comm);
             transcript.append_commitment(b"w_z_w", &self.w_z_chall_w_comm);
-            // Batch check
-            if opening_key
-                .batch_check(
-                    &[z_challenge, (z_challenge * domain.group_gen)],
-                    &[flattened_proof_a, flattened_proof_b],
-                    transcript,
-                )
-                .is_err()
-            {
-                return Err(Error::ProofVerificationError);
-            }
-
-            Ok(())
+            // Batch accumulation, the pairing is left to the caller
+            Ok(opening_key.batch_accumulate(
+                &[z_challenge, (z_challenge * domain.group_gen)],
+                &[flattened_proof_a, flattened_proof_b],
+                transcript,
+            ))
         }
 
-        #[allow(clippy::too_many_arguments)]
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
         fn compute_quotient_evaluation(
             &self,
             domain: &EvaluationDomain,
@@ -396,6 +633,10 @@ pub(crate) mod alloc {
             z_h_eval: &BlsScalar,
             l1_eval: &BlsScalar,
             z_hat_eval: &BlsScalar,
+            lookup: Option<(
+                &LookupEvaluations,
+                &(BlsScalar, BlsScalar, BlsScalar, BlsScalar),
+            )>,
         ) -> BlsScalar {
             // Compute the public input polynomial evaluated at challenge `z`
             let pi_eval =
@@ -427,11 +668,34 @@ pub(crate) mod alloc {
             // l_1(z) * alpha_0^2
             let c = l1_eval * alpha_sq;
 
+            let d = lookup.map_or(
+                BlsScalar::zero(),
+                |(evaluations, (lookup_sep_challenge, delta, epsilon, _))| {
+                    // Compute powers of the lookup separation challenge
+                    let kappa = lookup_sep_challenge.square();
+                    let kappa_sq = kappa.square();
+
+                    let epsilon_one_plus_delta =
+                        epsilon * (BlsScalar::one() + delta);
+
+                    // p(z * omega) * (epsilon * (1 + delta) + h_1 + delta *
+                    // h_2) * (epsilon * (1 + delta) + h_2 + delta *
+                    // h_1(z * omega))
+                    let d_0 = evaluations.lookup_perm_eval
+                        * (epsilon_one_plus_delta
+                            + evaluations.h_1_eval
+                            + delta * evaluations.h_2_eval)
+                        * (epsilon_one_plus_delta
+                            + evaluations.h_2_eval
+                            + delta * evaluations.h_1_next_eval);
+
+                    // (l_1(z) * kappa + d_0 * kappa^2) * lookup_sep
+                    (l1_eval * kappa + d_0 * kappa_sq) * lookup_sep_challenge
+                },
+            );
+
             // Return t_eval
-            (
-                a - b - c
-                //+ d
-            ) * z_h_eval.invert().unwrap()
+            (a - b - c - d) * z_h_eval.invert().unwrap()
         }
 
         fn compute_quotient_commitment(
@@ -450,7 +714,7 @@ pub(crate) mod alloc {
         }
 
         // Commitment to [r]_1
-        #[allow(clippy::too_many_arguments)]
+        #[allow(clippy::too_many_arguments, clippy::type_complexity)]
         fn compute_linearization_commitment(
             &self,
             alpha: &BlsScalar,
@@ -462,9 +726,17 @@ pub(crate) mod alloc {
                 fixed_base_sep_challenge,
                 var_base_sep_challenge,
             ): (&BlsScalar, &BlsScalar, &BlsScalar, &BlsScalar),
+            lookup: Option<&(
+                (&lookup::VerifierKey, &LookupProof),
+                (BlsScalar, BlsScalar, BlsScalar, BlsScalar),
+            )>,
             z_challenge: &BlsScalar,
             l1_eval: BlsScalar,
             verifier_key: &VerifierKey,
+            (custom_verifier_key, custom_sep_challenges): (
+                &custom::VerifierKey,
+                &[BlsScalar],
+            ),
         ) -> Commitment {
             let mut scalars: Vec<_> = Vec::with_capacity(6);
             let mut points: Vec<G1Affine> = Vec::with_capacity(6);
@@ -513,6 +785,30 @@ pub(crate) mod alloc {
                 self.z_comm.0,
             );
 
+            if let Some((
+                (lookup_key, lookup_proof),
+                (lookup_sep_challenge, delta, epsilon, zeta),
+            )) = lookup
+            {
+                lookup_key.compute_linearization_commitment(
+                    lookup_sep_challenge,
+                    &mut scalars,
+                    &mut points,
+                    &self.evaluations,
+                    &lookup_proof.evaluations,
+                    (delta, epsilon, zeta),
+                    &l1_eval,
+                    lookup_proof.p_comm.0,
+                );
+            }
+
+            custom_verifier_key.compute_linearization_commitment(
+                custom_sep_challenges,
+                &mut scalars,
+                &mut points,
+                &self.evaluations,
+            );
+
             Commitment::from(msm_variable_base(&points, &scalars))
         }
     }
@@ -589,9 +885,8 @@ mod proof_tests {
     use ff::Field;
     use rand_core::OsRng;
 
-    #[test]
-    fn test_dusk_bytes_serde_proof() {
-        let proof = Proof {
+    fn random_proof(lookup: bool) -> Proof {
+        Proof {
             a_comm: Commitment::default(),
             b_comm: Commitment::default(),
             c_comm: Commitment::default(),
@@ -621,10 +916,40 @@ mod proof_tests {
                 r_poly_eval: BlsScalar::random(&mut OsRng),
                 perm_eval: BlsScalar::random(&mut OsRng),
             },
-        };
+            lookup: lookup.then(|| LookupProof {
+                f_comm: Commitment::default(),
+                h_1_comm: Commitment::default(),
+                h_2_comm: Commitment::default(),
+                p_comm: Commitment::default(),
+                evaluations: LookupEvaluations {
+                    f_eval: BlsScalar::random(&mut OsRng),
+                    h_1_eval: BlsScalar::random(&mut OsRng),
+                    h_1_next_eval: BlsScalar::random(&mut OsRng),
+                    h_2_eval: BlsScalar::random(&mut OsRng),
+                    table_eval: BlsScalar::random(&mut OsRng),
+                    table_next_eval: BlsScalar::random(&mut OsRng),
+                    lookup_perm_eval: BlsScalar::random(&mut OsRng),
+                },
+            }),
+        }
+    }
 
-        let proof_bytes = proof.to_bytes();
-        let got_proof = Proof::from_bytes(&proof_bytes).unwrap();
+    #[test]
+    fn test_dusk_bytes_serde_proof() {
+        let proof = random_proof(false);
+
+        let proof_bytes = proof.to_var_bytes();
+        assert_eq!(proof_bytes.len(), Proof::SIZE);
+        let got_proof = Proof::from_slice(&proof_bytes).unwrap();
         assert_eq!(got_proof, proof);
+
+        let proof = random_proof(true);
+
+        let proof_bytes = proof.to_var_bytes();
+        assert_eq!(proof_bytes.len(), Proof::LOOKUP_SIZE);
+        let got_proof = Proof::from_slice(&proof_bytes).unwrap();
+        assert_eq!(got_proof, proof);
+
+        assert!(Proof::from_slice(&proof_bytes[1..]).is_err());
     }
 }
@@ -5,9 +5,10 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::{
+    composer::GateWires,
     error::Error,
-    fft::{EvaluationDomain, Polynomial},
-    proof_system::ProverKey,
+    fft::{EvaluationDomain, Evaluations, Polynomial},
+    proof_system::{widget, ProverKey},
 };
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
@@ -16,9 +17,11 @@ use rayon::prelude::*;
 
 /// Computes the Quotient [`Polynomial`] given the [`EvaluationDomain`], a
 /// [`ProverKey`] and some other info.
+#[allow(clippy::type_complexity)]
 pub(crate) fn compute(
     domain: &EvaluationDomain,
     prover_key: &ProverKey,
+    custom: (&widget::custom::ProverKey, &[BlsScalar]),
     z_poly: &Polynomial,
     (a_w_poly, b_w_poly, c_w_poly, d_w_poly): (
         &Polynomial,
@@ -26,6 +29,10 @@ pub(crate) fn compute(
         &Polynomial,
         &Polynomial,
     ),
+    lookup: Option<(
+        (&Polynomial, &Polynomial, &Polynomial, &Polynomial),
+        &(BlsScalar, BlsScalar, BlsScalar, BlsScalar),
+    )>,
     public_inputs_poly: &Polynomial,
     (
         alpha,
@@ -48,12 +55,31 @@ pub(crate) fn compute(
     // Compute 8n evals
     let domain_8n = EvaluationDomain::new(8 * domain.size())?;
 
-    let mut z_eval_8n = domain_8n.coset_fft(z_poly);
+    // the lookup argument is only proved for circuits with lookups
+    let lookup = prover_key.lookup.as_ref().zip(lookup);
 
-    let mut a_w_eval_8n = domain_8n.coset_fft(a_w_poly);
-    let mut b_w_eval_8n = domain_8n.coset_fft(b_w_poly);
-    let c_w_eval_8n = domain_8n.coset_fft(c_w_poly);
-    let mut d_w_eval_8n = domain_8n.coset_fft(d_w_poly);
+    // the coset FFTs are independent of each other
+    let mut polys = vec![z_poly, a_w_poly, b_w_poly, c_w_poly, d_w_poly];
+    if let Some((_, ((f_poly, h_1_poly, h_2_poly, p_poly), _))) = lookup {
+        polys.extend([f_poly, h_1_poly, h_2_poly, p_poly]);
+    }
+
+    #[cfg(not(feature = "std"))]
+    let polys = polys.iter();
+
+    #[cfg(feature = "std")]
+    let polys = polys.par_iter();
+
+    let evals: Vec<_> = polys.map(|poly| domain_8n.coset_fft(poly)).collect();
+    let mut evals = evals.into_iter();
+    let mut next = || evals.next().expect("there is an evaluation per poly");
+
+    let mut z_eval_8n = next();
+
+    let mut a_w_eval_8n = next();
+    let mut b_w_eval_8n = next();
+    let c_w_eval_8n = next();
+    let mut d_w_eval_8n = next();
 
     for i in 0..8 {
         z_eval_8n.push(z_eval_8n[i]);
@@ -72,6 +98,7 @@ pub(crate) fn compute(
             var_base_challenge,
         ),
         prover_key,
+        custom,
         (&a_w_eval_8n, &b_w_eval_8n, &c_w_eval_8n, &d_w_eval_8n),
         public_inputs_poly,
     );
@@ -84,6 +111,28 @@ pub(crate) fn compute(
         (alpha, beta, gamma),
     );
 
+    let t_3 = lookup.map(|(lookup_key, (_, challenges))| {
+        let f_eval_8n = next();
+        let mut h_1_eval_8n = next();
+        let h_2_eval_8n = next();
+        let mut p_eval_8n = next();
+
+        for i in 0..8 {
+            h_1_eval_8n.push(h_1_eval_8n[i]);
+            // h_2_eval_8n push not required
+            p_eval_8n.push(p_eval_8n[i]);
+        }
+
+        compute_lookup_checks(
+            domain,
+            lookup_key,
+            &prover_key.arithmetic.q_c.1,
+            (&a_w_eval_8n, &b_w_eval_8n, &c_w_eval_8n),
+            (&f_eval_8n, &h_1_eval_8n, &h_2_eval_8n, &p_eval_8n),
+            challenges,
+        )
+    });
+
     #[cfg(not(feature = "std"))]
     let range = (0..domain_8n.size()).into_iter();
 
@@ -92,7 +141,10 @@ pub(crate) fn compute(
 
     let quotient: Vec<_> = range
         .map(|i| {
-            let numerator = t_1[i] + t_2[i];
+            let mut numerator = t_1[i] + t_2[i];
+            if let Some(t_3) = &t_3 {
+                numerator += t_3[i];
+            }
             let denominator = prover_key.v_h_coset_8n()[i];
             numerator * denominator.invert().unwrap()
         })
@@ -103,7 +155,6 @@ pub(crate) fn compute(
     Ok(Polynomial::from_coefficients_vec(coset))
 }
 
-// Ensures that the circuit is satisfied
 // Ensures that the circuit is satisfied
 fn compute_circuit_satisfiability_equation(
     domain: &EvaluationDomain,
@@ -114,6 +165,7 @@ fn compute_circuit_satisfiability_equation(
         var_base_challenge,
     ): (&BlsScalar, &BlsScalar, &BlsScalar, &BlsScalar),
     prover_key: &ProverKey,
+    (custom_key, custom_challenges): (&widget::custom::ProverKey, &[BlsScalar]),
     (a_w_eval_8n, b_w_eval_8n, c_w_eval_8n, d_w_eval_8n): (
         &[BlsScalar],
         &[BlsScalar],
@@ -192,7 +244,21 @@ fn compute_circuit_satisfiability_equation(
                 d_w_next,
             );
 
-            (a + pi) + b + c + d + e
+            let f = custom_key.compute_quotient_i(
+                i,
+                custom_challenges,
+                &GateWires {
+                    a: *a_w,
+                    b: *b_w,
+                    c: *c_w,
+                    d: *d_w,
+                    a_next: *a_w_next,
+                    b_next: *b_w_next,
+                    d_next: *d_w_next,
+                },
+            );
+
+            (a + pi) + b + c + d + e + f
         })
         .collect();
     t
@@ -240,6 +306,69 @@ fn compute_permutation_checks(
         .collect();
     t
 }
+
+// Ensures that the lookup queries are part of the table
+fn compute_lookup_checks(
+    domain: &EvaluationDomain,
+    lookup_key: &widget::lookup::ProverKey,
+    q_c_eval_8n: &Evaluations,
+    (a_w_eval_8n, b_w_eval_8n, c_w_eval_8n): (
+        &[BlsScalar],
+        &[BlsScalar],
+        &[BlsScalar],
+    ),
+    (f_eval_8n, h_1_eval_8n, h_2_eval_8n, p_eval_8n): (
+        &[BlsScalar],
+        &[BlsScalar],
+        &[BlsScalar],
+        &[BlsScalar],
+    ),
+    (lookup_challenge, delta, epsilon, zeta): &(
+        BlsScalar,
+        BlsScalar,
+        BlsScalar,
+        BlsScalar,
+    ),
+) -> Vec<BlsScalar> {
+    let domain_8n = EvaluationDomain::new(8 * domain.size()).unwrap();
+    let l1_poly = compute_first_lagrange_poly_scaled(domain, BlsScalar::one());
+    let l1_evals = domain_8n.coset_fft(&l1_poly);
+
+    let mut table_eval_8n: Vec<_> = (0..domain_8n.size())
+        .map(|i| lookup_key.compute_table_i(i, zeta))
+        .collect();
+    for i in 0..8 {
+        table_eval_8n.push(table_eval_8n[i]);
+    }
+
+    #[cfg(not(feature = "std"))]
+    let range = (0..domain_8n.size()).into_iter();
+
+    #[cfg(feature = "std")]
+    let range = (0..domain_8n.size()).into_par_iter();
+
+    let t: Vec<_> = range
+        .map(|i| {
+            lookup_key.compute_quotient_i(
+                i,
+                lookup_challenge,
+                (
+                    &a_w_eval_8n[i],
+                    &b_w_eval_8n[i],
+                    &c_w_eval_8n[i],
+                    &q_c_eval_8n[i],
+                ),
+                (&f_eval_8n[i], &table_eval_8n[i], &table_eval_8n[i + 8]),
+                (&h_1_eval_8n[i], &h_1_eval_8n[i + 8], &h_2_eval_8n[i]),
+                (&p_eval_8n[i], &p_eval_8n[i + 8]),
+                &l1_evals[i],
+                (delta, epsilon, zeta),
+            )
+        })
+        .collect();
+    t
+}
+
 fn compute_first_lagrange_poly_scaled(
     domain: &EvaluationDomain,
     scale: BlsScalar,
@@ -5,14 +5,56 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::commitment_scheme::Commitment;
+use core::fmt;
 use dusk_bytes::{DeserializableSlice, Serializable};
 
 pub mod arithmetic;
+#[cfg(feature = "alloc")]
+pub mod custom;
 pub mod ecc;
 pub mod logic;
+pub mod lookup;
 pub mod permutation;
 pub mod range;
 
+/// Gates that can be enabled on a row of the circuit, used to report which
+/// constraint of a row doesn't hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateType {
+    /// Arithmetic gate, along with the public input of the row
+    Arithmetic,
+    /// Range gate, accumulating four quads of a range component
+    Range,
+    /// Logic gate, accumulating a step of a XOR or AND component
+    Logic,
+    /// Curve addition of a fixed base, used by the fixed base scalar
+    /// multiplication
+    FixedBaseAddition,
+    /// Curve addition of two witness points
+    VariableBaseAddition,
+    /// Lookup gate, querying a row of the lookup tables
+    Lookup,
+    /// Custom gate with the given selector label
+    Custom(&'static [u8]),
+}
+
+impl fmt::Display for GateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arithmetic => write!(f, "arithmetic"),
+            Self::Range => write!(f, "range"),
+            Self::Logic => write!(f, "logic"),
+            Self::FixedBaseAddition => write!(f, "fixed base addition"),
+            Self::VariableBaseAddition => write!(f, "variable base addition"),
+            Self::Lookup => write!(f, "lookup"),
+            Self::Custom(selector) => match core::str::from_utf8(selector) {
+                Ok(selector) => write!(f, "custom `{}`", selector),
+                Err(_) => write!(f, "custom {:?}", selector),
+            },
+        }
+    }
+}
+
 #[cfg(feature = "rkyv-impl")]
 use crate::util::check_field;
 #[cfg(feature = "rkyv-impl")]
@@ -34,7 +76,8 @@ use rkyv::{
     archive(bound(serialize = "__S: Serializer + ScratchSpace"))
 )]
 pub struct VerifierKey {
-    /// Circuit size (not padded to a power of two).
+    /// Circuit size, including the rows of the lookup table (not padded to a
+    /// power of two).
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) n: usize,
     /// VerifierKey for arithmetic gates
@@ -52,6 +95,9 @@ pub struct VerifierKey {
     /// VerifierKey for variable base curve addition gates
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) variable_base: ecc::curve_addition::VerifierKey,
+    /// VerifierKey for lookup gates, for circuits with lookups
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) lookup: Option<lookup::VerifierKey>,
     /// VerifierKey for permutation checks
     #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
     pub(crate) permutation: permutation::VerifierKey,
@@ -71,6 +117,7 @@ impl<C> CheckBytes<C> for ArchivedVerifierKey {
         check_field(&(*value).range, context, "range")?;
         check_field(&(*value).fixed_base, context, "fixed_base")?;
         check_field(&(*value).variable_base, context, "variable_base")?;
+        check_field(&(*value).lookup, context, "lookup")?;
         check_field(&(*value).permutation, context, "permutation")?;
 
         Ok(&*value)
@@ -103,13 +150,23 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         writer.write(&self.permutation.s_sigma_3.to_bytes());
         writer.write(&self.permutation.s_sigma_4.to_bytes());
 
+        // The slots of the lookup commitments are left zeroed for circuits
+        // without lookups, which is never the encoding of a point
+        if let Some(lookup) = &self.lookup {
+            writer.write(&lookup.q_lookup.to_bytes());
+            writer.write(&lookup.table_1.to_bytes());
+            writer.write(&lookup.table_2.to_bytes());
+            writer.write(&lookup.table_3.to_bytes());
+            writer.write(&lookup.table_4.to_bytes());
+        }
+
         buff
     }
 
     fn from_bytes(buf: &[u8; Self::SIZE]) -> Result<VerifierKey, Self::Error> {
         let mut buffer = &buf[..];
 
-        Ok(Self::from_polynomial_commitments(
+        let mut verifier_key = Self::from_polynomial_commitments(
             u64::from_reader(&mut buffer)? as usize,
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
@@ -126,14 +183,26 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
-        ))
+        );
+
+        if buffer.iter().any(|b| *b != 0) {
+            verifier_key.lookup = Some(lookup::VerifierKey {
+                q_lookup: Commitment::from_reader(&mut buffer)?,
+                table_1: Commitment::from_reader(&mut buffer)?,
+                table_2: Commitment::from_reader(&mut buffer)?,
+                table_3: Commitment::from_reader(&mut buffer)?,
+                table_4: Commitment::from_reader(&mut buffer)?,
+            });
+        }
+
+        Ok(verifier_key)
     }
 }
 
 impl VerifierKey {
     /// Constructs a [`VerifierKey`] from the widget VerifierKey's that are
     /// constructed based on the selector polynomial commitments and the
-    /// sigma polynomial commitments.
+    /// sigma polynomial commitments, without lookups.
     pub(crate) fn from_polynomial_commitments(
         n: usize,
         q_m: Commitment,
@@ -187,6 +256,7 @@ impl VerifierKey {
             range,
             fixed_base,
             variable_base,
+            lookup: None,
             permutation,
         }
     }
@@ -198,16 +268,18 @@ pub(crate) mod alloc {
     use crate::{
         error::Error,
         fft::{EvaluationDomain, Evaluations, Polynomial},
-        transcript::TranscriptProtocol,
+        transcript::TranscriptExt,
     };
     #[rustfmt::skip]
     use ::alloc::vec::Vec;
     use dusk_bls12_381::BlsScalar;
-    use merlin::Transcript;
 
     impl VerifierKey {
         /// Adds the circuit description to the transcript
-        pub(crate) fn seed_transcript(&self, transcript: &mut Transcript) {
+        pub(crate) fn seed_transcript<T: TranscriptExt>(
+            &self,
+            transcript: &mut T,
+        ) {
             transcript.append_commitment(b"q_m", &self.arithmetic.q_m);
             transcript.append_commitment(b"q_l", &self.arithmetic.q_l);
             transcript.append_commitment(b"q_r", &self.arithmetic.q_r);
@@ -225,6 +297,13 @@ pub(crate) mod alloc {
                 b"q_fixed_group_add",
                 &self.fixed_base.q_fixed_group_add,
             );
+            if let Some(lookup) = &self.lookup {
+                transcript.append_commitment(b"q_lookup", &lookup.q_lookup);
+                transcript.append_commitment(b"table_1", &lookup.table_1);
+                transcript.append_commitment(b"table_2", &lookup.table_2);
+                transcript.append_commitment(b"table_3", &lookup.table_3);
+                transcript.append_commitment(b"table_4", &lookup.table_4);
+            }
 
             transcript
                 .append_commitment(b"s_sigma_1", &self.permutation.s_sigma_1);
@@ -255,6 +334,12 @@ pub(crate) mod alloc {
         /// Circuit size
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
         pub(crate) n: usize,
+        /// Whether the proofs are blinded, hiding the witnesses
+        #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+        pub(crate) hiding: bool,
+        /// Whether the gates of the circuit were optimized when compiled
+        #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+        pub(crate) optimized: bool,
         /// ProverKey for arithmetic gate
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
         pub(crate) arithmetic: arithmetic::ProverKey,
@@ -270,6 +355,9 @@ pub(crate) mod alloc {
         /// ProverKey for variable base curve addition gates
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
         pub(crate) variable_base: ecc::curve_addition::ProverKey,
+        /// ProverKey for lookup gates, for circuits with lookups
+        #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+        pub(crate) lookup: Option<lookup::ProverKey>,
         /// ProverKey for permutation checks
         #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
         pub(crate) permutation: permutation::ProverKey,
@@ -297,16 +385,20 @@ pub(crate) mod alloc {
 
             // The amount of distinct polynomials in `ProverKey`
             // 7 (arithmetic) + 1 (logic) + 1 (range) + 1 (fixed_base)
-            // + 1 (variable_base) + 4 (permutation)
-            let poly_num = 15;
+            // + 1 (variable_base) + 5 (lookup, if any) + 4 (permutation)
+            let poly_num = match self.lookup {
+                Some(_) => 20,
+                None => 15,
+            };
 
             // The amount of distinct evaluations in `ProverKey`
             // poly_num + 1 (permutation) + 1 (v_h_coset_4n)
             let eval_num = poly_num + 2;
 
             // The amount of i64 in `ProverKey`
-            //  poly_num + 1 (self.n) + 1 (eval_size)
-            let i64_num = poly_num + 2;
+            //  poly_num + 1 (self.n) + 1 (self.hiding) + 1 (self.optimized)
+            //  + 1 (self.lookup) + 1 (eval_size)
+            let i64_num = poly_num + 5;
 
             // Calculate the amount of bytes needed to serialize `ProverKey`
             poly_size * poly_num + eval_size * eval_num + u64::SIZE * i64_num
@@ -324,6 +416,9 @@ pub(crate) mod alloc {
 
             let mut writer = &mut bytes[..];
             writer.write(&(self.n as u64).to_bytes());
+            writer.write(&(self.hiding as u64).to_bytes());
+            writer.write(&(self.optimized as u64).to_bytes());
+            writer.write(&(self.lookup.is_some() as u64).to_bytes());
             // Write Evaluation len in bytes.
             writer.write(&(eval_size as u64).to_bytes());
 
@@ -385,6 +480,29 @@ pub(crate) mod alloc {
                 &self.variable_base.q_variable_group_add.1.to_var_bytes(),
             );
 
+            // Lookup
+            if let Some(lookup) = &self.lookup {
+                writer.write(&(lookup.q_lookup.0.len() as u64).to_bytes());
+                writer.write(&lookup.q_lookup.0.to_var_bytes());
+                writer.write(&lookup.q_lookup.1.to_var_bytes());
+
+                writer.write(&(lookup.table_1.0.len() as u64).to_bytes());
+                writer.write(&lookup.table_1.0.to_var_bytes());
+                writer.write(&lookup.table_1.1.to_var_bytes());
+
+                writer.write(&(lookup.table_2.0.len() as u64).to_bytes());
+                writer.write(&lookup.table_2.0.to_var_bytes());
+                writer.write(&lookup.table_2.1.to_var_bytes());
+
+                writer.write(&(lookup.table_3.0.len() as u64).to_bytes());
+                writer.write(&lookup.table_3.0.to_var_bytes());
+                writer.write(&lookup.table_3.1.to_var_bytes());
+
+                writer.write(&(lookup.table_4.0.len() as u64).to_bytes());
+                writer.write(&lookup.table_4.0.to_var_bytes());
+                writer.write(&lookup.table_4.1.to_var_bytes());
+            }
+
             // Permutation
             writer
                 .write(&(self.permutation.s_sigma_1.0.len() as u64).to_bytes());
@@ -417,6 +535,21 @@ pub(crate) mod alloc {
         pub fn from_slice(bytes: &[u8]) -> Result<ProverKey, Error> {
             let mut buffer = bytes;
             let n = u64::from_reader(&mut buffer)? as usize;
+            let hiding = match u64::from_reader(&mut buffer)? {
+                0 => false,
+                1 => true,
+                _ => return Err(dusk_bytes::Error::InvalidData.into()),
+            };
+            let optimized = match u64::from_reader(&mut buffer)? {
+                0 => false,
+                1 => true,
+                _ => return Err(dusk_bytes::Error::InvalidData.into()),
+            };
+            let lookup = match u64::from_reader(&mut buffer)? {
+                0 => false,
+                1 => true,
+                _ => return Err(dusk_bytes::Error::InvalidData.into()),
+            };
             let evaluations_size = u64::from_reader(&mut buffer)? as usize;
             // let domain = crate::fft::EvaluationDomain::new(4 * size)?;
             // TODO: By creating this we can avoid including the
@@ -494,6 +627,39 @@ pub(crate) mod alloc {
             let q_variable_group_add =
                 (q_variable_group_add_poly, q_variable_group_add_evals);
 
+            let lookup = match lookup {
+                true => {
+                    let q_lookup_poly = poly_from_reader(&mut buffer)?;
+                    let q_lookup_evals = evals_from_reader(&mut buffer)?;
+                    let q_lookup = (q_lookup_poly, q_lookup_evals);
+
+                    let table_1_poly = poly_from_reader(&mut buffer)?;
+                    let table_1_evals = evals_from_reader(&mut buffer)?;
+                    let table_1 = (table_1_poly, table_1_evals);
+
+                    let table_2_poly = poly_from_reader(&mut buffer)?;
+                    let table_2_evals = evals_from_reader(&mut buffer)?;
+                    let table_2 = (table_2_poly, table_2_evals);
+
+                    let table_3_poly = poly_from_reader(&mut buffer)?;
+                    let table_3_evals = evals_from_reader(&mut buffer)?;
+                    let table_3 = (table_3_poly, table_3_evals);
+
+                    let table_4_poly = poly_from_reader(&mut buffer)?;
+                    let table_4_evals = evals_from_reader(&mut buffer)?;
+                    let table_4 = (table_4_poly, table_4_evals);
+
+                    Some(lookup::ProverKey {
+                        q_lookup,
+                        table_1,
+                        table_2,
+                        table_3,
+                        table_4,
+                    })
+                }
+                false => None,
+            };
+
             let s_sigma_1_poly = poly_from_reader(&mut buffer)?;
             let s_sigma_1_evals = evals_from_reader(&mut buffer)?;
             let s_sigma_1 = (s_sigma_1_poly, s_sigma_1_evals);
@@ -552,11 +718,14 @@ pub(crate) mod alloc {
 
             let prover_key = ProverKey {
                 n,
+                hiding,
+                optimized,
                 arithmetic,
                 logic,
                 range,
                 fixed_base,
                 variable_base,
+                lookup,
                 permutation,
                 v_h_coset_8n,
             };
@@ -615,6 +784,12 @@ mod test {
 
         let q_variable_group_add = rand_poly_eval(n);
 
+        let q_lookup = rand_poly_eval(n);
+        let table_1 = rand_poly_eval(n);
+        let table_2 = rand_poly_eval(n);
+        let table_3 = rand_poly_eval(n);
+        let table_4 = rand_poly_eval(n);
+
         let s_sigma_1 = rand_poly_eval(n);
         let s_sigma_2 = rand_poly_eval(n);
         let s_sigma_3 = rand_poly_eval(n);
@@ -659,13 +834,24 @@ mod test {
             q_variable_group_add,
         };
 
+        let lookup = lookup::ProverKey {
+            q_lookup,
+            table_1,
+            table_2,
+            table_3,
+            table_4,
+        };
+
         let prover_key = ProverKey {
             n,
+            hiding: false,
+            optimized: false,
             arithmetic,
             logic,
             fixed_base,
             range,
             variable_base,
+            lookup: Some(lookup),
             permutation,
             v_h_coset_8n,
         };
@@ -675,6 +861,19 @@ mod test {
 
         assert_eq!(pk, prover_key);
         assert_eq!(pk.to_var_bytes(), prover_key.to_var_bytes());
+
+        // the keys of circuits without lookups don't store the lookup
+        // polynomials
+        let prover_key = ProverKey {
+            lookup: None,
+            ..prover_key
+        };
+
+        let lookup_free_bytes = prover_key.to_var_bytes();
+        let pk = ProverKey::from_slice(&lookup_free_bytes).unwrap();
+
+        assert_eq!(pk, prover_key);
+        assert!(lookup_free_bytes.len() < prover_key_bytes.len());
     }
 
     #[test]
@@ -699,6 +898,12 @@ mod test {
 
         let q_logic = Commitment(G1Affine::generator());
 
+        let q_lookup = Commitment(G1Affine::generator());
+        let table_1 = Commitment(G1Affine::generator());
+        let table_2 = Commitment(G1Affine::generator());
+        let table_3 = Commitment(G1Affine::generator());
+        let table_4 = Commitment(G1Affine::generator());
+
         let s_sigma_1 = Commitment(G1Affine::generator());
         let s_sigma_2 = Commitment(G1Affine::generator());
         let s_sigma_3 = Commitment(G1Affine::generator());
@@ -727,6 +932,14 @@ mod test {
             q_variable_group_add,
         };
 
+        let lookup = lookup::VerifierKey {
+            q_lookup,
+            table_1,
+            table_2,
+            table_3,
+            table_4,
+        };
+
         let permutation = permutation::VerifierKey {
             s_sigma_1,
             s_sigma_2,
@@ -741,6 +954,7 @@ mod test {
             range,
             fixed_base,
             variable_base,
+            lookup: Some(lookup),
             permutation,
         };
 
@@ -748,5 +962,19 @@ mod test {
         let got = VerifierKey::from_bytes(&verifier_key_bytes).unwrap();
 
         assert_eq!(got, verifier_key);
+
+        // the lookup commitments of circuits without lookups are left zeroed
+        let verifier_key = VerifierKey {
+            lookup: None,
+            ..verifier_key
+        };
+
+        let verifier_key_bytes = verifier_key.to_bytes();
+        let got = VerifierKey::from_bytes(&verifier_key_bytes).unwrap();
+
+        assert_eq!(got, verifier_key);
+        assert!(verifier_key_bytes[15 * Commitment::SIZE + u64::SIZE..]
+            .iter()
+            .all(|b| *b == 0));
     }
 }
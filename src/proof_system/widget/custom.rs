@@ -0,0 +1,54 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+mod proverkey;
+mod verifierkey;
+
+pub(crate) use proverkey::ProverKey;
+pub(crate) use verifierkey::VerifierKey;
+
+use alloc::vec::Vec;
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+
+use crate::composer::CustomWidget;
+use crate::error::Error;
+
+/// Write the selector label of the gate, prefixed with its length
+fn write_selector(bytes: &mut Vec<u8>, widget: &CustomWidget) {
+    bytes.extend((widget.selector().len() as u64).to_bytes());
+    bytes.extend(widget.selector());
+}
+
+/// Read a selector label and find the gate it belongs to among `gates`
+///
+/// The implementations of the gates can't be serialized, so they are
+/// registered again when the keys are deserialized.
+fn read_selector(
+    buffer: &mut &[u8],
+    gates: &[CustomWidget],
+) -> Result<CustomWidget, Error> {
+    let len = u64::from_reader(buffer)? as usize;
+    let selector = take(buffer, len)?;
+
+    gates
+        .iter()
+        .find(|gate| gate.selector() == selector)
+        .copied()
+        .ok_or(Error::UnregisteredCustomGate)
+}
+
+/// Split the first `len` bytes off the buffer
+fn take<'a>(buffer: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if buffer.len() < len {
+        return Err(Error::NotEnoughBytes);
+    }
+
+    let (bytes, rest) = buffer.split_at(len);
+    *buffer = rest;
+
+    Ok(bytes)
+}
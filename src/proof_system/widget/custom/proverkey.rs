@@ -0,0 +1,94 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use crate::composer::{CustomWidget, GateWires};
+use crate::error::Error;
+use crate::fft::{Evaluations, Polynomial};
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{DeserializableSlice, Serializable};
+
+use super::{read_selector, take, write_selector};
+
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub(crate) struct ProverKey {
+    pub(crate) widgets: Vec<(CustomWidget, (Polynomial, Evaluations))>,
+}
+
+impl ProverKey {
+    /// Serialize the selectors of the gates along with their labels
+    pub(crate) fn to_var_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend((self.widgets.len() as u64).to_bytes());
+        self.widgets.iter().for_each(|(widget, (poly, evals))| {
+            let evals = evals.to_var_bytes();
+
+            write_selector(&mut bytes, widget);
+            bytes.extend((poly.len() as u64).to_bytes());
+            bytes.extend(poly.to_var_bytes());
+            bytes.extend((evals.len() as u64).to_bytes());
+            bytes.extend(evals);
+        });
+
+        bytes
+    }
+
+    /// Deserialize the selectors of the gates, looking up their
+    /// implementations in `gates`
+    pub(crate) fn from_reader(
+        buffer: &mut &[u8],
+        gates: &[CustomWidget],
+    ) -> Result<Self, Error> {
+        let len = u64::from_reader(buffer)? as usize;
+
+        let widgets = (0..len)
+            .map(|_| {
+                let widget = read_selector(buffer, gates)?;
+
+                let poly_len = u64::from_reader(buffer)? as usize;
+                let poly = take(buffer, poly_len * BlsScalar::SIZE)?;
+                let poly = Polynomial::from_slice(poly)?;
+
+                let evals_len = u64::from_reader(buffer)? as usize;
+                let evals = Evaluations::from_slice(take(buffer, evals_len)?)?;
+
+                Ok((widget, (poly, evals)))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { widgets })
+    }
+
+    pub(crate) fn compute_quotient_i(
+        &self,
+        index: usize,
+        separation_challenges: &[BlsScalar],
+        wires: &GateWires,
+    ) -> BlsScalar {
+        self.widgets.iter().zip(separation_challenges).fold(
+            BlsScalar::zero(),
+            |acc, ((widget, q), challenge)| {
+                acc + widget.constraint(wires) * q.1[index] * challenge
+            },
+        )
+    }
+
+    pub(crate) fn compute_linearization(
+        &self,
+        separation_challenges: &[BlsScalar],
+        evaluations: &GateWires,
+    ) -> Polynomial {
+        self.widgets
+            .iter()
+            .zip(separation_challenges)
+            .map(|((widget, q), challenge)| {
+                &q.0 * &(widget.linearization(evaluations) * challenge)
+            })
+            .sum()
+    }
+}
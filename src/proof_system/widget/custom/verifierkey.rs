@@ -0,0 +1,101 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use crate::commitment_scheme::Commitment;
+use crate::composer::{CustomWidget, GateWires};
+use crate::error::Error;
+use crate::proof_system::linearization_poly::ProofEvaluations;
+use crate::transcript::{TranscriptExt, TranscriptProtocol};
+use dusk_bls12_381::{BlsScalar, G1Affine};
+use dusk_bytes::{DeserializableSlice, Serializable};
+
+use super::{read_selector, write_selector};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub(crate) struct VerifierKey {
+    pub(crate) widgets: Vec<(CustomWidget, Commitment)>,
+}
+
+impl VerifierKey {
+    /// Serialize the selector commitments of the gates along with their
+    /// labels
+    pub(crate) fn to_var_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend((self.widgets.len() as u64).to_bytes());
+        self.widgets.iter().for_each(|(widget, q)| {
+            write_selector(&mut bytes, widget);
+            bytes.extend(q.to_bytes());
+        });
+
+        bytes
+    }
+
+    /// Deserialize the selector commitments of the gates, looking up their
+    /// implementations in `gates`
+    pub(crate) fn from_reader(
+        buffer: &mut &[u8],
+        gates: &[CustomWidget],
+    ) -> Result<Self, Error> {
+        let len = u64::from_reader(buffer)? as usize;
+
+        let widgets = (0..len)
+            .map(|_| {
+                let widget = read_selector(buffer, gates)?;
+                let q = Commitment::from_reader(buffer)?;
+
+                Ok((widget, q))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { widgets })
+    }
+
+    /// Adds the selector commitments of the gates to the transcript
+    pub(crate) fn seed_transcript<T: TranscriptExt>(&self, transcript: &mut T) {
+        self.widgets.iter().for_each(|(widget, q)| {
+            transcript.append_commitment(widget.selector(), q)
+        });
+    }
+
+    /// Computes the separation challenge of each gate
+    pub(crate) fn separation_challenges<T: TranscriptProtocol>(
+        &self,
+        transcript: &mut T,
+    ) -> Vec<BlsScalar> {
+        self.widgets
+            .iter()
+            .map(|(widget, _)| transcript.challenge_scalar(widget.selector()))
+            .collect()
+    }
+
+    pub(crate) fn compute_linearization_commitment(
+        &self,
+        separation_challenges: &[BlsScalar],
+        scalars: &mut Vec<BlsScalar>,
+        points: &mut Vec<G1Affine>,
+        evaluations: &ProofEvaluations,
+    ) {
+        let evaluations = GateWires {
+            a: evaluations.a_eval,
+            b: evaluations.b_eval,
+            c: evaluations.c_eval,
+            d: evaluations.d_eval,
+            a_next: evaluations.a_next_eval,
+            b_next: evaluations.b_next_eval,
+            d_next: evaluations.d_next_eval,
+        };
+
+        self.widgets.iter().zip(separation_challenges).for_each(
+            |((widget, q), challenge)| {
+                scalars.push(widget.linearization(&evaluations) * challenge);
+                points.push(q.0);
+            },
+        );
+    }
+}
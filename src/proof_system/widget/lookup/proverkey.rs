@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::fft::{Evaluations, Polynomial};
+use crate::lookup::compress;
+use dusk_bls12_381::BlsScalar;
+
+#[cfg(feature = "rkyv-impl")]
+use bytecheck::CheckBytes;
+#[cfg(feature = "rkyv-impl")]
+use rkyv::{
+    ser::{ScratchSpace, Serializer},
+    Archive, Deserialize, Serialize,
+};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(Archive, Deserialize, Serialize),
+    archive(bound(serialize = "__S: Serializer + ScratchSpace")),
+    archive_attr(derive(CheckBytes))
+)]
+pub(crate) struct ProverKey {
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) q_lookup: (Polynomial, Evaluations),
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_1: (Polynomial, Evaluations),
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_2: (Polynomial, Evaluations),
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_3: (Polynomial, Evaluations),
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_4: (Polynomial, Evaluations),
+}
+
+impl ProverKey {
+    /// Compress the four table columns into a single polynomial
+    /// `t(X) = t_1(X) + zeta · t_2(X) + zeta^2 · t_3(X) + zeta^3 · t_4(X)`
+    pub(crate) fn compute_table_poly(&self, zeta: &BlsScalar) -> Polynomial {
+        let t = &self.table_3.0 + &(&self.table_4.0 * zeta);
+        let t = &self.table_2.0 + &(&t * zeta);
+
+        &self.table_1.0 + &(&t * zeta)
+    }
+
+    /// Compress the four table columns evaluated at the `index` of the coset
+    pub(crate) fn compute_table_i(
+        &self,
+        index: usize,
+        zeta: &BlsScalar,
+    ) -> BlsScalar {
+        compress(
+            &self.table_1.1[index],
+            &self.table_2.1[index],
+            &self.table_3.1[index],
+            &self.table_4.1[index],
+            zeta,
+        )
+    }
+
+    pub(crate) fn compute_quotient_i(
+        &self,
+        index: usize,
+        lookup_separation_challenge: &BlsScalar,
+        (a_w_i, b_w_i, c_w_i, q_c_i): (
+            &BlsScalar,
+            &BlsScalar,
+            &BlsScalar,
+            &BlsScalar,
+        ),
+        (f_i, table_i, table_i_next): (&BlsScalar, &BlsScalar, &BlsScalar),
+        (h_1_i, h_1_i_next, h_2_i): (&BlsScalar, &BlsScalar, &BlsScalar),
+        (p_i, p_i_next): (&BlsScalar, &BlsScalar),
+        l1_i: &BlsScalar,
+        (delta, epsilon, zeta): (&BlsScalar, &BlsScalar, &BlsScalar),
+    ) -> BlsScalar {
+        let q_lookup_i = &self.q_lookup.1[index];
+
+        let kappa = lookup_separation_challenge.square();
+        let kappa_sq = kappa.square();
+
+        let one_plus_delta = BlsScalar::one() + delta;
+        let epsilon_one_plus_delta = epsilon * one_plus_delta;
+
+        // q_lookup(X) · (a(X) + zeta · b(X) + zeta^2 · c(X) + zeta^3 · q_c(X)
+        // - f(X))
+        let b_1 =
+            q_lookup_i * (compress(a_w_i, b_w_i, c_w_i, q_c_i, zeta) - f_i);
+
+        // L_1(X) · (p(X) - 1)
+        let b_2 = l1_i * (p_i - BlsScalar::one()) * kappa;
+
+        // p(X) · (1 + delta) · (epsilon + f(X))
+        // · (epsilon · (1 + delta) + t(X) + delta · t(Xw))
+        // - p(Xw) · (epsilon · (1 + delta) + h_1(X) + delta · h_2(X))
+        // · (epsilon · (1 + delta) + h_2(X) + delta · h_1(Xw))
+        let b_3 = {
+            let num = p_i
+                * one_plus_delta
+                * (epsilon + f_i)
+                * (epsilon_one_plus_delta + table_i + delta * table_i_next);
+            let den = p_i_next
+                * (epsilon_one_plus_delta + h_1_i + delta * h_2_i)
+                * (epsilon_one_plus_delta + h_2_i + delta * h_1_i_next);
+
+            (num - den) * kappa_sq
+        };
+
+        (b_1 + b_2 + b_3) * lookup_separation_challenge
+    }
+
+    pub(crate) fn compute_linearization(
+        &self,
+        lookup_separation_challenge: &BlsScalar,
+        (a_eval, b_eval, c_eval, q_c_eval): (
+            &BlsScalar,
+            &BlsScalar,
+            &BlsScalar,
+            &BlsScalar,
+        ),
+        (f_eval, table_eval, table_next_eval): (
+            &BlsScalar,
+            &BlsScalar,
+            &BlsScalar,
+        ),
+        l1_eval: &BlsScalar,
+        (delta, epsilon, zeta): (&BlsScalar, &BlsScalar, &BlsScalar),
+        p_poly: &Polynomial,
+    ) -> Polynomial {
+        let q_lookup_poly = &self.q_lookup.0;
+
+        let kappa = lookup_separation_challenge.square();
+        let kappa_sq = kappa.square();
+
+        let one_plus_delta = BlsScalar::one() + delta;
+        let epsilon_one_plus_delta = epsilon * one_plus_delta;
+
+        // (a_eval + zeta · b_eval + zeta^2 · c_eval + zeta^3 · q_c_eval
+        // - f_eval) · q_lookup(X)
+        let b_1 = compress(a_eval, b_eval, c_eval, q_c_eval, zeta) - f_eval;
+        let b_1 = b_1 * lookup_separation_challenge;
+
+        // (L_1(z) · kappa + (1 + delta) · (epsilon + f_eval)
+        // · (epsilon · (1 + delta) + t_eval + delta · t_next_eval) · kappa^2)
+        // · p(X)
+        let b_2 = l1_eval * kappa
+            + one_plus_delta
+                * (epsilon + f_eval)
+                * (epsilon_one_plus_delta
+                    + table_eval
+                    + delta * table_next_eval)
+                * kappa_sq;
+        let b_2 = b_2 * lookup_separation_challenge;
+
+        &(q_lookup_poly * &b_1) + &(p_poly * &b_2)
+    }
+}
@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::commitment_scheme::Commitment;
+
+#[cfg(feature = "rkyv-impl")]
+use bytecheck::CheckBytes;
+#[cfg(feature = "rkyv-impl")]
+use rkyv::{
+    ser::{ScratchSpace, Serializer},
+    Archive, Deserialize, Serialize,
+};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "rkyv-impl",
+    derive(Archive, Deserialize, Serialize),
+    archive(bound(serialize = "__S: Serializer + ScratchSpace")),
+    archive_attr(derive(CheckBytes))
+)]
+pub(crate) struct VerifierKey {
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) q_lookup: Commitment,
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_1: Commitment,
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_2: Commitment,
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_3: Commitment,
+    #[cfg_attr(feature = "rkyv-impl", omit_bounds)]
+    pub(crate) table_4: Commitment,
+}
+
+#[cfg(feature = "alloc")]
+mod alloc {
+    use super::*;
+    use crate::lookup::compress;
+    use crate::proof_system::linearization_poly::{
+        LookupEvaluations, ProofEvaluations,
+    };
+    #[rustfmt::skip]
+    use ::alloc::vec::Vec;
+    use dusk_bls12_381::{BlsScalar, G1Affine};
+
+    impl VerifierKey {
+        /// Compress the commitments to the four table columns into a
+        /// commitment to `t(X) = t_1(X) + zeta · t_2(X) + zeta^2 · t_3(X) +
+        /// zeta^3 · t_4(X)`
+        pub(crate) fn compute_table_commitment(
+            &self,
+            zeta: &BlsScalar,
+        ) -> Commitment {
+            let zeta_sq = zeta.square();
+            let zeta_cu = zeta_sq * zeta;
+
+            let t = self.table_1.0
+                + self.table_2.0 * zeta
+                + self.table_3.0 * zeta_sq
+                + self.table_4.0 * zeta_cu;
+
+            Commitment::from(t)
+        }
+
+        pub(crate) fn compute_linearization_commitment(
+            &self,
+            lookup_separation_challenge: &BlsScalar,
+            scalars: &mut Vec<BlsScalar>,
+            points: &mut Vec<G1Affine>,
+            evaluations: &ProofEvaluations,
+            lookup_evaluations: &LookupEvaluations,
+            (delta, epsilon, zeta): (&BlsScalar, &BlsScalar, &BlsScalar),
+            l1_eval: &BlsScalar,
+            p_comm: G1Affine,
+        ) {
+            let kappa = lookup_separation_challenge.square();
+            let kappa_sq = kappa.square();
+
+            let one_plus_delta = BlsScalar::one() + delta;
+            let epsilon_one_plus_delta = epsilon * one_plus_delta;
+
+            let b_1 = compress(
+                &evaluations.a_eval,
+                &evaluations.b_eval,
+                &evaluations.c_eval,
+                &evaluations.q_c_eval,
+                zeta,
+            ) - lookup_evaluations.f_eval;
+
+            scalars.push(b_1 * lookup_separation_challenge);
+            points.push(self.q_lookup.0);
+
+            let b_2 = l1_eval * kappa
+                + one_plus_delta
+                    * (epsilon + lookup_evaluations.f_eval)
+                    * (epsilon_one_plus_delta
+                        + lookup_evaluations.table_eval
+                        + delta * lookup_evaluations.table_next_eval)
+                    * kappa_sq;
+
+            scalars.push(b_2 * lookup_separation_challenge);
+            points.push(p_comm);
+        }
+    }
+}
@@ -6,15 +6,25 @@
 
 //! PLONK runtime controller
 
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
 use dusk_bls12_381::BlsScalar;
+use hashbrown::HashMap;
 
+#[cfg(feature = "debug")]
+use crate::composer::Builder;
 use crate::constraint_system::{Constraint, Witness};
 
 #[cfg(feature = "debug")]
 use crate::debugger::Debugger;
 
 /// Runtime events
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum RuntimeEvent {
     /// A witness was appended to the constraint system
@@ -25,19 +35,127 @@ pub enum RuntimeEvent {
         v: BlsScalar,
     },
 
+    /// A name was given to a witness
+    WitnessNamed {
+        /// Named witness
+        w: Witness,
+        /// Name of the witness, relative to the current namespace
+        name: String,
+    },
+
     /// A constraint was appended
     ConstraintAppended {
         /// Appended constraint
         c: Constraint,
     },
 
+    /// A namespace was entered, nested in the current one
+    NamespaceEntered {
+        /// Name of the namespace
+        name: String,
+    },
+
+    /// The current namespace was exited
+    NamespaceExited,
+
+    /// A gadget started appending its constraints, nested in the current one
+    GadgetEntered {
+        /// Name of the gadget
+        name: &'static str,
+    },
+
+    /// A gadget finished appending its constraints
+    GadgetExited {
+        /// Name of the gadget
+        name: &'static str,
+    },
+
     /// The proof construction was finished
     ProofFinished,
 }
 
+/// Observer of the events of a [`Runtime`]
+///
+/// An observer registered with [`Runtime::observe`] or
+/// [`Builder::observed`](crate::composer::Builder::observed) receives every
+/// event of the runtime from then on, in order, as the circuit is built. It
+/// is shared with the clones of the runtime, so it keeps its state behind
+/// interior mutability.
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use dusk_plonk::prelude::*;
+/// use dusk_plonk::runtime::{Observer, RuntimeEvent};
+///
+/// #[derive(Default)]
+/// struct RangeCounter(AtomicUsize);
+///
+/// impl Observer for RangeCounter {
+///     fn event(&self, event: &RuntimeEvent) {
+///         if let RuntimeEvent::GadgetEntered { name: "component_range" } =
+///             event
+///         {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let counter = Arc::new(RangeCounter::default());
+/// let mut builder = Builder::observed(counter.clone());
+///
+/// let w = builder.append_witness(42);
+/// builder.component_range::<4>(w);
+///
+/// assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+/// ```
+pub trait Observer: Send + Sync {
+    /// Handle an event of the runtime
+    fn event(&self, event: &RuntimeEvent);
+}
+
+// observers registered to a runtime, in order of registration
+#[derive(Clone, Default)]
+struct Observers(Vec<Arc<dyn Observer>>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// Guard of a gadget entered with [`Runtime::gadget`]
+///
+/// The [`GadgetExited`](RuntimeEvent::GadgetExited) event of the gadget is
+/// emitted when the guard is dropped, to the observers that received its
+/// [`GadgetEntered`](RuntimeEvent::GadgetEntered) event.
+#[must_use = "the gadget is exited when the guard is dropped"]
+#[derive(Debug)]
+pub struct GadgetGuard {
+    observers: Observers,
+    name: &'static str,
+}
+
+impl Drop for GadgetGuard {
+    fn drop(&mut self) {
+        let event = RuntimeEvent::GadgetExited { name: self.name };
+        self.observers.0.iter().for_each(|o| o.event(&event));
+    }
+}
+
 /// Runtime structure with debugger
 #[derive(Debug, Clone)]
 pub struct Runtime {
+    observers: Observers,
+    constraints: usize,
+    // path and first constraint of the namespaces entered and not exited
+    namespaces: Vec<(String, usize)>,
+    // path and constraints of the exited namespaces, innermost first
+    scopes: Vec<(String, Range<usize>)>,
+    names: HashMap<Witness, String>,
     #[cfg(feature = "debug")]
     debugger: Debugger,
 }
@@ -53,6 +171,11 @@ impl Runtime {
     #[allow(unused_variables)]
     pub fn new() -> Self {
         Self {
+            observers: Observers::default(),
+            constraints: 0,
+            namespaces: Vec::new(),
+            scopes: Vec::new(),
+            names: HashMap::new(),
             #[cfg(feature = "debug")]
             debugger: Debugger::new(),
         }
@@ -62,13 +185,112 @@ impl Runtime {
     #[allow(unused_variables)]
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
+            observers: Observers::default(),
+            constraints: 0,
+            namespaces: Vec::new(),
+            scopes: Vec::new(),
+            names: HashMap::new(),
             #[cfg(feature = "debug")]
             debugger: Debugger::with_capacity(capacity),
         }
     }
 
-    #[allow(unused_variables)]
+    /// Register `observer` to receive the events of the runtime from now on
+    pub fn observe(&mut self, observer: Arc<dyn Observer>) {
+        self.observers.0.push(observer);
+    }
+
+    /// Enter the gadget `name`, returning a guard that exits it when dropped
+    ///
+    /// The guard doesn't borrow the runtime, so the gadget can append its
+    /// constraints while it is held:
+    ///
+    /// ```
+    /// use dusk_plonk::prelude::*;
+    ///
+    /// fn square<C: Composer>(composer: &mut C, a: Witness) -> Witness {
+    ///     let _gadget = composer.runtime().gadget("square");
+    ///
+    ///     composer.gate_mul(Constraint::new().mult(1).a(a).b(a))
+    /// }
+    /// ```
+    pub fn gadget(&mut self, name: &'static str) -> GadgetGuard {
+        self.event(RuntimeEvent::GadgetEntered { name });
+
+        GadgetGuard {
+            observers: self.observers.clone(),
+            name,
+        }
+    }
+
+    /// Path of the namespace the constraint appended at `index` was appended
+    /// in, if any
+    pub(crate) fn constraint_namespace(&self, index: usize) -> Option<&str> {
+        self.scopes
+            .iter()
+            .map(|(path, constraints)| (path, constraints.clone()))
+            .chain(self.namespaces.iter().rev().map(|(path, start)| {
+                // an open namespace contains every constraint from its start
+                (path, *start..self.constraints)
+            }))
+            .find(|(_, constraints)| constraints.contains(&index))
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// Name of the witness `w`, qualified by the namespace it was named in
+    pub(crate) fn witness_name(&self, w: &Witness) -> Option<&str> {
+        self.names.get(w).map(String::as_str)
+    }
+
+    // qualify `name` by the path of the current namespace
+    fn qualified(&self, name: &str) -> String {
+        match self.namespaces.last() {
+            Some((path, _)) => format!("{}/{}", path, name),
+            None => String::from(name),
+        }
+    }
+
+    /// Source location of the constraint appended at `index`, if the
+    /// debugger resolved it
+    #[cfg(feature = "debug")]
+    pub(crate) fn constraint_source(&self, index: usize) -> Option<String> {
+        self.debugger.constraint_source(index)
+    }
+
+    /// Output the circuit appended to `builder` with the debugger, to the
+    /// file at `CDF_OUTPUT` if set
+    #[cfg(feature = "debug")]
+    pub(crate) fn write_debug_output(&self, builder: &Builder) {
+        self.debugger.write_output(builder);
+    }
+
     pub(crate) fn event(&mut self, event: RuntimeEvent) {
+        self.observers.0.iter().for_each(|o| o.event(&event));
+
+        match &event {
+            RuntimeEvent::WitnessNamed { w, name } => {
+                let name = self.qualified(name);
+                self.names.insert(*w, name);
+            }
+
+            RuntimeEvent::ConstraintAppended { .. } => {
+                self.constraints += 1;
+            }
+
+            RuntimeEvent::NamespaceEntered { name } => {
+                let path = self.qualified(name);
+                self.namespaces.push((path, self.constraints));
+            }
+
+            RuntimeEvent::NamespaceExited => {
+                if let Some((path, start)) = self.namespaces.pop() {
+                    self.scopes.push((path, start..self.constraints));
+                }
+            }
+
+            _ => (),
+        }
+
         #[cfg(feature = "debug")]
         self.debugger.event(event);
     }
@@ -0,0 +1,351 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! SHA-256 compression function and message padding.
+//!
+//! The native functions are the reference of the circuit gadgets
+//! [`Composer::component_sha256`] and [`Composer::component_sha256_pad`]: a
+//! message padded and compressed block by block in a circuit results in the
+//! same digest words as [`digest`].
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+use crate::composer::Composer;
+use crate::constraint_system::{Constraint, Witness};
+use crate::word;
+
+/// Initial hash value of SHA-256
+pub const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c,
+    0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1,
+    0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3,
+    0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+    0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+    0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+    0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+    0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+    0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+    0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Number of words of a message block
+pub const BLOCK_WORDS: usize = 16;
+
+// The rounds of the message schedule
+const ROUNDS: usize = 64;
+
+// The three shifts combined by each of the sigma functions
+#[derive(Debug, Clone, Copy)]
+enum Shift {
+    Rotr(usize),
+    Shr(usize),
+}
+
+const BIG_SIGMA_0: [Shift; 3] =
+    [Shift::Rotr(2), Shift::Rotr(13), Shift::Rotr(22)];
+const BIG_SIGMA_1: [Shift; 3] =
+    [Shift::Rotr(6), Shift::Rotr(11), Shift::Rotr(25)];
+const SMALL_SIGMA_0: [Shift; 3] =
+    [Shift::Rotr(7), Shift::Rotr(18), Shift::Shr(3)];
+const SMALL_SIGMA_1: [Shift; 3] =
+    [Shift::Rotr(17), Shift::Rotr(19), Shift::Shr(10)];
+
+fn sigma(x: u32, shifts: [Shift; 3]) -> u32 {
+    shifts.iter().fold(0, |acc, shift| match shift {
+        Shift::Rotr(n) => acc ^ x.rotate_right(*n as u32),
+        Shift::Shr(n) => acc ^ (x >> n),
+    })
+}
+
+/// Applies the SHA-256 compression function to `state` with a single message
+/// `block`
+pub fn compress(state: &mut [u32; 8], block: &[u32; BLOCK_WORDS]) {
+    let mut w = [0u32; ROUNDS];
+    w[..BLOCK_WORDS].copy_from_slice(block);
+    (BLOCK_WORDS..ROUNDS).for_each(|t| {
+        w[t] = sigma(w[t - 2], SMALL_SIGMA_1)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(sigma(w[t - 15], SMALL_SIGMA_0))
+            .wrapping_add(w[t - 16]);
+    });
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    (0..ROUNDS).for_each(|t| {
+        let ch = (e & f) ^ (!e & g);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+
+        let t1 = h
+            .wrapping_add(sigma(e, BIG_SIGMA_1))
+            .wrapping_add(ch)
+            .wrapping_add(K[t])
+            .wrapping_add(w[t]);
+        let t2 = sigma(a, BIG_SIGMA_0).wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    });
+
+    state
+        .iter_mut()
+        .zip([a, b, c, d, e, f, g, h])
+        .for_each(|(s, x)| *s = s.wrapping_add(x));
+}
+
+/// Pads `message` and splits it into blocks of big endian words.
+///
+/// The message is appended with a single `1` bit, the minimum amount of `0`
+/// bits and the message length in bits as a 64 bit big endian integer, so the
+/// padded message is a multiple of 512 bits.
+pub fn pad(message: &[u8]) -> Vec<[u32; BLOCK_WORDS]> {
+    let bits = (message.len() as u64) * 8;
+
+    let mut bytes = message.to_vec();
+    bytes.push(0x80);
+    while bytes.len() % 64 != 56 {
+        bytes.push(0x00);
+    }
+    bytes.extend_from_slice(&bits.to_be_bytes());
+
+    bytes
+        .chunks(64)
+        .map(|chunk| {
+            let mut block = [0u32; BLOCK_WORDS];
+            block.iter_mut().zip(chunk.chunks(4)).for_each(|(w, b)| {
+                *w = u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+            });
+            block
+        })
+        .collect()
+}
+
+/// Computes the SHA-256 digest of `message` as big endian words
+pub fn digest(message: &[u8]) -> [u32; 8] {
+    let mut state = IV;
+    pad(message)
+        .iter()
+        .for_each(|block| compress(&mut state, block));
+    state
+}
+
+// Bits of a word
+const WORD_BITS: usize = 32;
+
+// The sum of the shifts of `x`, computed from a single split of `x` at the
+// offsets of the shifts
+fn sigma_gadget<C: Composer>(
+    composer: &mut C,
+    x: Witness,
+    shifts: [Shift; 3],
+) -> Witness {
+    let mut offsets = shifts.map(|shift| match shift {
+        Shift::Rotr(n) | Shift::Shr(n) => n,
+    });
+    offsets.sort_unstable();
+
+    let limbs = word::split(composer, x, WORD_BITS, &offsets);
+    let [a, b, c] = shifts.map(|shift| match shift {
+        Shift::Rotr(n) => limbs.rotate_right(composer, n),
+        Shift::Shr(n) => limbs.shift_right(composer, n),
+    });
+
+    let a = word::xor(composer, a, b, WORD_BITS);
+    word::xor(composer, a, c, WORD_BITS)
+}
+
+// `(e & f) ^ (!e & g)` computed as `g ^ (e & (f ^ g))`
+fn ch_gadget<C: Composer>(
+    composer: &mut C,
+    e: Witness,
+    f: Witness,
+    g: Witness,
+) -> Witness {
+    let f_xor_g = word::xor(composer, f, g, WORD_BITS);
+    let e_and = word::and(composer, e, f_xor_g, WORD_BITS);
+
+    word::xor(composer, g, e_and, WORD_BITS)
+}
+
+// `(a & b) ^ (a & c) ^ (b & c)` computed as `(a & b) ^ (c & (a ^ b))`
+fn maj_gadget<C: Composer>(
+    composer: &mut C,
+    a: Witness,
+    b: Witness,
+    c: Witness,
+) -> Witness {
+    let a_xor_b = word::xor(composer, a, b, WORD_BITS);
+    let c_and = word::and(composer, c, a_xor_b, WORD_BITS);
+    let a_and_b = word::and(composer, a, b, WORD_BITS);
+
+    word::xor(composer, a_and_b, c_and, WORD_BITS)
+}
+
+// Reduces a sum of at most 16 words modulo `2^32`.
+//
+// The carry is constrained to 4 bits and the returned word to 32 bits.
+fn reduce<C: Composer>(composer: &mut C, sum: Witness) -> Witness {
+    let value = word::value(composer, sum);
+
+    let word = composer.append_witness(value & 0xffff_ffff);
+    let carry = composer.append_witness(value >> 32);
+
+    let constraint = Constraint::new()
+        .left(1)
+        .a(sum)
+        .right(-BlsScalar::pow_of_2(32))
+        .b(carry)
+        .fourth(-BlsScalar::one())
+        .d(word);
+    composer.append_gate(constraint);
+
+    composer.component_range::<2>(carry);
+    composer.component_range::<16>(word);
+
+    word
+}
+
+// The terms adding up `words`
+fn terms(words: &[Witness]) -> Vec<(BlsScalar, Witness)> {
+    words.iter().map(|w| (BlsScalar::one(), *w)).collect()
+}
+
+pub(crate) fn compress_gadget<C: Composer>(
+    composer: &mut C,
+    state: &[Witness; 8],
+    block: &[Witness; BLOCK_WORDS],
+) -> [Witness; 8] {
+    state
+        .iter()
+        .chain(block.iter())
+        .for_each(|w| composer.component_range::<16>(*w));
+
+    let mut w = block.to_vec();
+    (BLOCK_WORDS..ROUNDS).for_each(|t| {
+        let s1 = sigma_gadget(composer, w[t - 2], SMALL_SIGMA_1);
+        let s0 = sigma_gadget(composer, w[t - 15], SMALL_SIGMA_0);
+
+        let terms = terms(&[s1, w[t - 7], s0, w[t - 16]]);
+        let s = word::sum(composer, &terms, BlsScalar::zero());
+        w.push(reduce(composer, s));
+    });
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    (0..ROUNDS).for_each(|t| {
+        let s1 = sigma_gadget(composer, e, BIG_SIGMA_1);
+        let ch = ch_gadget(composer, e, f, g);
+        let s0 = sigma_gadget(composer, a, BIG_SIGMA_0);
+        let maj = maj_gadget(composer, a, b, c);
+
+        let terms_1 = terms(&[h, s1, ch, w[t]]);
+        let k = BlsScalar::from(K[t] as u64);
+        let t1 = word::sum(composer, &terms_1, k);
+
+        h = g;
+        g = f;
+        f = e;
+
+        let s = word::sum(composer, &terms(&[d, t1]), BlsScalar::zero());
+        e = reduce(composer, s);
+
+        d = c;
+        c = b;
+        b = a;
+
+        let s = word::sum(composer, &terms(&[t1, s0, maj]), BlsScalar::zero());
+        a = reduce(composer, s);
+    });
+
+    let mut digest = [C::ZERO; 8];
+    digest
+        .iter_mut()
+        .zip(state.iter().zip([a, b, c, d, e, f, g, h]))
+        .for_each(|(o, (s, x))| {
+            let s = word::sum(composer, &terms(&[*s, x]), BlsScalar::zero());
+            *o = reduce(composer, s);
+        });
+
+    digest
+}
+
+pub(crate) fn pad_gadget<C: Composer>(
+    composer: &mut C,
+    message: &[Witness],
+) -> Vec<[Witness; BLOCK_WORDS]> {
+    let bits = (message.len() as u64) * 32;
+
+    let mut words = message.to_vec();
+    words.push(composer.append_constant(0x8000_0000u64));
+    while words.len() % BLOCK_WORDS != BLOCK_WORDS - 2 {
+        words.push(C::ZERO);
+    }
+    words.push(composer.append_constant(bits >> 32));
+    words.push(composer.append_constant(bits & 0xffff_ffff));
+
+    words
+        .chunks(BLOCK_WORDS)
+        .map(|chunk| {
+            let mut block = [C::ZERO; BLOCK_WORDS];
+            block.copy_from_slice(chunk);
+            block
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn digest_test_vectors() {
+        // FIPS 180-2 appendix B
+        assert_eq!(
+            digest(b"abc"),
+            [
+                0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3,
+                0x96177a9c, 0xb410ff61, 0xf20015ad,
+            ]
+        );
+        assert_eq!(
+            digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            [
+                0x248d6a61, 0xd20638b8, 0xe5c02693, 0x0c3e6039, 0xa33ce459,
+                0x64ff2167, 0xf6ecedd4, 0x19db06c1,
+            ]
+        );
+        assert_eq!(
+            digest(b""),
+            [
+                0xe3b0c442, 0x98fc1c14, 0x9afbf4c8, 0x996fb924, 0x27ae41e4,
+                0x649b934c, 0xa495991b, 0x7852b855,
+            ]
+        );
+    }
+
+    #[test]
+    fn pad_fills_whole_blocks() {
+        assert_eq!(pad(b"").len(), 1);
+        assert_eq!(pad(&[0u8; 55]).len(), 1);
+        assert_eq!(pad(&[0u8; 56]).len(), 2);
+
+        let blocks = pad(b"abc");
+        assert_eq!(blocks[0][0], 0x61626380);
+        assert_eq!(blocks[0][15], 24);
+    }
+}
@@ -0,0 +1,342 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Schnorr and EdDSA signatures over JubJub.
+//!
+//! The message is a set of [`BlsScalar`] and the challenge of both schemes is
+//! computed with the [`hades`](crate::hades) sponge and truncated to
+//! [`CHALLENGE_BITS`], so the signatures can be verified in a circuit with
+//! [`Composer::component_verify_schnorr`] and
+//! [`Composer::component_verify_eddsa`].
+//!
+//! - Schnorr: `R = r · G`, `c = H(R, m)`, `u = r - c · sk` and the signature is
+//!   valid if `u · G + c · PK == R`.
+//! - EdDSA: `R = r · G`, `c = H(R, PK, m)`, `s = r + c · sk` and the signature
+//!   is valid if `s · G == R + c · PK`. The nonce `r` is derived from the
+//!   secret key and the message, so signing is deterministic.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::{
+    JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR_EXTENDED,
+};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::composer::Composer;
+use crate::constraint_system::{Constraint, Witness, WitnessPoint};
+use crate::hades::{Sponge, SpongeGadget};
+
+/// Number of bits the challenge hash is truncated to, so it fits a
+/// [`JubJubScalar`]
+pub const CHALLENGE_BITS: usize = 250;
+
+/// Secret key of a JubJub signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretKey(JubJubScalar);
+
+/// Public key `sk · G` of a JubJub signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(JubJubExtended);
+
+/// Signature `(R, s)` of either the Schnorr or the EdDSA scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    r: JubJubExtended,
+    s: JubJubScalar,
+}
+
+/// Signature appended to a circuit, as a point `R` and the scalar `s`
+#[derive(Debug, Clone, Copy)]
+pub struct WitnessSignature {
+    r: WitnessPoint,
+    s: Witness,
+}
+
+fn challenge(
+    r: &JubJubExtended,
+    public_key: Option<&PublicKey>,
+    message: &[BlsScalar],
+) -> JubJubScalar {
+    let r = JubJubAffine::from(r);
+
+    let mut sponge = Sponge::new();
+    sponge.absorb(&[r.get_u(), r.get_v()]);
+    if let Some(pk) = public_key {
+        let pk = JubJubAffine::from(pk.0);
+        sponge.absorb(&[pk.get_u(), pk.get_v()]);
+    }
+    sponge.absorb(message);
+
+    let mut bytes = sponge.squeeze().to_bytes();
+    bytes[CHALLENGE_BITS / 8] &= (1 << (CHALLENGE_BITS % 8)) - 1;
+    bytes[CHALLENGE_BITS / 8 + 1..].fill(0);
+
+    JubJubScalar::from_bytes(&bytes).expect("the challenge fits the scalar")
+}
+
+impl SecretKey {
+    /// Creates a secret key from a JubJub scalar
+    pub const fn new(sk: JubJubScalar) -> Self {
+        Self(sk)
+    }
+
+    /// Creates a random secret key
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(JubJubScalar::random(rng))
+    }
+
+    /// Public key of the secret key
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(GENERATOR_EXTENDED * self.0)
+    }
+
+    /// Signs `message` with the Schnorr scheme and a random nonce
+    pub fn sign_schnorr<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        message: &[BlsScalar],
+    ) -> Signature {
+        let nonce = JubJubScalar::random(rng);
+        let r = GENERATOR_EXTENDED * nonce;
+
+        let c = challenge(&r, None, message);
+        let s = nonce - c * self.0;
+
+        Signature { r, s }
+    }
+
+    /// Signs `message` with the EdDSA scheme
+    pub fn sign_eddsa(&self, message: &[BlsScalar]) -> Signature {
+        // the nonce is reduced from 512 bits of the hash of the secret key and
+        // the message, so it is not biased
+        let mut sponge = Sponge::new();
+        sponge.absorb(&[BlsScalar::from_bytes(&self.0.to_bytes())
+            .expect("a jubjub scalar fits a bls scalar")]);
+        sponge.absorb(message);
+
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(&sponge.squeeze().to_bytes());
+        wide[32..].copy_from_slice(&sponge.squeeze().to_bytes());
+        let nonce = JubJubScalar::from_bytes_wide(&wide);
+        let r = GENERATOR_EXTENDED * nonce;
+
+        let c = challenge(&r, Some(&self.public_key()), message);
+        let s = nonce + c * self.0;
+
+        Signature { r, s }
+    }
+}
+
+impl PublicKey {
+    /// Creates a public key from a JubJub point
+    pub const fn new(pk: JubJubExtended) -> Self {
+        Self(pk)
+    }
+
+    /// JubJub point of the public key
+    pub const fn point(&self) -> &JubJubExtended {
+        &self.0
+    }
+
+    /// Verifies a Schnorr `signature` of `message`
+    pub fn verify_schnorr(
+        &self,
+        signature: &Signature,
+        message: &[BlsScalar],
+    ) -> bool {
+        let c = challenge(&signature.r, None, message);
+
+        GENERATOR_EXTENDED * signature.s + self.0 * c == signature.r
+    }
+
+    /// Verifies an EdDSA `signature` of `message`
+    pub fn verify_eddsa(
+        &self,
+        signature: &Signature,
+        message: &[BlsScalar],
+    ) -> bool {
+        let c = challenge(&signature.r, Some(self), message);
+
+        GENERATOR_EXTENDED * signature.s == signature.r + self.0 * c
+    }
+}
+
+impl Signature {
+    /// Creates a signature from its point `R` and scalar `s`
+    pub const fn new(r: JubJubExtended, s: JubJubScalar) -> Self {
+        Self { r, s }
+    }
+
+    /// Point `R` of the signature
+    pub const fn r(&self) -> &JubJubExtended {
+        &self.r
+    }
+
+    /// Scalar `s` of the signature
+    pub const fn s(&self) -> &JubJubScalar {
+        &self.s
+    }
+}
+
+impl WitnessSignature {
+    /// Appends `signature` to `composer` as witnesses
+    pub fn append<C: Composer>(
+        composer: &mut C,
+        signature: &Signature,
+    ) -> Self {
+        let r = composer.append_point(signature.r);
+        let s = composer.append_witness(signature.s);
+
+        Self { r, s }
+    }
+
+    /// Point `R` of the signature
+    pub const fn r(&self) -> &WitnessPoint {
+        &self.r
+    }
+
+    /// Scalar `s` of the signature
+    pub const fn s(&self) -> &Witness {
+        &self.s
+    }
+}
+
+pub(crate) fn challenge_gadget<C: Composer>(
+    composer: &mut C,
+    r: &WitnessPoint,
+    public_key: Option<&WitnessPoint>,
+    message: &[Witness],
+) -> Witness {
+    let mut sponge = SpongeGadget::new();
+    sponge.absorb(composer, &[*r.x(), *r.y()]);
+    if let Some(pk) = public_key {
+        sponge.absorb(composer, &[*pk.x(), *pk.y()]);
+    }
+    sponge.absorb(composer, message);
+
+    let hash = sponge.squeeze(composer);
+
+    // the decomposition only holds modulo the BLS modulus `q`, and `hash + q`
+    // still fits 255 bits, so the bits are asserted to be smaller than `q`
+    // for the truncation to be unique; the most significant bits are then
+    // subtracted from the hash
+    let bits = composer.component_decomposition::<255>(hash);
+    assert_canonical(composer, &bits);
+
+    bits[CHALLENGE_BITS..]
+        .iter()
+        .enumerate()
+        .fold(hash, |acc, (i, bit)| {
+            let constraint = Constraint::new()
+                .left(-BlsScalar::pow_of_2((CHALLENGE_BITS + i) as u64))
+                .a(*bit)
+                .right(1)
+                .b(acc);
+
+            composer.gate_add(constraint)
+        })
+}
+
+/// Asserts the little endian `bits` to encode an integer smaller than the
+/// BLS modulus `q`
+///
+/// The bits are compared to `q - 1` from the most significant one, keeping
+/// track of whether they are equal to the bits of `q - 1` so far. Every bit
+/// for which `q - 1` has a zero must then be zero if the previous ones are
+/// equal.
+fn assert_canonical<C: Composer>(composer: &mut C, bits: &[Witness; 255]) {
+    let max = (-BlsScalar::one()).to_bits();
+
+    bits.iter()
+        .zip(max.iter())
+        .rev()
+        .fold(C::ONE, |equal, (bit, max)| {
+            let constraint = Constraint::new().mult(1).a(equal).b(*bit);
+
+            if *max == 1 {
+                composer.gate_mul(constraint)
+            } else {
+                composer.append_gate(constraint);
+                equal
+            }
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::composer::Builder;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sign_and_verify() {
+        let mut rng = StdRng::seed_from_u64(0x5c4);
+        let sk = SecretKey::random(&mut rng);
+        let pk = sk.public_key();
+
+        let message = [BlsScalar::from(42), BlsScalar::from(7)];
+        let other = [BlsScalar::from(42), BlsScalar::from(8)];
+
+        let signature = sk.sign_schnorr(&mut rng, &message);
+        assert!(pk.verify_schnorr(&signature, &message));
+        assert!(!pk.verify_schnorr(&signature, &other));
+        assert!(!pk.verify_eddsa(&signature, &message));
+
+        let signature = sk.sign_eddsa(&message);
+        assert_eq!(signature, sk.sign_eddsa(&message));
+        assert!(pk.verify_eddsa(&signature, &message));
+        assert!(!pk.verify_eddsa(&signature, &other));
+        assert!(!pk.verify_schnorr(&signature, &message));
+
+        let other_pk = SecretKey::random(&mut rng).public_key();
+        assert!(!other_pk.verify_eddsa(&signature, &message));
+    }
+
+    // bits of `q - 1 + n`, which fit 255 bits for a small `n`
+    fn bits_above_modulus(n: u8) -> [bool; 255] {
+        let mut bytes = (-BlsScalar::one()).to_bytes();
+        let mut carry = n as u16;
+        bytes.iter_mut().for_each(|byte| {
+            carry += *byte as u16;
+            *byte = carry as u8;
+            carry >>= 8;
+        });
+
+        let mut bits = [false; 255];
+        bits.iter_mut()
+            .enumerate()
+            .for_each(|(i, bit)| *bit = (bytes[i / 8] >> (i % 8)) & 1 == 1);
+        bits
+    }
+
+    fn is_canonical(bits: [bool; 255]) -> bool {
+        let mut builder = Builder::initialized();
+
+        let bits = bits.map(|bit| {
+            let bit = builder.append_witness(BlsScalar::from(bit as u64));
+            builder.component_boolean(bit);
+            bit
+        });
+        assert_canonical(&mut builder, &bits);
+
+        let size = builder.constraints().next_power_of_two();
+        builder.check_constraints(size).is_ok()
+    }
+
+    #[test]
+    fn canonical_bits() {
+        let mut five = [false; 255];
+        five[0] = true;
+        five[2] = true;
+
+        assert!(is_canonical(five));
+        assert!(is_canonical(bits_above_modulus(0)));
+
+        // `q` and `q + 5` decompose to the same scalars as `0` and `5`
+        assert!(!is_canonical(bits_above_modulus(1)));
+        assert!(!is_canonical(bits_above_modulus(6)));
+    }
+}
@@ -4,78 +4,86 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-//! This is an extension over the [Merlin Transcript](Transcript)
-//! which adds a few extra functionalities.
-
-use core::mem;
-
-use dusk_bls12_381::BlsScalar;
+//! Fiat-Shamir transcripts the challenges of the protocol are derived from.
+//!
+//! The [`Prover`](crate::composer::Prover) and the
+//! [`Verifier`](crate::composer::Verifier) are generic over the
+//! [`TranscriptProtocol`], and use the [`Merlin`] transcript by default. The
+//! other backends are:
+//!
+//! - [`Keccak`]: Keccak-256 chain meant to be replayed by an EVM verifier.
+//! - [`Hades`]: [`hades`](crate::hades) sponge meant to be replayed in a
+//!   circuit.
+//! - [`Blake2b`]: BLAKE2b-512 chain.
+
+use dusk_bls12_381::{BlsScalar, G1Affine};
 use dusk_bytes::Serializable;
-use merlin::Transcript;
 
 use crate::commitment_scheme::Commitment;
 use crate::proof_system::VerifierKey;
 
-/// Transcript adds an abstraction over the Merlin transcript
-/// For convenience
-pub(crate) trait TranscriptProtocol {
-    /// Append a `commitment` with the given `label`.
-    fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment);
+mod blake2b;
+mod hades;
+mod keccak;
+mod merlin;
+
+pub use self::blake2b::Blake2b;
+pub use self::hades::Hades;
+pub use self::keccak::Keccak;
+pub use self::merlin::Merlin;
+
+pub(crate) use self::keccak::{encode_point, encode_scalar, keccak256};
+
+/// Transcript of the messages exchanged by the prover and the verifier, from
+/// which the challenges of the protocol are derived.
+///
+/// The labels are part of the protocol description, so a backend is free to
+/// bind them to the transcript or not, as long as the order of the messages
+/// is.
+///
+/// A transcript is shared by the rounds of a proof, which may run on the
+/// thread pool of the prover, so it must be `Send` and `Sync`.
+pub trait TranscriptProtocol: Clone + Send + Sync {
+    /// Create a new transcript for the protocol with the given `label`
+    fn new(label: &[u8]) -> Self;
+
+    /// Append a `message` with the given `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Append a `point` with the given `label`.
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.append_message(label, &point.to_bytes())
+    }
 
     /// Append a `BlsScalar` with the given `label`.
-    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar);
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.append_message(label, &s.to_bytes())
+    }
 
     /// Compute a `label`ed challenge variable.
     fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar;
-
-    /// Append domain separator for the circuit size.
-    fn circuit_domain_sep(&mut self, n: u64);
-
-    /// Create a new instance of the base transcript of the protocol
-    fn base(
-        label: &[u8],
-        verifier_key: &VerifierKey,
-        constraints: usize,
-    ) -> Self;
 }
 
-impl TranscriptProtocol for Transcript {
+/// Operations of the protocol shared by every [`TranscriptProtocol`]
+pub(crate) trait TranscriptExt: TranscriptProtocol {
+    /// Append a `commitment` with the given `label`.
     fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment) {
-        self.append_message(label, &comm.0.to_bytes());
-    }
-
-    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
-        self.append_message(label, &s.to_bytes())
-    }
-
-    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
-        let mut buf = [0u8; 64];
-        self.challenge_bytes(label, &mut buf);
-
-        BlsScalar::from_bytes_wide(&buf)
+        self.append_point(label, &comm.0)
     }
 
+    /// Append domain separator for the circuit size.
     fn circuit_domain_sep(&mut self, n: u64) {
         self.append_message(b"dom-sep", b"circuit_size");
-        self.append_u64(b"n", n);
+        self.append_message(b"n", &n.to_le_bytes());
     }
 
+    /// Create a new instance of the base transcript of the protocol
     fn base(
         label: &[u8],
         verifier_key: &VerifierKey,
         constraints: usize,
     ) -> Self {
-        // Transcript can't be serialized/deserialized. One alternative is to
-        // fork merlin and implement these functionalities, so we can use custom
-        // transcripts for provers and verifiers. However, we don't have a use
-        // case for this feature in Dusk.
-
-        // Safety: static lifetime is a pointless requirement from merlin that
-        // doesn't add any security but instead restricts a lot the
-        // serialization and deserialization of transcripts
-        let label = unsafe { mem::transmute(label) };
-
-        let mut transcript = Transcript::new(label);
+        let mut transcript = Self::new(label);
 
         transcript.circuit_domain_sep(constraints as u64);
 
@@ -84,3 +92,5 @@ impl TranscriptProtocol for Transcript {
         transcript
     }
 }
+
+impl<T: TranscriptProtocol> TranscriptExt for T {}
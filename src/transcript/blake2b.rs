@@ -0,0 +1,201 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+use super::TranscriptProtocol;
+
+const BLOCK: usize = 128;
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Transcript over BLAKE2b-512.
+///
+/// Every message is framed as the length and the bytes of its label followed
+/// by the length and the bytes of the message, the lengths being 32 bit
+/// little endian integers. A challenge appends its label, the state is set to
+/// the digest of the previous state and the framed messages, and the
+/// challenge is the digest read as a 512 bit little endian integer, reduced
+/// modulo the order of the scalar field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blake2b {
+    state: [u8; 64],
+    buffer: Vec<u8>,
+}
+
+impl TranscriptProtocol for Blake2b {
+    fn new(label: &[u8]) -> Self {
+        let mut buffer = Vec::new();
+        frame(&mut buffer, b"dom-sep");
+        frame(&mut buffer, label);
+
+        Self {
+            state: [0u8; 64],
+            buffer,
+        }
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        frame(&mut self.buffer, label);
+        frame(&mut self.buffer, message);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        frame(&mut self.buffer, label);
+
+        let mut message = Vec::with_capacity(64 + self.buffer.len());
+        message.extend(self.state);
+        message.append(&mut self.buffer);
+
+        self.state = blake2b(&message);
+
+        BlsScalar::from_bytes_wide(&self.state)
+    }
+}
+
+fn frame(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    buffer.extend((bytes.len() as u32).to_le_bytes());
+    buffer.extend(bytes);
+}
+
+// Unkeyed BLAKE2b with a 64 byte digest
+fn blake2b(message: &[u8]) -> [u8; 64] {
+    let mut h = IV;
+    h[0] ^= 0x0101_0040;
+
+    // an empty message is a single block of zeroes
+    let blocks = message.len().max(1).div_ceil(BLOCK);
+
+    (0..blocks).for_each(|i| {
+        let start = i * BLOCK;
+        let end = message.len().min(start + BLOCK);
+
+        let mut block = [0u8; BLOCK];
+        block[..end - start].copy_from_slice(&message[start..end]);
+
+        let last = i == blocks - 1;
+        compress(&mut h, &block, end as u128, last);
+    });
+
+    let mut digest = [0u8; 64];
+    digest
+        .chunks_exact_mut(8)
+        .zip(h.iter())
+        .for_each(|(bytes, word)| bytes.copy_from_slice(&word.to_le_bytes()));
+    digest
+}
+
+fn compress(h: &mut [u64; 8], block: &[u8; BLOCK], t: u128, last: bool) {
+    let mut m = [0u64; 16];
+    m.iter_mut()
+        .zip(block.chunks_exact(8))
+        .for_each(|(word, bytes)| {
+            let mut le = [0u8; 8];
+            le.copy_from_slice(bytes);
+            *word = u64::from_le_bytes(le);
+        });
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    (0..12).for_each(|round| {
+        let s = &SIGMA[round % 10];
+
+        mix(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+        mix(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+        mix(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+        mix(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+
+        mix(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+        mix(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+        mix(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+        mix(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+    });
+
+    h.iter_mut()
+        .enumerate()
+        .for_each(|(i, h)| *h ^= v[i] ^ v[i + 8]);
+}
+
+fn mix(v: &mut [u64; 16], [a, b, c, d]: [usize; 4], x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn blake2b_vectors() {
+        assert_eq!(
+            hex(&blake2b(b"")),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+             d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        assert_eq!(
+            hex(&blake2b(b"abc")),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+
+        // messages filling exactly one and more than one block
+        let block = [0x61u8; BLOCK];
+        assert_ne!(blake2b(&block), blake2b(&block[..BLOCK - 1]));
+        assert_ne!(blake2b(&[0x61u8; BLOCK + 1]), blake2b(&block));
+    }
+
+    #[test]
+    fn challenges() {
+        let mut transcript = Blake2b::new(b"dusk-plonk");
+        transcript.append_scalar(b"s", &BlsScalar::one());
+
+        let mut other = transcript.clone();
+        let a = transcript.challenge_scalar(b"a");
+        let b = transcript.challenge_scalar(b"a");
+        assert_ne!(a, b);
+        assert_ne!(a, other.challenge_scalar(b"b"));
+    }
+}
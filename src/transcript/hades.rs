@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::hades::Sponge;
+
+use super::TranscriptProtocol;
+
+// Number of bytes of a message packed into a scalar
+const CHUNK: usize = 31;
+
+/// Transcript over the [`hades`](crate::hades) sponge, meant to be replayed
+/// by a verifier circuit.
+///
+/// Scalars are absorbed as they are. Labels and any other message are
+/// absorbed as their length followed by chunks of 31 little endian bytes, so
+/// every chunk fits a scalar. A challenge absorbs its label and is squeezed
+/// out of the sponge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hades {
+    sponge: Sponge,
+}
+
+impl Hades {
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.sponge.absorb(&[BlsScalar::from(bytes.len() as u64)]);
+
+        bytes.chunks(CHUNK).for_each(|chunk| {
+            let mut packed = [0u8; 32];
+            packed[..chunk.len()].copy_from_slice(chunk);
+
+            let packed =
+                BlsScalar::from_bytes(&packed).expect("31 bytes fit a scalar");
+            self.sponge.absorb(&[packed]);
+        });
+    }
+}
+
+impl TranscriptProtocol for Hades {
+    fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            sponge: Sponge::new(),
+        };
+        transcript.absorb_bytes(label);
+
+        transcript
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.absorb_bytes(label);
+        self.absorb_bytes(message);
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.absorb_bytes(label);
+        self.sponge.absorb(&[*s]);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        self.absorb_bytes(label);
+        self.sponge.squeeze()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn challenges() {
+        let mut transcript = Hades::new(b"dusk-plonk");
+
+        // the length of the messages is bound to the transcript
+        let mut a = transcript;
+        let mut b = transcript;
+        a.append_message(b"m", &[1]);
+        b.append_message(b"m", &[1, 0]);
+        assert_ne!(a.challenge_scalar(b"c"), b.challenge_scalar(b"c"));
+
+        transcript.append_scalar(b"s", &BlsScalar::from(42));
+        let first = transcript.challenge_scalar(b"c");
+        let second = transcript.challenge_scalar(b"c");
+        assert_ne!(first, second);
+
+        let mut sponge = Sponge::new();
+        sponge.absorb(&[BlsScalar::from(10)]);
+        sponge.absorb(&[BlsScalar::from_raw([
+            0x6f6c_702d_6b73_7564,
+            0x6b6e,
+            0,
+            0,
+        ])]);
+        sponge.absorb(&[BlsScalar::one(), BlsScalar::from(b's' as u64)]);
+        sponge.absorb(&[BlsScalar::from(42)]);
+        sponge.absorb(&[BlsScalar::one(), BlsScalar::from(b'c' as u64)]);
+        assert_eq!(first, sponge.squeeze());
+    }
+}
@@ -0,0 +1,174 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine};
+
+use crate::keccak;
+
+use super::TranscriptProtocol;
+
+/// Transcript over Keccak-256, meant to be replayed by a verifier running on
+/// the EVM.
+///
+/// The state is a single digest that is chained with every message, starting
+/// from the digest of the label of the transcript, so the base transcript of
+/// a circuit fits a constant of a contract:
+///
+/// - a message updates the state to `keccak256(state || message)`, where
+///   scalars are 32 byte big endian integers and points are encoded as the 128
+///   byte big endian `(x, y)` coordinates of EIP-2537, the identity being all
+///   zeroes;
+/// - a challenge updates the state to `h = keccak256(state || label)` and is
+///   `(h · 2^256 + keccak256(h)) mod r`, where the digests are read as big
+///   endian integers.
+///
+/// The labels of the messages are not bound to the transcript, since their
+/// order is fixed by the protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keccak {
+    state: [u8; 32],
+}
+
+impl Keccak {
+    /// Current digest of the transcript
+    pub const fn state(&self) -> &[u8; 32] {
+        &self.state
+    }
+
+    fn chain(&mut self, data: &[u8]) {
+        let mut message = Vec::with_capacity(32 + data.len());
+        message.extend(self.state);
+        message.extend(data);
+
+        self.state = keccak256(&message);
+    }
+}
+
+impl TranscriptProtocol for Keccak {
+    fn new(label: &[u8]) -> Self {
+        Self {
+            state: keccak256(label),
+        }
+    }
+
+    fn append_message(&mut self, _label: &'static [u8], message: &[u8]) {
+        self.chain(message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.append_message(label, &encode_point(point));
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        self.append_message(label, &encode_scalar(s));
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        self.chain(label);
+
+        let high = self.state;
+        let low = keccak256(&high);
+
+        // the wide integer `high || low`, as little endian bytes
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(&high);
+        wide[32..].copy_from_slice(&low);
+        wide.reverse();
+
+        BlsScalar::from_bytes_wide(&wide)
+    }
+}
+
+/// Encode a scalar as a 32 byte big endian integer
+pub(crate) fn encode_scalar(s: &BlsScalar) -> [u8; 32] {
+    let mut bytes = s.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+/// Encode a point as the 128 byte big endian `(x, y)` coordinates of EIP-2537,
+/// each padded to 64 bytes, with the identity encoded as all zeroes
+pub(crate) fn encode_point(point: &G1Affine) -> [u8; 128] {
+    let mut uncompressed = point.to_uncompressed();
+
+    // the coordinates are smaller than 2^381, so the three most significant
+    // bits only carry the flags of the encoding
+    uncompressed[0] &= 0x1f;
+
+    let mut bytes = [0u8; 128];
+    bytes[16..64].copy_from_slice(&uncompressed[..48]);
+    bytes[80..].copy_from_slice(&uncompressed[48..]);
+    bytes
+}
+
+/// Keccak-256 digest of a message
+pub(crate) fn keccak256(message: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    digest
+        .chunks_exact_mut(8)
+        .zip(keccak::digest(message).iter())
+        .for_each(|(bytes, lane)| bytes.copy_from_slice(&lane.to_le_bytes()));
+    digest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dusk_bls12_381::G1Projective;
+
+    #[test]
+    fn encodings() {
+        let s = BlsScalar::from(0x0102);
+        let bytes = encode_scalar(&s);
+        assert_eq!(&bytes[30..], &[0x01, 0x02]);
+        assert!(bytes[..30].iter().all(|b| *b == 0));
+
+        assert_eq!(encode_point(&G1Affine::identity()), [0u8; 128]);
+
+        let g = G1Affine::generator();
+        let uncompressed = g.to_uncompressed();
+        let bytes = encode_point(&g);
+        assert_eq!(&bytes[16..64], &uncompressed[..48]);
+        assert_eq!(&bytes[80..], &uncompressed[48..]);
+        assert!(bytes[..16].iter().chain(&bytes[64..80]).all(|b| *b == 0));
+
+        let p = G1Affine::from(G1Projective::generator() * BlsScalar::from(7));
+        assert_ne!(encode_point(&p), bytes);
+    }
+
+    #[test]
+    fn challenges() {
+        let mut transcript = Keccak::new(b"dusk-plonk");
+        assert_eq!(transcript.state(), &keccak256(b"dusk-plonk"));
+
+        transcript.append_scalar(b"s", &BlsScalar::one());
+        let mut message = keccak256(b"dusk-plonk").to_vec();
+        message.extend(encode_scalar(&BlsScalar::one()));
+        assert_eq!(transcript.state(), &keccak256(&message));
+
+        let mut other = transcript.clone();
+        let mut message = transcript.state().to_vec();
+        message.extend(b"a");
+        let high = keccak256(&message);
+        let low = keccak256(&high);
+        let pow_256 = BlsScalar::from(2).pow(&[256, 0, 0, 0]);
+        let be = |bytes: [u8; 32]| {
+            let mut wide = [0u8; 64];
+            wide[..32].copy_from_slice(&bytes);
+            wide[..32].reverse();
+            BlsScalar::from_bytes_wide(&wide)
+        };
+
+        let a = transcript.challenge_scalar(b"a");
+        assert_eq!(a, be(high) * pow_256 + be(low));
+        assert_eq!(transcript.state(), &high);
+
+        let b = transcript.challenge_scalar(b"a");
+        assert_ne!(a, b);
+        assert_ne!(a, other.challenge_scalar(b"b"));
+    }
+}
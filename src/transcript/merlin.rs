@@ -0,0 +1,81 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::boxed::Box;
+use core::fmt;
+
+use dusk_bls12_381::BlsScalar;
+use merlin::Transcript;
+
+use super::TranscriptProtocol;
+
+/// [Merlin](https://merlin.cool) transcript, which is the default of the
+/// protocol.
+///
+/// Merlin requires the label of the transcript to be `'static`, so the label
+/// is leaked when the transcript is created. A transcript is created once per
+/// prover and verifier, and cloned for every proof.
+#[derive(Clone)]
+pub struct Merlin {
+    transcript: Transcript,
+}
+
+impl Merlin {
+    /// Fill `dest` with bytes derived from the transcript and the `label`
+    pub fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.transcript.challenge_bytes(label, dest);
+    }
+}
+
+impl fmt::Debug for Merlin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Merlin").finish_non_exhaustive()
+    }
+}
+
+impl TranscriptProtocol for Merlin {
+    fn new(label: &[u8]) -> Self {
+        let label: &'static [u8] = Box::leak(label.into());
+
+        Self {
+            transcript: Transcript::new(label),
+        }
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.transcript.append_message(label, message);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        let mut buf = [0u8; 64];
+        self.challenge_bytes(label, &mut buf);
+
+        BlsScalar::from_bytes_wide(&buf)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merlin_compatibility() {
+        let label = b"dusk-plonk".to_vec();
+        let mut transcript = Merlin::new(&label);
+        let mut reference = Transcript::new(b"dusk-plonk");
+
+        // a label that isn't `'static` must start the same transcript
+        transcript.append_message(b"m", b"message");
+        reference.append_message(b"m", b"message");
+
+        let mut expected = [0u8; 64];
+        reference.challenge_bytes(b"scalar", &mut expected);
+        assert_eq!(
+            transcript.challenge_scalar(b"scalar"),
+            BlsScalar::from_bytes_wide(&expected)
+        );
+    }
+}
@@ -56,16 +56,6 @@ pub(crate) fn random_g2_point<R: RngCore + CryptoRng>(
     G2Affine::generator() * BlsScalar::random(rng)
 }
 
-/// This function is only used to generate the SRS.
-/// The intention is just to compute the resulting points
-/// of the operation `a*P, b*P, c*P ... (n-1)*P` into a `Vec`.
-pub(crate) fn slow_multiscalar_mul_single_base(
-    scalars: &[BlsScalar],
-    base: G1Projective,
-) -> Vec<G1Projective> {
-    scalars.iter().map(|s| base * *s).collect()
-}
-
 // while we do not have batch inversion for scalars
 use core::ops::MulAssign;
 
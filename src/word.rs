@@ -0,0 +1,197 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Gadgets over words of up to 64 bits, shared by the hash functions.
+//!
+//! The bitwise operations append the gates of the logic component with the
+//! inputs wired to its accumulators, so they also constrain their inputs to
+//! the bits of the word. The shifts and rotations split a word into range
+//! constrained limbs and recombine them.
+
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+use crate::composer::{logic_component, Composer};
+use crate::constraint_system::{Constraint, Witness};
+
+/// Value of the 64 least significant bits of the witness `w`
+pub(crate) fn value<C: Composer>(composer: &C, w: Witness) -> u64 {
+    let bytes = composer[w].to_bytes();
+
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&bytes[..8]);
+
+    u64::from_le_bytes(value)
+}
+
+/// `a ^ b` for words of `bits` bits
+pub(crate) fn xor<C: Composer>(
+    composer: &mut C,
+    a: Witness,
+    b: Witness,
+    bits: usize,
+) -> Witness {
+    logic_component(composer, a, b, bits, true, true)
+}
+
+/// `a & b` for words of `bits` bits
+pub(crate) fn and<C: Composer>(
+    composer: &mut C,
+    a: Witness,
+    b: Witness,
+    bits: usize,
+) -> Witness {
+    logic_component(composer, a, b, bits, false, true)
+}
+
+/// `!a` for a word of `bits` bits, which is left unconstrained
+pub(crate) fn not<C: Composer>(
+    composer: &mut C,
+    a: Witness,
+    bits: usize,
+) -> Witness {
+    let ones = BlsScalar::pow_of_2(bits as u64) - BlsScalar::one();
+    let constraint = Constraint::new().left(-BlsScalar::one()).a(a);
+
+    composer.gate_add(constraint.constant(ones))
+}
+
+/// `constant + Σ coefficient · witness`, without any modular reduction
+pub(crate) fn sum<C: Composer>(
+    composer: &mut C,
+    terms: &[(BlsScalar, Witness)],
+    constant: BlsScalar,
+) -> Witness {
+    let term = |i: usize| {
+        terms
+            .get(i)
+            .copied()
+            .unwrap_or((BlsScalar::zero(), C::ZERO))
+    };
+
+    let constraint = Constraint::new()
+        .left(term(0).0)
+        .a(term(0).1)
+        .right(term(1).0)
+        .b(term(1).1)
+        .fourth(term(2).0)
+        .d(term(2).1)
+        .constant(constant);
+    let acc = composer.gate_add(constraint);
+
+    let rest = terms.get(3..).unwrap_or_default();
+    rest.chunks(2).fold(acc, |acc, chunk| {
+        let (l, a) = chunk[0];
+        let (r, b) = chunk
+            .get(1)
+            .copied()
+            .unwrap_or((BlsScalar::zero(), C::ZERO));
+
+        let constraint = Constraint::new()
+            .left(l)
+            .a(a)
+            .right(r)
+            .b(b)
+            .fourth(1)
+            .d(acc);
+
+        composer.gate_add(constraint)
+    })
+}
+
+/// Limbs of a word split at increasing offsets, as returned by [`split`]
+#[derive(Debug, Clone)]
+pub(crate) struct Limbs {
+    bits: usize,
+    // offset of the least significant bit of every limb, and the limb
+    limbs: Vec<(usize, Witness)>,
+}
+
+/// Split the word `w` of `bits` bits at the increasing `offsets`
+///
+/// The limbs are range constrained and asserted to recompose `w`, so `w` is
+/// constrained to `bits` bits.
+pub(crate) fn split<C: Composer>(
+    composer: &mut C,
+    w: Witness,
+    bits: usize,
+    offsets: &[usize],
+) -> Limbs {
+    let value = value(composer, w);
+
+    let starts = [0].into_iter().chain(offsets.iter().copied());
+    let ends = offsets.iter().copied().chain([bits]);
+
+    let limbs: Vec<_> = starts
+        .zip(ends)
+        .map(|(start, end)| {
+            let width = end - start;
+            let limb = (value >> start) & (u64::MAX >> (64 - width));
+
+            let limb = composer.append_witness(limb);
+            composer.component_range_bits(limb, width);
+
+            (start, limb)
+        })
+        .collect();
+
+    let terms: Vec<_> = limbs
+        .iter()
+        .map(|(start, limb)| (BlsScalar::pow_of_2(*start as u64), *limb))
+        .collect();
+    let recomposed = sum(composer, &terms, BlsScalar::zero());
+    composer.assert_equal(recomposed, w);
+
+    Limbs { bits, limbs }
+}
+
+impl Limbs {
+    /// The word rotated right by `n` bits, an offset of the split
+    pub(crate) fn rotate_right<C: Composer>(
+        &self,
+        composer: &mut C,
+        n: usize,
+    ) -> Witness {
+        let terms: Vec<_> = self
+            .limbs
+            .iter()
+            .map(|(start, limb)| {
+                let shift = (start + self.bits - n) % self.bits;
+                (BlsScalar::pow_of_2(shift as u64), *limb)
+            })
+            .collect();
+
+        sum(composer, &terms, BlsScalar::zero())
+    }
+
+    /// The word rotated left by `n` bits, `bits - n` being an offset of the
+    /// split
+    pub(crate) fn rotate_left<C: Composer>(
+        &self,
+        composer: &mut C,
+        n: usize,
+    ) -> Witness {
+        self.rotate_right(composer, (self.bits - n) % self.bits)
+    }
+
+    /// The word shifted right by `n` bits, an offset of the split
+    pub(crate) fn shift_right<C: Composer>(
+        &self,
+        composer: &mut C,
+        n: usize,
+    ) -> Witness {
+        let terms: Vec<_> = self
+            .limbs
+            .iter()
+            .filter(|(start, _)| *start >= n)
+            .map(|(start, limb)| {
+                (BlsScalar::pow_of_2((start - n) as u64), *limb)
+            })
+            .collect();
+
+        sum(composer, &terms, BlsScalar::zero())
+    }
+}
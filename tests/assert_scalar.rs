@@ -308,3 +308,78 @@ fn assert_equal_constant() {
     let circuit = TestCircuit::new(scalar, constant, public);
     check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
 }
+
+#[test]
+fn assert_not_equal() {
+    pub struct TestCircuit {
+        scalar_a: BlsScalar,
+        scalar_b: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(scalar_a: BlsScalar, scalar_b: BlsScalar) -> Self {
+            Self { scalar_a, scalar_b }
+        }
+    }
+
+    impl Default for TestCircuit {
+        fn default() -> Self {
+            Self::new(BlsScalar::one(), BlsScalar::zero())
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_scalar_a = composer.append_witness(self.scalar_a);
+            let w_scalar_b = composer.append_witness(self.scalar_b);
+
+            composer.assert_not_equal(w_scalar_a, w_scalar_b)
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"assert_not_equal";
+    let mut rng = StdRng::seed_from_u64(0xc1adde);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test:
+    // 1 != 0
+    let msg = "Satisfied circuit verification should pass";
+    let circuit = TestCircuit::new(BlsScalar::one(), BlsScalar::zero());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // x != y
+    let msg = "Satisfied circuit verification should pass";
+    let scalar_a = BlsScalar::random(&mut rng);
+    let scalar_b = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(scalar_a, scalar_b);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // x == x
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let scalar_a = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(scalar_a, scalar_a);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 0 == 0, which the gadget reports as an error
+    let circuit = TestCircuit::new(BlsScalar::zero(), BlsScalar::zero());
+    let result = prover.prove(&mut rng, &circuit);
+    let expected = Error::UnsatisfiableGadget {
+        gadget: "assert_not_equal",
+    };
+    assert_eq!(result.unwrap_err(), expected);
+}
@@ -0,0 +1,280 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_c = composer.gate_mul(constraint);
+
+        composer.assert_equal_constant(w_c, 0, Some(self.a * self.b));
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct OtherCircuit {
+    a: BlsScalar,
+}
+
+impl OtherCircuit {
+    pub fn new(a: BlsScalar) -> Self {
+        Self { a }
+    }
+}
+
+impl Circuit for OtherCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        composer.component_range::<32>(w_a);
+
+        let constraint = Constraint::new().left(1).constant(1).a(w_a);
+        let w_b = composer.gate_add(constraint);
+        composer.assert_equal_constant(w_b, 0, Some(self.a + BlsScalar::one()));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn verify_batch_same_circuit() {
+    let mut rng = StdRng::seed_from_u64(0xba7c);
+
+    let label = b"verify_batch";
+    let pp = PublicParameters::setup(1 << 6, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let mut proofs: Vec<(Proof, Vec<BlsScalar>)> = (0..4u64)
+        .map(|i| {
+            let circuit = TestCircuit::new(
+                BlsScalar::from(i + 2),
+                BlsScalar::from(i + 3),
+            );
+            prover
+                .prove(&mut rng, &circuit)
+                .expect("Proving should pass")
+        })
+        .collect();
+
+    // Test:
+    // a batch of valid proofs and an empty batch
+    verifier
+        .verify_batch(&proofs, &mut rng)
+        .expect("Verification of a batch of valid proofs should pass");
+    verifier
+        .verify_batch(&[], &mut rng)
+        .expect("Verification of an empty batch should pass");
+
+    // Test fails:
+    // a proof with the public inputs of another proof
+    let mut batch = proofs.clone();
+    batch[2].1 = proofs[1].1.clone();
+    assert_eq!(
+        verifier.verify_batch(&batch, &mut rng),
+        Err(Error::InvalidProofInBatch { index: 2 })
+    );
+
+    // Test fails:
+    // a proof with an inconsistent number of public inputs
+    let mut batch = proofs.clone();
+    batch[3].1.push(BlsScalar::one());
+    assert_eq!(
+        verifier.verify_batch(&batch, &mut rng),
+        Err(Error::InvalidProofInBatch { index: 3 })
+    );
+
+    // Test fails:
+    // the first invalid proof is reported
+    proofs.swap(0, 1);
+    proofs[0].1 = proofs[1].1.clone();
+    assert_eq!(
+        verifier.verify_batch(&proofs, &mut rng),
+        Err(Error::InvalidProofInBatch { index: 0 })
+    );
+}
+
+#[test]
+fn verify_batch_different_circuits() {
+    let mut rng = StdRng::seed_from_u64(0xba7d);
+
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"test circuit")
+            .expect("Circuit should compile");
+    let (other_prover, other_verifier) =
+        Compiler::compile::<OtherCircuit>(&pp, b"other circuit")
+            .expect("Circuit should compile");
+
+    let circuit = TestCircuit::new(BlsScalar::from(2), BlsScalar::from(3));
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should pass");
+    let circuit = OtherCircuit::new(BlsScalar::from(42));
+    let (other_proof, other_pi) = other_prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should pass");
+
+    // Test:
+    // a batch of valid proofs of different circuits
+    let batch = [
+        (&verifier, &proof, pi.as_slice()),
+        (&other_verifier, &other_proof, other_pi.as_slice()),
+        (&verifier, &proof, pi.as_slice()),
+    ];
+    verify_batch(&batch, &mut rng)
+        .expect("Verification of a batch of valid proofs should pass");
+
+    // Test fails:
+    // a proof verified against the verifier of another circuit
+    let batch = [
+        (&verifier, &proof, pi.as_slice()),
+        (&verifier, &other_proof, other_pi.as_slice()),
+    ];
+    assert_eq!(
+        verify_batch(&batch, &mut rng),
+        Err(Error::InvalidProofInBatch { index: 1 })
+    );
+
+    // Test fails:
+    // a verifier compiled from different public parameters
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (_, foreign_verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"test circuit")
+            .expect("Circuit should compile");
+    let batch = [
+        (&verifier, &proof, pi.as_slice()),
+        (&foreign_verifier, &proof, pi.as_slice()),
+    ];
+    assert_eq!(
+        verify_batch(&batch, &mut rng),
+        Err(Error::InconsistentOpeningKeys)
+    );
+}
+
+#[test]
+fn verify_deferred() {
+    let mut rng = StdRng::seed_from_u64(0xba7e);
+
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"test circuit")
+            .expect("Circuit should compile");
+    let (other_prover, other_verifier) =
+        Compiler::compile::<OtherCircuit>(&pp, b"other circuit")
+            .expect("Circuit should compile");
+
+    let circuit = TestCircuit::new(BlsScalar::from(2), BlsScalar::from(3));
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should pass");
+    let circuit = OtherCircuit::new(BlsScalar::from(42));
+    let (other_proof, other_pi) = other_prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should pass");
+
+    let accumulator = verifier
+        .verify_deferred(&proof, &pi)
+        .expect("Deferred verification should pass");
+    let other_accumulator = other_verifier
+        .verify_deferred(&other_proof, &other_pi)
+        .expect("Deferred verification should pass");
+
+    // Test:
+    // the accumulator of a valid proof and the serialization roundtrip
+    accumulator
+        .finalize(&verifier)
+        .expect("Finalization of a valid accumulator should pass");
+    let bytes = accumulator.to_bytes();
+    assert_eq!(Accumulator::from_bytes(&bytes), Ok(accumulator));
+
+    // Test:
+    // the folded accumulators of valid proofs of different circuits
+    let folded = accumulator
+        .fold(&other_accumulator)
+        .expect("Folding accumulators of the same opening key should pass");
+    folded
+        .finalize(&other_verifier)
+        .expect("Finalization of a valid accumulator should pass");
+
+    // Test fails:
+    // the accumulator of a proof with wrong public inputs
+    let wrong_pi = [pi[0] + BlsScalar::one()];
+    let wrong_accumulator = verifier
+        .verify_deferred(&proof, &wrong_pi)
+        .expect("Deferred verification doesn't check the pairing");
+    assert_eq!(
+        wrong_accumulator.finalize(&verifier),
+        Err(Error::ProofVerificationError)
+    );
+
+    // Test fails:
+    // an invalid accumulator folded with valid ones
+    let folded = folded
+        .fold(&wrong_accumulator)
+        .expect("Folding accumulators of the same opening key should pass");
+    assert_eq!(
+        folded.finalize(&verifier),
+        Err(Error::ProofVerificationError)
+    );
+
+    // Test fails:
+    // an accumulator of a verifier compiled from different public parameters
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (foreign_prover, foreign_verifier) =
+        Compiler::compile::<TestCircuit>(&pp, b"test circuit")
+            .expect("Circuit should compile");
+    let circuit = TestCircuit::new(BlsScalar::from(2), BlsScalar::from(3));
+    let (foreign_proof, foreign_pi) = foreign_prover
+        .prove(&mut rng, &circuit)
+        .expect("Proving should pass");
+    let foreign_accumulator = foreign_verifier
+        .verify_deferred(&foreign_proof, &foreign_pi)
+        .expect("Deferred verification should pass");
+
+    assert_eq!(
+        accumulator.fold(&foreign_accumulator),
+        Err(Error::InconsistentOpeningKeys)
+    );
+    assert_eq!(
+        accumulator.finalize(&foreign_verifier),
+        Err(Error::InconsistentOpeningKeys)
+    );
+    foreign_accumulator
+        .finalize(&foreign_verifier)
+        .expect("Finalization of a valid accumulator should pass");
+}
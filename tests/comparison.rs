@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+// an odd width to make sure the comparison isn't restricted to bit pairs
+const BITS: usize = 5;
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+    result: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: u64, b: u64, c: u64, result: bool) -> Self {
+        Self {
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+            result: (result as u64).into(),
+        }
+    }
+}
+
+#[test]
+fn less_than() {
+    #[derive(Default)]
+    pub struct LessThan(TestCircuit);
+
+    impl Circuit for LessThan {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.0.a);
+            let w_b = composer.append_witness(self.0.b);
+
+            let result = composer.component_less_than::<BITS>(w_a, w_b);
+            composer.assert_equal_constant(result, 0, Some(self.0.result));
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_less_than";
+    let mut rng = StdRng::seed_from_u64(0x1e55);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<LessThan>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test default works:
+    // 0 < 0 is false
+    let msg = "Default circuit verification should pass";
+    let circuit = LessThan::default();
+    let pi = vec![BlsScalar::zero()];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // the comparison over the whole range of the width
+    let msg = "Verification of a satisfied circuit should pass";
+    for (a, b) in [(3, 7), (7, 3), (7, 7), (0, 31), (31, 0), (30, 31)] {
+        let result = a < b;
+        let pi = vec![BlsScalar::from(result as u64)];
+        let circuit = LessThan(TestCircuit::new(a, b, 0, result));
+        check_satisfied_circuit(
+            &prover, &verifier, &pi, &circuit, &mut rng, &msg,
+        );
+    }
+
+    // Test fails:
+    // 7 < 3 is false
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessThan(TestCircuit::new(7, 3, 0, true));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 3 < 7 is true
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessThan(TestCircuit::new(3, 7, 0, false));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // q - 1 doesn't fit the width, even though `q - 1 - 0 + 2^BITS` wraps
+    // around the modulus to a value with the most significant bit unset
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessThan(TestCircuit {
+        a: -BlsScalar::one(),
+        result: BlsScalar::one(),
+        ..Default::default()
+    });
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 32 doesn't fit the width
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessThan(TestCircuit::new(3, 32, 0, true));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn less_or_equal() {
+    #[derive(Default)]
+    pub struct LessOrEqual(TestCircuit);
+
+    impl Circuit for LessOrEqual {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.0.a);
+            let w_b = composer.append_witness(self.0.b);
+
+            let result = composer.component_less_or_equal::<BITS>(w_a, w_b);
+            composer.assert_equal_constant(result, 0, Some(self.0.result));
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_less_or_equal";
+    let mut rng = StdRng::seed_from_u64(0x1e55);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<LessOrEqual>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // the comparison over the whole range of the width
+    let msg = "Verification of a satisfied circuit should pass";
+    for (a, b) in [(0, 0), (3, 7), (7, 3), (7, 7), (0, 31), (31, 0), (31, 31)] {
+        let result = a <= b;
+        let pi = vec![BlsScalar::from(result as u64)];
+        let circuit = LessOrEqual(TestCircuit::new(a, b, 0, result));
+        check_satisfied_circuit(
+            &prover, &verifier, &pi, &circuit, &mut rng, &msg,
+        );
+    }
+
+    // Test fails:
+    // 7 <= 7 is true
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessOrEqual(TestCircuit::new(7, 7, 0, false));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // q - 1 doesn't fit the width
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LessOrEqual(TestCircuit {
+        b: -BlsScalar::one(),
+        result: BlsScalar::zero(),
+        ..Default::default()
+    });
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn in_range() {
+    #[derive(Default)]
+    pub struct InRange(TestCircuit);
+
+    impl Circuit for InRange {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_x = composer.append_witness(self.0.a);
+            let w_lower = composer.append_witness(self.0.b);
+            let w_upper = composer.append_witness(self.0.c);
+
+            let result =
+                composer.component_in_range::<BITS>(w_x, w_lower, w_upper);
+            composer.assert_equal_constant(result, 0, Some(self.0.result));
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_in_range";
+    let mut rng = StdRng::seed_from_u64(0x1e55);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<InRange>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // values inside, outside and at the bounds of the range
+    let msg = "Verification of a satisfied circuit should pass";
+    for (x, lower, upper) in [
+        (18, 18, 30),
+        (30, 18, 30),
+        (21, 18, 30),
+        (17, 18, 30),
+        (31, 18, 30),
+    ] {
+        let result = lower <= x && x <= upper;
+        let pi = vec![BlsScalar::from(result as u64)];
+        let circuit = InRange(TestCircuit::new(x, lower, upper, result));
+        check_satisfied_circuit(
+            &prover, &verifier, &pi, &circuit, &mut rng, &msg,
+        );
+    }
+
+    // Test fails:
+    // 31 is above the range
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = InRange(TestCircuit::new(31, 18, 30, true));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 17 is below the range
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = InRange(TestCircuit::new(17, 18, 30, true));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the bounds don't fit the width
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = InRange(TestCircuit::new(21, 18, 32, true));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // q - 1 doesn't fit the width
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = InRange(TestCircuit {
+        a: -BlsScalar::one(),
+        c: BlsScalar::from(30),
+        result: BlsScalar::one(),
+        ..Default::default()
+    });
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+// Multiplication of three witnesses: `a · b · d = c`
+struct Mul3;
+
+impl CustomGate for Mul3 {
+    const SELECTOR: &'static [u8] = b"q_mul3";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b * w.d - w.c
+    }
+}
+
+// Squaring into the next row: `a² = a_next`
+struct SquareNext;
+
+impl CustomGate for SquareNext {
+    const SELECTOR: &'static [u8] = b"q_square_next";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a.square() - w.a_next
+    }
+}
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    d: BlsScalar,
+    c: BlsScalar,
+    square: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(
+        a: BlsScalar,
+        b: BlsScalar,
+        d: BlsScalar,
+        c: BlsScalar,
+        square: BlsScalar,
+    ) -> Self {
+        Self { a, b, d, c, square }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_d = composer.append_witness(self.d);
+        let w_c = composer.append_witness(self.c);
+        let w_square = composer.append_witness(self.square);
+
+        let constraint = Constraint::new().a(w_a).b(w_b).o(w_c).d(w_d);
+        composer.append_widget::<Mul3>(constraint)?;
+
+        // the square of `a` is wired to the next row
+        composer.append_widget::<SquareNext>(Constraint::new().a(w_a))?;
+        composer.append_custom_gate(Constraint::new().a(w_square));
+
+        composer.assert_equal_constant(w_c, 0, Some(self.c));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_gate() {
+    let mut rng = StdRng::seed_from_u64(0xc057);
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"custom_gate";
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let a = BlsScalar::from(3);
+    let b = BlsScalar::from(5);
+    let d = BlsScalar::from(7);
+    let c = a * b * d;
+
+    // Test:
+    // both gates are satisfied
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(a, b, d, c, a.square());
+    let pi = vec![c];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the product of the three witnesses is wrong
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, d, c + b, a.square());
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the square in the next row is wrong
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, d, c, a.square() + a);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the proof doesn't verify against a circuit without the custom gates
+    #[derive(Default)]
+    struct Plain;
+
+    impl Circuit for Plain {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::zero());
+            let w_b = composer.append_witness(BlsScalar::zero());
+            let w_d = composer.append_witness(BlsScalar::zero());
+            let w_c = composer.append_witness(BlsScalar::zero());
+            let w_square = composer.append_witness(BlsScalar::zero());
+
+            let constraint = Constraint::new().a(w_a).b(w_b).o(w_c).d(w_d);
+            composer.append_custom_gate(constraint);
+            composer.append_custom_gate(Constraint::new().a(w_a));
+            composer.append_custom_gate(Constraint::new().a(w_square));
+
+            composer.assert_equal_constant(w_c, 0, Some(BlsScalar::zero()));
+
+            Ok(())
+        }
+    }
+
+    let (_, plain_verifier) =
+        Compiler::compile::<Plain>(&pp, label).expect("Circuit should compile");
+    let circuit = TestCircuit::new(a, b, d, c, a.square());
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    plain_verifier
+        .verify(&proof, &pi)
+        .expect_err("The proof shouldn't verify without the custom gates");
+}
+
+#[test]
+fn custom_gate_serialization() {
+    let mut rng = StdRng::seed_from_u64(0xc058);
+
+    let label = b"custom_gate";
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let gates = [
+        CustomWidget::new::<Mul3>(),
+        CustomWidget::new::<SquareNext>(),
+    ];
+
+    // the gates must be registered to deserialize the keys
+    assert_eq!(
+        Prover::try_from_bytes(prover.to_bytes()).err(),
+        Some(Error::UnregisteredCustomGate)
+    );
+    assert_eq!(
+        Verifier::try_from_bytes_with_gates(verifier.to_bytes(), &gates[..1])
+            .err(),
+        Some(Error::UnregisteredCustomGate)
+    );
+
+    let a = BlsScalar::from(3);
+    let b = BlsScalar::from(5);
+    let d = BlsScalar::from(7);
+    let c = a * b * d;
+    let circuit = TestCircuit::new(a, b, d, c, a.square());
+
+    let prover_bytes = prover.to_bytes();
+    let verifier_bytes = verifier.to_bytes();
+    assert_eq!(prover_bytes.len(), prover.serialized_size());
+    assert_eq!(verifier_bytes.len(), verifier.serialized_size());
+
+    let prover = Prover::try_from_bytes_with_gates(prover_bytes, &gates)
+        .expect("The prover should deserialize");
+    let verifier = Verifier::try_from_bytes_with_gates(verifier_bytes, &gates)
+        .expect("The verifier should deserialize");
+
+    let msg = "Verification of a satisfied circuit should pass";
+    let pi = vec![c];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // the compressed circuit keeps the rows of the gates
+    let compressed = Compiler::compress::<TestCircuit>()
+        .expect("The circuit should compress");
+
+    assert_eq!(
+        Compiler::decompress(&pp, label, &compressed).err(),
+        Some(Error::UnregisteredCustomGate)
+    );
+
+    let (decompressed_prover, decompressed_verifier) =
+        Compiler::decompress_with_gates(&pp, label, &compressed, &gates)
+            .expect("The circuit should decompress");
+
+    assert_eq!(decompressed_prover.to_bytes(), prover.to_bytes());
+    assert_eq!(decompressed_verifier.to_bytes(), verifier.to_bytes());
+
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, d, c, a.square() + a);
+    check_unsatisfied_circuit(&decompressed_prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn custom_gate_duplicate_selector() {
+    // Same selector as `Mul3`, with another constraint
+    struct Mul3Clash;
+
+    impl CustomGate for Mul3Clash {
+        const SELECTOR: &'static [u8] = b"q_mul3";
+
+        fn constraint(w: &GateWires) -> BlsScalar {
+            w.a * w.b * w.d + w.c
+        }
+    }
+
+    // Selector of a built-in gate
+    struct Range;
+
+    impl CustomGate for Range {
+        const SELECTOR: &'static [u8] = b"q_range";
+
+        fn constraint(w: &GateWires) -> BlsScalar {
+            w.a - w.b
+        }
+    }
+
+    struct ClashCircuit<G> {
+        gate: core::marker::PhantomData<G>,
+    }
+
+    impl<G> Default for ClashCircuit<G> {
+        fn default() -> Self {
+            Self {
+                gate: core::marker::PhantomData,
+            }
+        }
+    }
+
+    impl<G: CustomGate> Circuit for ClashCircuit<G> {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_one = composer.append_witness(BlsScalar::one());
+            let constraint =
+                Constraint::new().a(w_one).b(w_one).o(w_one).d(w_one);
+
+            // appending the same gate twice is fine
+            composer.append_widget::<Mul3>(constraint)?;
+            composer.append_widget::<Mul3>(constraint)?;
+            composer.append_widget::<G>(constraint)?;
+
+            Ok(())
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0xc059);
+
+    let label = b"custom_gate";
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    Compiler::compile::<ClashCircuit<SquareNext>>(&pp, label)
+        .expect("Circuit should compile");
+
+    assert_eq!(
+        Compiler::compile::<ClashCircuit<Mul3Clash>>(&pp, label).err(),
+        Some(Error::DuplicateCustomGateSelector {
+            selector: b"q_mul3"
+        })
+    );
+    assert_eq!(
+        Compiler::compile::<ClashCircuit<Range>>(&pp, label).err(),
+        Some(Error::DuplicateCustomGateSelector {
+            selector: b"q_range"
+        })
+    );
+}
@@ -4,11 +4,16 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::sync::Mutex;
 use std::{env, io};
 
 use dusk_cdf::CircuitDescription;
+use dusk_jubjub::GENERATOR_EXTENDED;
 use dusk_plonk::prelude::*;
 
+// `CDF_OUTPUT` is shared by the tests of the process
+static CDF_OUTPUT: Mutex<()> = Mutex::new(());
+
 #[derive(Debug, Default)]
 struct EmptyCircuit;
 
@@ -35,6 +40,7 @@ fn generate_cdf_works() -> io::Result<()> {
     let (prover, _verifier) = Compiler::compile::<EmptyCircuit>(&pp, label)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
+    let _lock = CDF_OUTPUT.lock().unwrap_or_else(|e| e.into_inner());
     env::set_var("CDF_OUTPUT", &path);
 
     prover
@@ -45,3 +51,91 @@ fn generate_cdf_works() -> io::Result<()> {
 
     Ok(())
 }
+
+const XOR_TABLE_ID: u64 = 1;
+
+// `a · b = c`, as a custom gate
+struct Mul;
+
+impl CustomGate for Mul {
+    const SELECTOR: &'static [u8] = b"q_mul";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b - w.c
+    }
+}
+
+// Circuit enabling the gates the arithmetic equation alone can't evaluate,
+// with an unsatisfied custom gate on its last row
+#[derive(Debug, Default)]
+struct GatesCircuit {
+    c: BlsScalar,
+}
+
+impl Circuit for GatesCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let xor_table: Vec<_> = (0..4u64)
+            .flat_map(|a| (0..4u64).map(move |b| [a, b, a ^ b]))
+            .map(|row| row.map(BlsScalar::from))
+            .collect();
+        composer.append_lookup_table(XOR_TABLE_ID, &xor_table);
+
+        let w_a = composer.append_witness(BlsScalar::from(2));
+        let w_b = composer.append_witness(BlsScalar::from(3));
+        let w_c = composer.append_witness(self.c);
+
+        composer.component_range::<2>(w_a);
+        let w_xor = composer.append_logic_xor::<2>(w_a, w_b);
+        composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_xor);
+
+        let w_point = composer.append_point(GENERATOR_EXTENDED);
+        composer.component_add_point(w_point, w_point);
+
+        composer
+            .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_c))?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn cdf_evaluates_every_gate() -> io::Result<()> {
+    let rng = &mut rand::thread_rng();
+
+    let dir = tempdir::TempDir::new("plonk-cdf")?;
+    let path = dir.path().canonicalize()?.join("gates.cdf");
+
+    let label = b"cdf-gates";
+    let pp = PublicParameters::setup(1 << 6, rng).map_err(io::Error::other)?;
+
+    let (prover, _verifier) = Compiler::compile::<GatesCircuit>(&pp, label)
+        .map_err(io::Error::other)?;
+
+    let _lock = CDF_OUTPUT.lock().unwrap_or_else(|e| e.into_inner());
+    env::set_var("CDF_OUTPUT", &path);
+
+    // the output is written before the proof fails
+    let circuit = GatesCircuit {
+        c: BlsScalar::from(7),
+    };
+    prover
+        .prove(rng, &circuit)
+        .expect_err("Proof creation of an unsatisfied circuit should fail");
+
+    let mut cdf = path.canonicalize().and_then(CircuitDescription::open)?;
+    let constraints = cdf.preamble().constraints;
+    assert_eq!(constraints, circuit.size::<Builder>());
+
+    let mut failing = vec![];
+    for idx in 0..constraints {
+        if !cdf.fetch_constraint(idx)?.polynomial().is_ok() {
+            failing.push(idx);
+        }
+    }
+    assert_eq!(failing, vec![constraints - 1]);
+
+    Ok(())
+}
@@ -161,3 +161,108 @@ fn component_decomposition() {
     let circuit = TestCircuit::new(a, decomp_expected);
     check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
 }
+
+#[test]
+fn component_decomposition_limbs() {
+    pub struct TestCircuit<const N: usize> {
+        limb_bits: usize,
+        a: BlsScalar,
+        decomp_expected: [BlsScalar; N],
+    }
+
+    impl<const N: usize> TestCircuit<N> {
+        pub fn new(
+            limb_bits: usize,
+            a: BlsScalar,
+            decomp_expected: [BlsScalar; N],
+        ) -> Self {
+            Self {
+                limb_bits,
+                a,
+                decomp_expected,
+            }
+        }
+    }
+
+    impl<const N: usize> Default for TestCircuit<N> {
+        fn default() -> Self {
+            Self::new(8, BlsScalar::zero(), [BlsScalar::zero(); N])
+        }
+    }
+
+    impl<const N: usize> Circuit for TestCircuit<N> {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let decomp_circuit: [Witness; N] =
+                composer.component_decomposition_limbs(w_a, self.limb_bits);
+
+            decomp_circuit.iter().zip(self.decomp_expected).for_each(
+                |(limb_circuit, limb_expected)| {
+                    let w_limb_expected =
+                        composer.append_witness(limb_expected);
+                    composer.assert_equal(*limb_circuit, w_limb_expected);
+                },
+            );
+
+            Ok(())
+        }
+    }
+
+    let label = b"component_decomposition_limbs";
+    let mut rng = StdRng::seed_from_u64(0x1ea);
+    let capacity = 1 << 8;
+    let pi = vec![];
+
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    // Test 8 bit limbs
+    //
+    // Compile new circuit descriptions for the prover and verifier
+    const N: usize = 4;
+    let (prover, verifier) = Compiler::compile::<TestCircuit<N>>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // 0xdeadbeef = [0xef, 0xbe, 0xad, 0xde]
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::from(0xdeadbeef);
+    let decomp_expected = [0xef, 0xbe, 0xad, 0xde].map(BlsScalar::from);
+    let circuit = TestCircuit::new(8, a, decomp_expected);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 2^32 doesn't fit 4 limbs of 8 bits
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let a = BlsScalar::pow_of_2(32);
+    let circuit = TestCircuit::new(8, a, [BlsScalar::zero(); N]);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test 5 bit limbs
+    //
+    // Compile new circuit descriptions for the prover and verifier
+    const M: usize = 3;
+    let circuit =
+        TestCircuit::<M>::new(5, BlsScalar::zero(), [BlsScalar::zero(); M]);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+
+    // Test:
+    // 0b11111_00001_10000 = [0b10000, 0b00001, 0b11111]
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::from(0b11111_00001_10000);
+    let decomp_expected = [0b10000, 0b00001, 0b11111].map(BlsScalar::from);
+    let circuit = TestCircuit::new(5, a, decomp_expected);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the limbs are in little endian
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let decomp_expected = [0b11111, 0b00001, 0b10000].map(BlsScalar::from);
+    let circuit = TestCircuit::new(5, a, decomp_expected);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,179 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::hades::{self, Sponge, SpongeGadget, WIDTH};
+use dusk_plonk::prelude::*;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+#[test]
+fn hades_permutation() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        state: [BlsScalar; WIDTH],
+        result: [BlsScalar; WIDTH],
+    }
+
+    impl TestCircuit {
+        pub fn new(
+            state: [BlsScalar; WIDTH],
+            result: [BlsScalar; WIDTH],
+        ) -> Self {
+            Self { state, result }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let mut state = [C::ZERO; WIDTH];
+            state
+                .iter_mut()
+                .zip(self.state.iter())
+                .for_each(|(w, s)| *w = composer.append_witness(*s));
+
+            let permuted = composer.component_hades_permutation(&state);
+
+            permuted.iter().zip(self.result.iter()).for_each(|(w, r)| {
+                let w_result = composer.append_witness(*r);
+                composer.assert_equal(*w, w_result);
+            });
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_hades_permutation";
+    let mut rng = StdRng::seed_from_u64(0x4ade5);
+    let capacity = 1 << 12;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test default works:
+    let msg = "Default circuit verification should fail";
+    let circuit = TestCircuit::default();
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test:
+    // permutation of zeroes
+    let msg = "Verification of a satisfied circuit should pass";
+    let state = [BlsScalar::zero(); WIDTH];
+    let mut result = state;
+    hades::permutation(&mut result);
+    let circuit = TestCircuit::new(state, result);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // permutation of a random state
+    let msg = "Verification of a satisfied circuit should pass";
+    let mut state = [BlsScalar::zero(); WIDTH];
+    state
+        .iter_mut()
+        .for_each(|s| *s = BlsScalar::random(&mut rng));
+    let mut result = state;
+    hades::permutation(&mut result);
+    let circuit = TestCircuit::new(state, result);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // permutation of a different state
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let mut wrong_state = state;
+    wrong_state[WIDTH - 1] += BlsScalar::one();
+    let circuit = TestCircuit::new(wrong_state, result);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn hades_sponge() {
+    const INPUTS: usize = 6;
+    const OUTPUTS: usize = 2;
+
+    #[derive(Default)]
+    pub struct TestCircuit {
+        input: [BlsScalar; INPUTS],
+        output: [BlsScalar; OUTPUTS],
+    }
+
+    impl TestCircuit {
+        pub fn new(
+            input: [BlsScalar; INPUTS],
+            output: [BlsScalar; OUTPUTS],
+        ) -> Self {
+            Self { input, output }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let mut input = [C::ZERO; INPUTS];
+            input
+                .iter_mut()
+                .zip(self.input.iter())
+                .for_each(|(w, s)| *w = composer.append_witness(*s));
+
+            let mut sponge = SpongeGadget::new();
+            sponge.absorb(composer, &input);
+
+            self.output.iter().for_each(|o| {
+                let w_squeezed = sponge.squeeze(composer);
+                let w_output = composer.append_public(*o);
+                composer.assert_equal(w_squeezed, w_output);
+            });
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"hades_sponge";
+    let mut rng = StdRng::seed_from_u64(0x4ade5);
+    let capacity = 1 << 12;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // squeeze the random input with the native sponge
+    let msg = "Verification of a satisfied circuit should pass";
+    let mut input = [BlsScalar::zero(); INPUTS];
+    input
+        .iter_mut()
+        .for_each(|s| *s = BlsScalar::random(&mut rng));
+
+    let mut sponge = Sponge::new();
+    sponge.absorb(&input);
+    let mut output = [BlsScalar::zero(); OUTPUTS];
+    output.iter_mut().for_each(|o| *o = sponge.squeeze());
+
+    let pi = output.to_vec();
+    let circuit = TestCircuit::new(input, output);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the squeezed elements are in a different order
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(input, [output[1], output[0]]);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
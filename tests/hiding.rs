@@ -0,0 +1,221 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+const XOR_TABLE_ID: u64 = 1;
+
+// xor of every pair of 2-bit values
+fn xor_table() -> Vec<[BlsScalar; 3]> {
+    (0..4u64)
+        .flat_map(|a| (0..4u64).map(move |b| (a, b)))
+        .map(|(a, b)| [a.into(), b.into(), (a ^ b).into()])
+        .collect()
+}
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    c: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: u64, b: u64, c: u64) -> Self {
+        Self {
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+        }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_c = composer.append_witness(self.c);
+
+        composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_c);
+        composer.component_range::<2>(w_c);
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_ab = composer.gate_mul(constraint);
+        composer.assert_equal_constant(w_ab, 0, Some(self.a * self.b));
+
+        Ok(())
+    }
+}
+
+// A circuit whose wires are mostly constant, so are its unblinded
+// polynomials
+#[derive(Default)]
+pub struct SparseCircuit;
+
+impl Circuit for SparseCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        composer.append_public(BlsScalar::zero());
+
+        Ok(())
+    }
+}
+
+#[test]
+fn non_hiding() {
+    let label = b"non_hiding";
+    let mut rng = StdRng::seed_from_u64(0x41d);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let (fast_prover, fast_verifier) =
+        Compiler::compile_non_hiding::<TestCircuit>(&pp, label)
+            .expect("Circuit should compile");
+
+    // the mode is recorded in the proving key only
+    assert!(prover.is_hiding());
+    assert!(!fast_prover.is_hiding());
+    assert_eq!(verifier.to_bytes(), fast_verifier.to_bytes());
+    assert_ne!(prover.to_bytes(), fast_prover.to_bytes());
+
+    let fast_prover = Prover::try_from_bytes(fast_prover.to_bytes())
+        .expect("Prover should deserialize");
+    assert!(!fast_prover.is_hiding());
+
+    // the proofs of both modes are verified by the same verifier
+    let circuit = TestCircuit::new(2, 3, 1);
+    let pi = vec![BlsScalar::from(6)];
+
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+    check_satisfied_circuit(
+        &fast_prover,
+        &verifier,
+        &pi,
+        &circuit,
+        &mut rng,
+        msg,
+    );
+
+    // without blinding, the proofs of a circuit don't depend on the rng
+    let (proof, _) = fast_prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    let (other, _) = fast_prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    assert_eq!(proof.to_var_bytes(), other.to_var_bytes());
+
+    let (proof, _) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    let (other, _) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    assert_ne!(proof.to_var_bytes(), other.to_var_bytes());
+
+    // unsatisfied circuits are still rejected
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(2, 3, 2);
+    check_unsatisfied_circuit(&fast_prover, &circuit, &mut rng, msg);
+
+    // a proof of other public inputs is rejected
+    let circuit = TestCircuit::new(1, 3, 2);
+    let (proof, _) = fast_prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect_err("Verification with wrong public inputs should fail");
+}
+
+#[test]
+fn non_hiding_constant_polynomials() {
+    let label = b"non_hiding_constant";
+    let mut rng = StdRng::seed_from_u64(0x41e);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let (prover, verifier) =
+        Compiler::compile_non_hiding::<SparseCircuit>(&pp, label)
+            .expect("Circuit should compile");
+
+    let pi = vec![BlsScalar::zero()];
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(
+        &prover,
+        &verifier,
+        &pi,
+        &SparseCircuit,
+        &mut rng,
+        msg,
+    );
+}
+
+#[test]
+fn non_hiding_with_circuit_and_compressed() {
+    let label = b"non_hiding_compressed";
+    let mut rng = StdRng::seed_from_u64(0x41f);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let circuit = TestCircuit::new(2, 3, 1);
+    let pi = vec![BlsScalar::from(6)];
+
+    let (prover, verifier) =
+        Compiler::compile_non_hiding::<TestCircuit>(&pp, label)
+            .expect("Circuit should compile");
+    let (circuit_prover, circuit_verifier) =
+        Compiler::compile_with_circuit_non_hiding(&pp, label, &circuit)
+            .expect("Circuit should compile");
+
+    // the compressed circuit records the mode
+    let compressed = Compiler::compress_non_hiding::<TestCircuit>()
+        .expect("Circuit should compress");
+    let (decompressed_prover, decompressed_verifier) =
+        Compiler::decompress(&pp, label, &compressed)
+            .expect("Circuit should decompress");
+
+    let compressed =
+        Compiler::compress::<TestCircuit>().expect("Circuit should compress");
+    let (hiding_prover, _) = Compiler::decompress(&pp, label, &compressed)
+        .expect("Circuit should decompress");
+
+    assert!(!circuit_prover.is_hiding());
+    assert!(!decompressed_prover.is_hiding());
+    assert!(hiding_prover.is_hiding());
+    assert_eq!(prover.to_bytes(), circuit_prover.to_bytes());
+    assert_eq!(prover.to_bytes(), decompressed_prover.to_bytes());
+    assert_eq!(verifier.to_bytes(), circuit_verifier.to_bytes());
+    assert_eq!(verifier.to_bytes(), decompressed_verifier.to_bytes());
+
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(
+        &decompressed_prover,
+        &verifier,
+        &pi,
+        &circuit,
+        &mut rng,
+        msg,
+    );
+}
@@ -0,0 +1,315 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+#[test]
+fn is_zero() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(a: BlsScalar, result: BlsScalar) -> Self {
+            Self { a, result }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let w_result = composer.append_witness(self.result);
+
+            let is_zero = composer.component_is_zero(w_a);
+            composer.assert_equal(is_zero, w_result);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_is_zero";
+    let mut rng = StdRng::seed_from_u64(0x2e70);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test:
+    // 0 is zero
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(BlsScalar::zero(), BlsScalar::one());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // random is not zero
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(a, BlsScalar::zero());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the default circuit claims 0 is not zero
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::default();
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // random is not zero
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, BlsScalar::one());
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn is_equal() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(a: BlsScalar, b: BlsScalar, result: BlsScalar) -> Self {
+            Self { a, b, result }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_result = composer.append_witness(self.result);
+
+            let is_equal = composer.component_is_equal(w_a, w_b);
+            composer.assert_equal(is_equal, w_result);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_is_equal";
+    let mut rng = StdRng::seed_from_u64(0x2e70);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test:
+    // x == x
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(a, a, BlsScalar::one());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // x != x + 1
+    let msg = "Verification of a satisfied circuit should pass";
+    let b = a + BlsScalar::one();
+    let circuit = TestCircuit::new(a, b, BlsScalar::zero());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // x == x + 1
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, b, BlsScalar::one());
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn inverse() {
+    pub struct TestCircuit {
+        a: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(a: BlsScalar, result: BlsScalar) -> Self {
+            Self { a, result }
+        }
+    }
+
+    impl Default for TestCircuit {
+        fn default() -> Self {
+            Self::new(BlsScalar::one(), BlsScalar::one())
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let w_result = composer.append_witness(self.result);
+
+            let inverse = composer.component_inverse(w_a)?;
+            composer.assert_equal(inverse, w_result);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_inverse";
+    let mut rng = StdRng::seed_from_u64(0x2e70);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test:
+    // x · x^-1 = 1
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(a, a.invert().unwrap());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // x^-1 != x
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a, a);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 0 has no inverse, which the gadget reports as an error
+    let circuit = TestCircuit::new(BlsScalar::zero(), BlsScalar::zero());
+    let result = prover.prove(&mut rng, &circuit);
+    let expected = Error::UnsatisfiableGadget {
+        gadget: "component_inverse",
+    };
+    assert_eq!(result.unwrap_err(), expected);
+
+    // Test:
+    // the gate is appended even without an inverse, so the circuit
+    // description doesn't depend on the witness
+    let mut composer = Builder::initialized();
+    let w_zero = composer.append_witness(BlsScalar::zero());
+    let constraints = composer.constraints();
+    let result = composer.component_inverse(w_zero);
+    assert_eq!(result.unwrap_err(), expected);
+    assert_eq!(composer.constraints(), constraints + 1);
+}
+
+#[test]
+fn div() {
+    pub struct TestCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+        result: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(a: BlsScalar, b: BlsScalar, result: BlsScalar) -> Self {
+            Self { a, b, result }
+        }
+    }
+
+    impl Default for TestCircuit {
+        fn default() -> Self {
+            Self::new(BlsScalar::zero(), BlsScalar::one(), BlsScalar::zero())
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_result = composer.append_witness(self.result);
+
+            let quotient = composer.component_div(w_a, w_b)?;
+            composer.assert_equal(quotient, w_result);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_div";
+    let mut rng = StdRng::seed_from_u64(0x2e70);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test default works:
+    // 0 / 1 = 0
+    let msg = "Default circuit verification should pass";
+    let circuit = TestCircuit::default();
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // (x · y) / y = x
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(a * b, b, a);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // (x · y) / y != y
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(a * b, b, b);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 0 / 0 has no quotient, not even 0, which the gadget reports as an error
+    let circuit = TestCircuit::new(
+        BlsScalar::zero(),
+        BlsScalar::zero(),
+        BlsScalar::zero(),
+    );
+    let result = prover.prove(&mut rng, &circuit);
+    let expected = Error::UnsatisfiableGadget {
+        gadget: "component_div",
+    };
+    assert_eq!(result.unwrap_err(), expected);
+
+    // Test fails:
+    // a random quotient doesn't satisfy the division by zero either
+    let circuit = TestCircuit::new(a, BlsScalar::zero(), b);
+    let result = prover.prove(&mut rng, &circuit);
+    assert_eq!(result.unwrap_err(), expected);
+}
@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::keccak::{self, DIGEST_LANES};
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+#[test]
+fn keccak256() {
+    const LANES: usize = 4;
+
+    #[derive(Default)]
+    pub struct TestCircuit {
+        message: [u64; LANES],
+        digest: [u64; DIGEST_LANES],
+    }
+
+    impl TestCircuit {
+        pub fn new(message: [u64; LANES], digest: [u64; DIGEST_LANES]) -> Self {
+            Self { message, digest }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let mut message = [C::ZERO; LANES];
+            message
+                .iter_mut()
+                .zip(self.message.iter())
+                .for_each(|(w, m)| *w = composer.append_witness(*m));
+
+            let digest = composer.component_keccak256(&message);
+
+            digest.iter().zip(self.digest.iter()).for_each(|(w, d)| {
+                composer.assert_equal_constant(*w, 0, Some((*d).into()))
+            });
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_keccak256";
+    let mut rng = StdRng::seed_from_u64(0xecc256);
+    let capacity = 1 << 17;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // the digest matches the native reference
+    let msg = "Verification of a satisfied circuit should pass";
+    let bytes = b"storage slot of a dusk contract";
+    let mut padded = [0u8; 8 * LANES];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let mut message = [0u64; LANES];
+    message.iter_mut().zip(padded.chunks(8)).for_each(|(m, b)| {
+        let mut lane = [0u8; 8];
+        lane.copy_from_slice(b);
+        *m = u64::from_le_bytes(lane);
+    });
+    let digest = keccak::digest(&padded);
+    let pi: Vec<BlsScalar> = digest.iter().map(|d| (*d).into()).collect();
+    let circuit = TestCircuit::new(message, digest);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the digest of a different message
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let mut wrong_message = message;
+    wrong_message[LANES - 1] ^= 1 << 63;
+    let circuit = TestCircuit::new(wrong_message, digest);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,357 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+const XOR_TABLE_ID: u64 = 1;
+const SQUARE_TABLE_ID: u64 = 2;
+
+// xor of every pair of 2-bit values
+fn xor_table() -> Vec<[BlsScalar; 3]> {
+    (0..4u64)
+        .flat_map(|a| (0..4u64).map(move |b| (a, b)))
+        .map(|(a, b)| [a.into(), b.into(), (a ^ b).into()])
+        .collect()
+}
+
+// squares of the values in [0, rows)
+fn square_table(rows: u64) -> Vec<[BlsScalar; 3]> {
+    (0..rows)
+        .map(|a| [a.into(), (a * a).into(), BlsScalar::zero()])
+        .collect()
+}
+
+#[test]
+fn lookup() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        table_id: u64,
+        a: BlsScalar,
+        b: BlsScalar,
+        c: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(table_id: u64, a: u64, b: u64, c: u64) -> Self {
+            Self {
+                table_id,
+                a: a.into(),
+                b: b.into(),
+                c: c.into(),
+            }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+            composer.append_lookup_table(SQUARE_TABLE_ID, &square_table(4));
+
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+            let w_c = composer.append_witness(self.c);
+
+            composer.component_lookup(self.table_id, w_a, w_b, w_c);
+            composer.component_lookup(self.table_id, w_a, w_b, w_c);
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_lookup";
+    let mut rng = StdRng::seed_from_u64(0xfaded);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let circuit = TestCircuit::new(XOR_TABLE_ID, 0, 0, 0);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test:
+    // 0 ^ 0 = 0
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // 2 ^ 3 = 1
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(XOR_TABLE_ID, 2, 3, 1);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 2 ^ 3 != 2
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(XOR_TABLE_ID, 2, 3, 2);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 4 is not part of the 2-bit xor table
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(XOR_TABLE_ID, 4, 0, 4);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // (3, 9, 0) is a row of the square table, but the circuit description
+    // queries the xor table
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(SQUARE_TABLE_ID, 3, 9, 0);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test:
+    // (3, 9, 0) is a row of the square table
+    let circuit = TestCircuit::new(SQUARE_TABLE_ID, 3, 9, 0);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // (1, 2, 3) is a row of the xor table, but the circuit description queries
+    // the square table
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(XOR_TABLE_ID, 1, 2, 3);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn lookup_table_larger_than_circuit() {
+    const ROWS: u64 = 100;
+
+    #[derive(Default)]
+    pub struct TestCircuit {
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(a: u64, b: u64) -> Self {
+            Self {
+                a: a.into(),
+                b: b.into(),
+            }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            composer.append_lookup_table(SQUARE_TABLE_ID, &square_table(ROWS));
+
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+
+            composer.component_lookup(SQUARE_TABLE_ID, w_a, w_b, C::ZERO);
+
+            Ok(())
+        }
+    }
+
+    let label = b"component_lookup";
+    let mut rng = StdRng::seed_from_u64(0xfaded);
+    let capacity = 1 << 8;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+
+    // Test:
+    // 99^2 = 9801
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(99, 9801);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 100 is not part of the table
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(100, 10000);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // The tables must survive the circuit compression
+    let compressed =
+        Compiler::compress::<TestCircuit>().expect("Circuit should compress");
+    let (prover, verifier) = Compiler::decompress(&pp, label, &compressed)
+        .expect("Circuit should decompress");
+
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(12, 144);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn lookup_table_of_the_prover_key() {
+    pub struct TestCircuit {
+        table: Vec<[BlsScalar; 3]>,
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(table: Vec<[BlsScalar; 3]>, a: u64, b: u64) -> Self {
+            Self {
+                table,
+                a: a.into(),
+                b: b.into(),
+            }
+        }
+    }
+
+    impl Default for TestCircuit {
+        fn default() -> Self {
+            Self::new(square_table(16), 0, 0)
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            composer.append_lookup_table(SQUARE_TABLE_ID, &self.table);
+
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+
+            composer.component_lookup(SQUARE_TABLE_ID, w_a, w_b, C::ZERO);
+
+            Ok(())
+        }
+    }
+
+    let label = b"component_lookup";
+    let mut rng = StdRng::seed_from_u64(0xfaded);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let pi = vec![];
+
+    // Test:
+    // 15^2 = 225
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = TestCircuit::new(square_table(16), 15, 225);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // (16, 256, 0) is a row of the table appended by the circuit, but not of
+    // the table the circuit was compiled with
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let table = (1..17)
+        .map(|a| [a.into(), (a * a).into(), BlsScalar::zero()])
+        .collect();
+    let circuit = TestCircuit::new(table, 16, 256);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn lookup_argument_of_the_proof() {
+    #[derive(Default)]
+    pub struct TestCircuit {
+        lookup: bool,
+        a: BlsScalar,
+        b: BlsScalar,
+    }
+
+    impl TestCircuit {
+        pub fn new(lookup: bool, a: u64, b: u64) -> Self {
+            Self {
+                lookup,
+                a: a.into(),
+                b: b.into(),
+            }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+            let w_b = composer.append_witness(self.b);
+
+            let constraint = Constraint::new().mult(1).a(w_a).b(w_a);
+            let w_c = composer.gate_mul(constraint);
+            composer.assert_equal(w_c, w_b);
+
+            if self.lookup {
+                composer
+                    .append_lookup_table(SQUARE_TABLE_ID, &square_table(16));
+                composer.component_lookup(SQUARE_TABLE_ID, w_a, w_b, C::ZERO);
+            }
+
+            Ok(())
+        }
+    }
+
+    let label = b"component_lookup";
+    let mut rng = StdRng::seed_from_u64(0xfaded);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let circuit = TestCircuit::new(false, 3, 9);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    // the proofs of circuits without lookups skip the lookup argument
+    let bytes = proof.to_var_bytes();
+    assert_eq!(bytes.len(), Proof::SIZE);
+    let proof = Proof::from_slice(&bytes).expect("Proof should deserialize");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+
+    let circuit = TestCircuit::new(true, 3, 9);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    let bytes = proof.to_var_bytes();
+    assert_eq!(bytes.len(), Proof::LOOKUP_SIZE);
+    let proof = Proof::from_slice(&bytes).expect("Proof should deserialize");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+
+    // a proof stripped of its lookup argument is rejected by a verifier of a
+    // circuit with lookups
+    let stripped = Proof::from_slice(&bytes[..Proof::SIZE])
+        .expect("Proof should deserialize");
+    assert!(matches!(
+        verifier.verify(&stripped, &pi),
+        Err(Error::ProofVerificationError)
+    ));
+    assert!(Proof::from_slice(&bytes[..Proof::SIZE + 1]).is_err());
+}
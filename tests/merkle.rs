@@ -0,0 +1,252 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::merkle::{self, Opening, WitnessLevel};
+use dusk_plonk::prelude::*;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+// Builds a tree over `leaves` and returns its root and the opening of the leaf
+// at `index`
+fn tree<const A: usize>(
+    leaves: &[BlsScalar],
+    mut index: usize,
+) -> (BlsScalar, Opening<A>) {
+    let mut level = leaves.to_vec();
+    let mut branch = Vec::new();
+
+    while level.len() > 1 {
+        let nodes: Vec<[BlsScalar; A]> = level
+            .chunks(A)
+            .map(|chunk| {
+                let mut children = [BlsScalar::zero(); A];
+                children.copy_from_slice(chunk);
+                children
+            })
+            .collect();
+
+        branch.push((nodes[index / A], index % A));
+        level = nodes.iter().map(merkle::hash).collect();
+        index /= A;
+    }
+
+    (level[0], Opening::new(branch))
+}
+
+pub struct TestCircuit<const A: usize> {
+    leaf: BlsScalar,
+    opening: Opening<A>,
+    root: BlsScalar,
+}
+
+impl<const A: usize> TestCircuit<A> {
+    pub fn new(leaf: BlsScalar, opening: Opening<A>, root: BlsScalar) -> Self {
+        Self {
+            leaf,
+            opening,
+            root,
+        }
+    }
+}
+
+impl<const A: usize> Default for TestCircuit<A> {
+    fn default() -> Self {
+        Self::new(
+            BlsScalar::zero(),
+            Opening::new(Vec::new()),
+            BlsScalar::zero(),
+        )
+    }
+}
+
+impl<const A: usize> Circuit for TestCircuit<A> {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_leaf = composer.append_witness(self.leaf);
+        let path = WitnessLevel::append(composer, &self.opening);
+
+        let root = composer.component_merkle_opening(w_leaf, &path);
+        composer.assert_equal_constant(root, 0, Some(self.root));
+
+        Ok(())
+    }
+}
+
+fn check_opening<const A: usize>(leaves: usize, index: usize) {
+    let mut rng = StdRng::seed_from_u64(0x3e41e);
+    let leaves: Vec<BlsScalar> =
+        (0..leaves).map(|_| BlsScalar::random(&mut rng)).collect();
+    let (root, opening) = tree::<A>(&leaves, index);
+    assert!(opening.verify(&leaves[index], &root));
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_merkle_opening";
+    let capacity = 1 << 12;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let circuit = TestCircuit::new(leaves[index], opening.clone(), root);
+    let (prover, verifier) =
+        Compiler::compile_with_circuit(&pp, label, &circuit)
+            .expect("Circuit should compile");
+
+    // Test:
+    // the opening of the leaf results in the root
+    let msg = "Verification of a satisfied circuit should pass";
+    let pi = vec![root];
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // the opening of another leaf results in the same root
+    let msg = "Verification of a satisfied circuit should pass";
+    let other = (index + 1) % leaves.len();
+    let (_, other_opening) = tree::<A>(&leaves, other);
+    let circuit = TestCircuit::new(leaves[other], other_opening, root);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // a leaf that isn't part of the tree
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let leaf = BlsScalar::random(&mut rng);
+    let circuit = TestCircuit::new(leaf, opening.clone(), root);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the opening with the position of a sibling
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let mut branch = opening.branch().to_vec();
+    branch[0].1 = (branch[0].1 + 1) % A;
+    let circuit = TestCircuit::new(leaves[index], Opening::new(branch), root);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // a different root
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit =
+        TestCircuit::new(leaves[index], opening, root + BlsScalar::one());
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn merkle_opening_arity_2() {
+    check_opening::<2>(8, 5);
+}
+
+#[test]
+fn merkle_opening_arity_4() {
+    check_opening::<4>(16, 9);
+}
+
+// Circuit opening a tree of arity 4 of a single level from the scalars of its
+// siblings and position bits
+pub struct LevelCircuit {
+    leaf: BlsScalar,
+    siblings: Vec<BlsScalar>,
+    bits: Vec<BlsScalar>,
+    root: BlsScalar,
+}
+
+impl Default for LevelCircuit {
+    fn default() -> Self {
+        Self {
+            leaf: BlsScalar::zero(),
+            siblings: vec![BlsScalar::zero(); 3],
+            bits: vec![BlsScalar::zero(); 2],
+            root: BlsScalar::zero(),
+        }
+    }
+}
+
+impl Circuit for LevelCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_leaf = composer.append_witness(self.leaf);
+        let siblings = self
+            .siblings
+            .iter()
+            .map(|s| composer.append_witness(*s))
+            .collect();
+        let bits = self
+            .bits
+            .iter()
+            .map(|b| composer.append_witness(*b))
+            .collect();
+        let path = [WitnessLevel::<4>::new(siblings, bits)];
+
+        let root = composer.component_merkle_opening(w_leaf, &path);
+        composer.assert_equal_constant(root, 0, Some(self.root));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn merkle_opening_level() {
+    let mut rng = StdRng::seed_from_u64(0x1e7e1);
+    let children = [(); 4].map(|_| BlsScalar::random(&mut rng));
+    let root = merkle::hash(&children);
+
+    let label = b"component_merkle_opening";
+    let pp = PublicParameters::setup(1 << 12, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<LevelCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // the leaf at every position results in the root
+    for position in 0..4 {
+        let msg = "Verification of a satisfied circuit should pass";
+        let siblings = (0..4)
+            .filter(|i| *i != position)
+            .map(|i| children[i])
+            .collect();
+        let bits = (0..2)
+            .map(|i| BlsScalar::from((position >> i) as u64 & 1))
+            .collect();
+        let circuit = LevelCircuit {
+            leaf: children[position],
+            siblings,
+            bits,
+            root,
+        };
+        check_satisfied_circuit(
+            &prover,
+            &verifier,
+            &vec![root],
+            &circuit,
+            &mut rng,
+            &msg,
+        );
+    }
+
+    // Test fails:
+    // a position bit that isn't boolean
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = LevelCircuit {
+        leaf: children[0],
+        siblings: children[1..].to_vec(),
+        bits: vec![BlsScalar::from(2), BlsScalar::zero()],
+        root,
+    };
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+#[should_panic(expected = "a level has `A - 1` siblings")]
+fn merkle_level_siblings() {
+    let siblings = vec![Builder::ZERO; 2];
+    let bits = vec![Builder::ZERO; 2];
+
+    WitnessLevel::<4>::new(siblings, bits);
+}
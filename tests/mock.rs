@@ -0,0 +1,371 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_jubjub::GENERATOR_EXTENDED;
+use dusk_plonk::composer::{GateType, MockFailure};
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+const XOR_TABLE_ID: u64 = 1;
+
+// xor of every pair of 2-bit values
+fn xor_table() -> Vec<[BlsScalar; 3]> {
+    (0..4u64)
+        .flat_map(|a| (0..4u64).map(move |b| (a, b)))
+        .map(|(a, b)| [a.into(), b.into(), (a ^ b).into()])
+        .collect()
+}
+
+// `a · b = c`, as a custom gate
+struct Mul;
+
+impl CustomGate for Mul {
+    const SELECTOR: &'static [u8] = b"q_mul";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b - w.c
+    }
+}
+
+// Circuit enabling every gate of the composer
+#[derive(Default)]
+pub struct TestCircuit {
+    a: u64,
+    b: u64,
+    c: u64,
+    scalar: JubJubScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: u64, b: u64, c: u64) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            scalar: JubJubScalar::from(0x5ca1a7u64),
+        }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+        let w_c = composer.append_witness(self.c);
+
+        // arithmetic and public input
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_ab = composer.gate_mul(constraint);
+        let w_pi = composer.append_public(self.a * self.b);
+        composer.assert_equal(w_ab, w_pi);
+
+        // range and logic
+        composer.component_range::<2>(w_c);
+        let w_xor = composer.append_logic_xor::<2>(w_a, w_b);
+        let w_and = composer.append_logic_and::<2>(w_a, w_b);
+        let w_expected = composer.append_witness(self.a & self.b);
+        composer.assert_equal(w_and, w_expected);
+
+        // lookup
+        composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_xor);
+
+        // custom gate
+        composer
+            .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_ab))?;
+
+        // fixed and variable base curve additions
+        let w_scalar = composer.append_witness(self.scalar);
+        let w_point =
+            composer.component_mul_generator(w_scalar, GENERATOR_EXTENDED)?;
+        let w_double = composer.component_add_point(w_point, w_point);
+
+        let point = GENERATOR_EXTENDED * self.scalar;
+        composer.assert_equal_public_point(w_double, point + point);
+
+        Ok(())
+    }
+}
+
+// Constraint failures of the circuit, as pairs of row and gate
+fn failed_gates<C: Circuit>(circuit: &C) -> Vec<(usize, GateType)> {
+    MockProver::run(circuit)
+        .expect("circuit should run")
+        .verify()
+        .expect_err("circuit shouldn't be satisfied")
+        .into_iter()
+        .filter_map(|failure| match failure {
+            MockFailure::Constraint { index, gate, .. } => Some((index, gate)),
+            MockFailure::CopyConstraint { .. } => None,
+        })
+        .collect()
+}
+
+#[test]
+fn mock_satisfied() {
+    let circuit = TestCircuit::new(2, 3, 1);
+
+    let mock = MockProver::run(&circuit).expect("circuit should run");
+    mock.assert_satisfied();
+
+    assert_eq!(mock.constraints(), circuit.size::<Builder>());
+
+    // the checks of the mock prover agree with the proof
+    let mut rng = StdRng::seed_from_u64(0x30c);
+    let pp = PublicParameters::setup(1 << 10, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, b"mock")
+        .expect("Circuit should compile");
+
+    let pi = mock.public_inputs();
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // out of range, the accumulated quads don't add up to the witness
+    let circuit = TestCircuit::new(2, 3, 16);
+    let gates = failed_gates(&circuit);
+    assert_eq!(gates.len(), 1);
+    assert_eq!(gates[0].1, GateType::Arithmetic);
+
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, msg);
+}
+
+#[test]
+fn mock_unsatisfied_gates() {
+    // the custom gate is the only one checking `a · b` against a constant
+    #[derive(Default)]
+    struct CustomCircuit;
+
+    impl Circuit for CustomCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::from(2));
+            let w_b = composer.append_witness(BlsScalar::from(3));
+            let w_c = composer.append_witness(BlsScalar::from(7));
+
+            composer
+                .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_c))?;
+
+            Ok(())
+        }
+    }
+
+    let index = CustomCircuit.size::<Builder>() - 1;
+    assert_eq!(
+        failed_gates(&CustomCircuit),
+        vec![(index, GateType::Custom(b"q_mul"))]
+    );
+
+    // a query out of the lookup table
+    #[derive(Default)]
+    struct LookupCircuit;
+
+    impl Circuit for LookupCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+
+            let w_a = composer.append_witness(BlsScalar::from(1));
+            let w_b = composer.append_witness(BlsScalar::from(2));
+
+            composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_b);
+
+            Ok(())
+        }
+    }
+
+    let index = LookupCircuit.size::<Builder>() - 1;
+    assert_eq!(
+        failed_gates(&LookupCircuit),
+        vec![(index, GateType::Lookup)]
+    );
+}
+
+#[test]
+fn mock_report() {
+    #[derive(Default)]
+    struct MulCircuit;
+
+    impl Circuit for MulCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::from(2));
+            let w_b = composer.append_witness(BlsScalar::from(3));
+            let w_c = composer.append_witness(BlsScalar::from(7));
+
+            let constraint = Constraint::new()
+                .mult(1)
+                .output(-BlsScalar::one())
+                .a(w_a)
+                .b(w_b)
+                .o(w_c);
+            composer.append_gate(constraint);
+
+            Ok(())
+        }
+    }
+
+    let failures = MockProver::run(&MulCircuit)
+        .expect("circuit should run")
+        .verify()
+        .expect_err("circuit shouldn't be satisfied");
+
+    assert_eq!(failures.len(), 1);
+    match &failures[0] {
+        MockFailure::Constraint {
+            index,
+            gate,
+            selectors,
+            wires,
+            public_input,
+            namespace,
+            names,
+            source,
+        } => {
+            assert_eq!(*index, MulCircuit.size::<Builder>() - 1);
+            assert_eq!(*gate, GateType::Arithmetic);
+            assert_eq!(selectors.q_m, BlsScalar::one());
+            assert_eq!(selectors.q_o, -BlsScalar::one());
+            assert_eq!(selectors.q_arith, BlsScalar::one());
+            assert_eq!(wires.a, BlsScalar::from(2));
+            assert_eq!(wires.b, BlsScalar::from(3));
+            assert_eq!(wires.c, BlsScalar::from(7));
+            assert_eq!(*public_input, BlsScalar::zero());
+            assert!(namespace.is_none());
+            assert_eq!(*names, [None, None, None, None]);
+
+            #[cfg(not(feature = "debug"))]
+            assert!(source.is_none());
+            #[cfg(feature = "debug")]
+            assert!(source.as_ref().is_some_and(|s| s.contains("mock.rs")));
+        }
+        failure => panic!("unexpected failure: {}", failure),
+    }
+
+    let report = failures[0].to_string();
+    assert!(report.contains("arithmetic gate"));
+    assert!(report.contains("q_m"));
+    assert!(!report.contains("q_range"));
+}
+
+#[test]
+fn prove_constraint_check() {
+    let mut rng = StdRng::seed_from_u64(0xc4ec);
+    let pp = PublicParameters::setup(1 << 10, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, b"check")
+        .expect("Circuit should compile");
+    let prover = prover.with_constraint_check(true);
+
+    // the check doesn't reject satisfied circuits
+    let circuit = TestCircuit::new(2, 3, 1);
+    let pi = MockProver::run(&circuit)
+        .expect("circuit should run")
+        .public_inputs();
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    // the prover fails on the first constraint reported by the mock prover
+    let circuit = TestCircuit::new(2, 3, 16);
+    let (index, gate_type) = failed_gates(&circuit)[0];
+    let err = prover
+        .prove(&mut rng, &circuit)
+        .expect_err("Proof creation of an unsatisfied circuit should fail");
+    assert_eq!(err, Error::UnsatisfiedConstraint { index, gate_type });
+
+    // without the check, the proof fails later on
+    let err = prover
+        .with_constraint_check(false)
+        .prove(&mut rng, &circuit)
+        .expect_err("Proof creation of an unsatisfied circuit should fail");
+    assert!(!matches!(err, Error::UnsatisfiedConstraint { .. }));
+}
+
+#[test]
+fn mock_names() {
+    #[derive(Default)]
+    struct NamedCircuit;
+
+    impl Circuit for NamedCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness_named(2, "a");
+
+            composer.namespace("square", |composer| {
+                let w_b = composer.append_witness_named(3, "b");
+
+                composer.namespace("mul", |composer| {
+                    let constraint = Constraint::new()
+                        .mult(1)
+                        .output(-BlsScalar::one())
+                        .a(w_a)
+                        .b(w_b)
+                        .o(w_b);
+                    composer.append_gate(constraint);
+                });
+
+                // the inner namespace was exited
+                composer.assert_equal_constant(w_b, 4, None);
+            });
+
+            Ok(())
+        }
+    }
+
+    let failures = MockProver::run(&NamedCircuit)
+        .expect("circuit should run")
+        .verify()
+        .expect_err("circuit shouldn't be satisfied");
+
+    let namespaces: Vec<_> = failures
+        .iter()
+        .map(|failure| match failure {
+            MockFailure::Constraint {
+                namespace, names, ..
+            } => (namespace.as_deref(), names.clone()),
+            failure => panic!("unexpected failure: {}", failure),
+        })
+        .collect();
+
+    let a = Some(String::from("a"));
+    let b = Some(String::from("square/b"));
+    assert_eq!(
+        namespaces,
+        vec![
+            (Some("square/mul"), [a, b.clone(), b.clone(), None]),
+            (Some("square"), [b, None, None, None]),
+        ]
+    );
+
+    let report = failures[0].to_string();
+    assert!(report.contains("in `square/mul`"));
+    assert!(report.contains("(`square/b`)"));
+}
+
+#[test]
+#[should_panic(expected = "the circuit isn't satisfied")]
+fn mock_assert_satisfied() {
+    MockProver::run(&TestCircuit::new(2, 3, 16))
+        .expect("circuit should run")
+        .assert_satisfied();
+}
@@ -0,0 +1,202 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::{Arc, Mutex};
+
+use dusk_plonk::prelude::*;
+use dusk_plonk::runtime::{Observer, RuntimeEvent};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Count of the constraints appended by every gadget, including the ones of
+// its nested gadgets
+#[derive(Default)]
+struct GadgetCounter {
+    gadgets: Mutex<Vec<(&'static str, usize)>>,
+    stack: Mutex<Vec<usize>>,
+    constraints: Mutex<usize>,
+    proofs: Mutex<usize>,
+}
+
+impl Observer for GadgetCounter {
+    fn event(&self, event: &RuntimeEvent) {
+        let mut constraints = self.constraints.lock().unwrap();
+        let mut stack = self.stack.lock().unwrap();
+
+        match event {
+            RuntimeEvent::ConstraintAppended { .. } => *constraints += 1,
+            RuntimeEvent::GadgetEntered { .. } => stack.push(*constraints),
+            RuntimeEvent::GadgetExited { name } => {
+                let start = stack.pop().expect("the gadget was entered");
+                let count = *constraints - start;
+
+                self.gadgets.lock().unwrap().push((name, count));
+            }
+            RuntimeEvent::ProofFinished => *self.proofs.lock().unwrap() += 1,
+            _ => (),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        composer.component_range::<4>(w_a);
+        composer.component_less_than::<8>(w_a, w_b);
+
+        composer.gadget("square", |composer| {
+            let constraint = Constraint::new().mult(1).a(w_a).b(w_a);
+            composer.gate_mul(constraint)
+        });
+
+        Ok(())
+    }
+}
+
+#[test]
+fn observe_gadgets() {
+    let counter = Arc::new(GadgetCounter::default());
+    let mut builder = Builder::observed(counter.clone());
+
+    // the initialization of the builder is observed
+    assert_eq!(*counter.constraints.lock().unwrap(), builder.constraints());
+
+    let circuit = TestCircuit {
+        a: BlsScalar::from(3),
+        b: BlsScalar::from(7),
+    };
+    circuit.circuit(&mut builder).expect("circuit should run");
+
+    assert_eq!(*counter.constraints.lock().unwrap(), builder.constraints());
+    assert!(counter.stack.lock().unwrap().is_empty());
+
+    // the gadgets exit innermost first
+    let gadgets = counter.gadgets.lock().unwrap();
+    let (range, nested) = gadgets.split_at(2);
+    let (nested, gadgets) = nested.split_at(nested.len() - 2);
+
+    assert_eq!(range[0].0, "component_range_bits");
+    assert_eq!(range[1].0, "component_range");
+    assert_eq!(range[0].1, range[1].1);
+
+    // the inputs of the comparison are range checked, and the bits of the
+    // difference are boolean
+    let (checks, nested) = nested.split_at(2);
+    assert!(checks
+        .iter()
+        .all(|(name, _)| *name == "component_range_bits"));
+    assert!(nested.iter().all(|(name, _)| *name == "component_boolean"));
+    let nested: usize = nested.iter().map(|(_, count)| count).sum();
+
+    assert_eq!(gadgets[0].0, "component_less_than");
+    assert!(gadgets[0].1 > nested);
+    assert_eq!(gadgets[1], ("square", 1));
+
+    let mut builder = Builder::initialized();
+    let w = builder.append_witness(3);
+    let before = builder.constraints();
+    builder.component_range::<4>(w);
+
+    assert_eq!(range[1].1, builder.constraints() - before);
+}
+
+#[test]
+fn observe_from_circuit() {
+    // an observer registered by a circuit only sees the events that follow
+    #[derive(Default)]
+    struct LateCircuit {
+        counter: Arc<GadgetCounter>,
+    }
+
+    impl Circuit for LateCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(BlsScalar::from(2));
+            composer.component_boolean(w_a);
+
+            composer.runtime().observe(self.counter.clone());
+            composer.component_boolean(C::ONE);
+
+            Ok(())
+        }
+    }
+
+    let circuit = LateCircuit::default();
+    let mut builder = Builder::initialized();
+    circuit.circuit(&mut builder).expect("circuit should run");
+
+    let gadgets = circuit.counter.gadgets.lock().unwrap();
+    assert_eq!(*gadgets, vec![("component_boolean", 1)]);
+    assert_eq!(*circuit.counter.constraints.lock().unwrap(), 1);
+}
+
+#[test]
+fn observe_compiled_and_proven() {
+    let mut rng = StdRng::seed_from_u64(0x0b5e);
+    let pp = PublicParameters::setup(1 << 7, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let label = b"observer";
+    let compiled = Arc::new(GadgetCounter::default());
+    let (prover, verifier) =
+        Compiler::compile_observed::<TestCircuit>(&pp, label, compiled.clone())
+            .expect("Circuit should compile");
+
+    let mut builder = Builder::initialized();
+    TestCircuit::default()
+        .circuit(&mut builder)
+        .expect("circuit should run");
+    let constraints = builder.constraints();
+
+    // the compiled circuit is observed, but it isn't proven
+    assert_eq!(*compiled.constraints.lock().unwrap(), constraints);
+    assert_eq!(*compiled.proofs.lock().unwrap(), 0);
+
+    let proven = Arc::new(GadgetCounter::default());
+    let prover = prover.with_observer(proven.clone());
+
+    let circuit = TestCircuit {
+        a: BlsScalar::from(3),
+        b: BlsScalar::from(7),
+    };
+    let (proof, public_inputs) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &public_inputs)
+        .expect("Verification of a satisfied circuit should pass");
+
+    // the circuit of the proof appends the same gadgets as the compiled one
+    assert_eq!(*proven.constraints.lock().unwrap(), constraints);
+    assert_eq!(*proven.proofs.lock().unwrap(), 1);
+    assert_eq!(
+        *proven.gadgets.lock().unwrap(),
+        *compiled.gadgets.lock().unwrap()
+    );
+
+    // the observers are kept by the clones of the prover, and receive the
+    // events of every proof
+    let clone = prover.clone();
+    clone
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    assert_eq!(*proven.proofs.lock().unwrap(), 2);
+    assert_eq!(*compiled.proofs.lock().unwrap(), 0);
+}
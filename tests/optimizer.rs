@@ -0,0 +1,218 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_jubjub::GENERATOR_EXTENDED;
+use dusk_plonk::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+const XOR_TABLE_ID: u64 = 1;
+
+// xor of every pair of 2-bit values
+fn xor_table() -> Vec<[BlsScalar; 3]> {
+    (0..4u64)
+        .flat_map(|a| (0..4u64).map(move |b| (a, b)))
+        .map(|(a, b)| [a.into(), b.into(), (a ^ b).into()])
+        .collect()
+}
+
+// `a · b = c`, as a custom gate
+struct Mul;
+
+impl CustomGate for Mul {
+    const SELECTOR: &'static [u8] = b"q_mul";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b - w.c
+    }
+}
+
+// Circuit with redundant constants and linear combinations between the
+// gates reading the next row
+#[derive(Default)]
+pub struct TestCircuit {
+    a: u64,
+    b: u64,
+    sum: u64,
+    scalar: JubJubScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: u64, b: u64, sum: u64) -> Self {
+        Self {
+            a,
+            b,
+            sum,
+            scalar: JubJubScalar::from(0x5ca1a7u64),
+        }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        // `a + b + 2 + 3 = sum`, with the constants appended twice
+        let constraint = Constraint::new().left(1).right(1);
+        let w_two = composer.append_constant(2);
+        let w_three = composer.append_constant(3);
+        let w_other = composer.append_constant(3);
+
+        let w_ab = composer.gate_add(constraint.a(w_a).b(w_b));
+        let w_two_three = composer.gate_add(constraint.a(w_two).b(w_three));
+        let w_sum = composer.gate_add(constraint.a(w_ab).b(w_two_three));
+        let w_pi = composer.append_public(self.sum);
+        composer.assert_equal(w_sum, w_pi);
+
+        // unused output
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_other);
+        composer.gate_mul(constraint);
+
+        // range, logic and lookup
+        composer.component_range::<2>(w_b);
+        let w_xor = composer.append_logic_xor::<2>(w_a, w_b);
+        composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_xor);
+
+        // custom gate
+        let w_c = composer.append_witness(self.a * self.b);
+        composer
+            .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_c))?;
+        let w_c = composer.gate_add(Constraint::new().left(1).a(w_c));
+        composer.assert_equal_constant(
+            w_c,
+            0,
+            Some(BlsScalar::from(self.a * self.b)),
+        );
+
+        // fixed and variable base curve additions
+        let w_scalar = composer.append_witness(self.scalar);
+        let w_point =
+            composer.component_mul_generator(w_scalar, GENERATOR_EXTENDED)?;
+        let w_double = composer.component_add_point(w_point, w_point);
+
+        let point = GENERATOR_EXTENDED * self.scalar;
+        composer.assert_equal_public_point(w_double, point + point);
+
+        Ok(())
+    }
+}
+
+// `a + 1 + 1 = sum`
+#[derive(Default)]
+pub struct SumCircuit {
+    a: BlsScalar,
+    sum: BlsScalar,
+}
+
+impl Circuit for SumCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_one = composer.append_constant(1);
+
+        let constraint = Constraint::new().left(1).right(1).a(w_one);
+        let w_sum = composer.gate_add(constraint.b(w_a));
+        let w_sum = composer.gate_add(constraint.b(w_sum));
+        composer.assert_equal_constant(w_sum, 0, Some(self.sum));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn optimized() {
+    let label = b"optimized";
+    let mut rng = StdRng::seed_from_u64(0x0b7);
+    let pp = PublicParameters::setup(1 << 11, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let (optimized_prover, optimized_verifier) =
+        Compiler::compile_optimized::<TestCircuit>(&pp, label)
+            .expect("Circuit should compile");
+
+    assert!(!prover.is_optimized());
+    assert!(optimized_prover.is_optimized());
+
+    // the public inputs are the same, in the same order
+    let circuit = TestCircuit::new(2, 3, 10);
+    let (_, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(
+        &optimized_prover,
+        &optimized_verifier,
+        &pi,
+        &circuit,
+        &mut rng,
+        msg,
+    );
+
+    // the gates of the circuits differ
+    let (proof, _) = optimized_prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect_err("Verification with another circuit should fail");
+
+    // unsatisfied circuits are still rejected
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit = TestCircuit::new(2, 3, 11);
+    check_unsatisfied_circuit(&optimized_prover, &circuit, &mut rng, msg);
+
+    let circuit = TestCircuit::new(2, 4, 11);
+    check_unsatisfied_circuit(&optimized_prover, &circuit, &mut rng, msg);
+}
+
+#[test]
+fn optimized_serialization() {
+    let label = b"optimized_serialization";
+    let mut rng = StdRng::seed_from_u64(0x0b8);
+    let pp = PublicParameters::setup(1 << 5, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let (prover, verifier) =
+        Compiler::compile_optimized::<SumCircuit>(&pp, label)
+            .expect("Circuit should compile");
+
+    let prover = Prover::try_from_bytes(prover.to_bytes())
+        .expect("Prover should deserialize");
+    assert!(prover.is_optimized());
+
+    let circuit = SumCircuit {
+        a: BlsScalar::from(3),
+        sum: BlsScalar::from(5),
+    };
+    let pi = vec![BlsScalar::from(5)];
+    let msg = "Verification of a satisfied circuit should pass";
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, msg);
+
+    let circuit = SumCircuit {
+        a: BlsScalar::from(4),
+        sum: BlsScalar::from(6),
+    };
+    let (proof, _) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect_err("Verification with wrong public inputs should fail");
+}
@@ -7,7 +7,7 @@
 use dusk_plonk::prelude::*;
 use ff::Field;
 use rand::rngs::StdRng;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 
 mod common;
 use common::{check_satisfied_circuit, check_unsatisfied_circuit};
@@ -147,3 +147,130 @@ fn range() {
     let circuit: TestCircuit<BIT_PAIRS_128> = TestCircuit::new(a);
     check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
 }
+
+#[test]
+fn range_bits() {
+    // the value of `a` shifted `shift` bits to the right
+    fn shr(a: &BlsScalar, shift: usize) -> BlsScalar {
+        a.to_bits()
+            .iter()
+            .skip(shift)
+            .rev()
+            .fold(BlsScalar::zero(), |acc, bit| {
+                acc.double() + BlsScalar::from(*bit as u64)
+            })
+    }
+
+    #[derive(Default)]
+    pub struct TestCircuit<const BITS: usize> {
+        a: BlsScalar,
+    }
+
+    impl<const BITS: usize> TestCircuit<BITS> {
+        pub fn new(a: BlsScalar) -> Self {
+            Self { a }
+        }
+    }
+
+    impl<const BITS: usize> Circuit for TestCircuit<BITS> {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let w_a = composer.append_witness(self.a);
+
+            let accumulators = composer.component_range_bits(w_a, BITS);
+
+            // every accumulator holds two more bits than the previous one
+            let len = accumulators.len();
+            accumulators.iter().enumerate().for_each(|(i, acc)| {
+                let expected = shr(&self.a, 2 * (len - 1 - i));
+                let w_expected = composer.append_witness(expected);
+                composer.assert_equal(*acc, w_expected);
+            });
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_range_bits";
+    let mut rng = StdRng::seed_from_u64(0xb1eeb);
+    let capacity = 1 << 8;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    // public input to be used by all tests
+    let pi = vec![];
+
+    // Test bits = 1
+    let (prover, verifier) = Compiler::compile::<TestCircuit<1>>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // 1 < 2^1
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit: TestCircuit<1> = TestCircuit::new(BlsScalar::one());
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 2 !< 2^1
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let circuit: TestCircuit<1> = TestCircuit::new(BlsScalar::from(2));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test bits = 65
+    let (prover, verifier) = Compiler::compile::<TestCircuit<65>>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // 2^65 - 1 < 2^65
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::pow_of_2(65) - BlsScalar::one();
+    let circuit: TestCircuit<65> = TestCircuit::new(a);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test:
+    // random u64 < 2^65
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::from(rng.next_u64());
+    let circuit: TestCircuit<65> = TestCircuit::new(a);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 2^65 !< 2^65
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let a = BlsScalar::pow_of_2(65);
+    let circuit: TestCircuit<65> = TestCircuit::new(a);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test bits = 253
+    let (prover, verifier) = Compiler::compile::<TestCircuit<253>>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // 2^252 < 2^253
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = BlsScalar::pow_of_2(252);
+    let circuit: TestCircuit<253> = TestCircuit::new(a);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // 2^253 !< 2^253
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let a = BlsScalar::pow_of_2(253);
+    let circuit: TestCircuit<253> = TestCircuit::new(a);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test bits = 257
+    let (prover, verifier) = Compiler::compile::<TestCircuit<257>>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // -bls(1) < 2^257
+    let msg = "Verification of a satisfied circuit should pass";
+    let a = -BlsScalar::one();
+    let circuit: TestCircuit<257> = TestCircuit::new(a);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,171 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_jubjub::GENERATOR_EXTENDED;
+use dusk_plonk::composer::GateType;
+use dusk_plonk::prelude::*;
+
+const XOR_TABLE_ID: u64 = 1;
+
+// xor of every pair of 2-bit values
+fn xor_table() -> Vec<[BlsScalar; 3]> {
+    (0..4u64)
+        .flat_map(|a| (0..4u64).map(move |b| (a, b)))
+        .map(|(a, b)| [a.into(), b.into(), (a ^ b).into()])
+        .collect()
+}
+
+// `a · b = c`, as a custom gate
+struct Mul;
+
+impl CustomGate for Mul {
+    const SELECTOR: &'static [u8] = b"q_mul";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b - w.c
+    }
+}
+
+#[derive(Default)]
+pub struct EmptyCircuit;
+
+impl Circuit for EmptyCircuit {
+    fn circuit<C>(&self, _composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        Ok(())
+    }
+}
+
+// `a · b = pi`
+#[derive(Default)]
+pub struct MulCircuit;
+
+impl Circuit for MulCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(BlsScalar::from(2));
+        let w_b = composer.append_witness(BlsScalar::from(3));
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_ab = composer.gate_mul(constraint);
+        let w_pi = composer.append_public(BlsScalar::from(6));
+        composer.assert_equal(w_ab, w_pi);
+
+        Ok(())
+    }
+}
+
+// Circuit enabling every gate of the composer
+#[derive(Default)]
+pub struct GatesCircuit;
+
+impl Circuit for GatesCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        composer.append_lookup_table(XOR_TABLE_ID, &xor_table());
+
+        let w_a = composer.append_witness(BlsScalar::from(2));
+        let w_b = composer.append_witness(BlsScalar::from(3));
+        let w_c = composer.append_witness(BlsScalar::from(6));
+
+        composer.component_range::<2>(w_a);
+        let w_xor = composer.append_logic_xor::<2>(w_a, w_b);
+        composer.component_lookup(XOR_TABLE_ID, w_a, w_b, w_xor);
+        composer
+            .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_c))?;
+
+        let scalar = JubJubScalar::from(0x5ca1a7u64);
+        let w_scalar = composer.append_witness(scalar);
+        let w_point =
+            composer.component_mul_generator(w_scalar, GENERATOR_EXTENDED)?;
+        let w_double = composer.component_add_point(w_point, w_point);
+
+        let point = GENERATOR_EXTENDED * scalar;
+        composer.assert_equal_public_point(w_double, point + point);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn report_counts() {
+    let empty = Compiler::report::<EmptyCircuit>().expect("circuit should run");
+    let report = Compiler::report::<MulCircuit>().expect("circuit should run");
+
+    assert_eq!(report.constraints, MulCircuit.size::<Builder>());
+    assert_eq!(report.constraints, empty.constraints + 3);
+    assert_eq!(
+        report.gate(GateType::Arithmetic),
+        empty.gate(GateType::Arithmetic) + 3
+    );
+    assert_eq!(report.gate(GateType::Range), 0);
+
+    // the product and the public input are wired twice
+    assert_eq!(report.public_inputs, 1);
+    assert_eq!(report.witnesses, empty.witnesses + 4);
+    assert_eq!(report.copy_cycles, empty.copy_cycles + 2);
+    assert_eq!(report.domain_size, report.constraints.next_power_of_two());
+
+    let text = report.to_string();
+    assert!(text.contains("arithmetic gates"));
+    assert!(!text.contains("range gates"));
+}
+
+#[test]
+fn report_gates() {
+    let report =
+        Compiler::report::<GatesCircuit>().expect("circuit should run");
+
+    let gates: Vec<_> = report.gates.iter().map(|(gate, _)| *gate).collect();
+    assert_eq!(
+        gates,
+        vec![
+            GateType::Arithmetic,
+            GateType::Range,
+            GateType::Logic,
+            GateType::FixedBaseAddition,
+            GateType::VariableBaseAddition,
+            GateType::Lookup,
+            GateType::Custom(b"q_mul"),
+        ]
+    );
+
+    assert_eq!(report.gate(GateType::VariableBaseAddition), 1);
+    assert_eq!(report.gate(GateType::Lookup), 1);
+    assert_eq!(report.gate(GateType::Custom(b"q_mul")), 1);
+    assert_eq!(report.public_inputs, 2);
+}
+
+#[test]
+fn report_call_sites() {
+    let report =
+        Compiler::report::<GatesCircuit>().expect("circuit should run");
+
+    #[cfg(not(feature = "debug"))]
+    assert!(report.call_sites.is_empty());
+
+    #[cfg(feature = "debug")]
+    {
+        // the scalar multiplication appends most of the constraints
+        let top = &report.call_sites[0];
+        assert!(top.source.contains("report.rs"));
+        assert!(report.to_string().contains(&top.source));
+
+        let counts: Vec<_> = report
+            .call_sites
+            .iter()
+            .map(|site| site.constraints)
+            .collect();
+        assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(counts.iter().sum::<usize>() <= report.constraints);
+    }
+}
@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use dusk_plonk::sha256;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+#[test]
+fn sha256_single_block() {
+    const WORDS: usize = 3;
+
+    #[derive(Default)]
+    pub struct TestCircuit {
+        message: [u32; WORDS],
+        digest: [u32; 8],
+    }
+
+    impl TestCircuit {
+        pub fn new(message: [u32; WORDS], digest: [u32; 8]) -> Self {
+            Self { message, digest }
+        }
+    }
+
+    impl Circuit for TestCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let mut message = [C::ZERO; WORDS];
+            message
+                .iter_mut()
+                .zip(self.message.iter())
+                .for_each(|(w, m)| *w = composer.append_witness(*m as u64));
+
+            let mut state = [C::ZERO; 8];
+            state
+                .iter_mut()
+                .zip(sha256::IV.iter())
+                .for_each(|(w, iv)| *w = composer.append_constant(*iv as u64));
+
+            for block in composer.component_sha256_pad(&message) {
+                state = composer.component_sha256(&state, &block);
+            }
+
+            state.iter().zip(self.digest.iter()).for_each(|(w, d)| {
+                composer.assert_equal_constant(*w, 0, Some((*d as u64).into()))
+            });
+
+            Ok(())
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_sha256";
+    let mut rng = StdRng::seed_from_u64(0x5ba256);
+    let capacity = 1 << 16;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    // Test:
+    // the digest matches the native reference
+    let msg = "Verification of a satisfied circuit should pass";
+    let bytes = b"dusk-network";
+    let mut message = [0u32; WORDS];
+    message
+        .iter_mut()
+        .zip(bytes.chunks(4))
+        .for_each(|(w, b)| *w = u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    let digest = sha256::digest(bytes);
+    let pi: Vec<BlsScalar> =
+        digest.iter().map(|d| BlsScalar::from(*d as u64)).collect();
+    let circuit = TestCircuit::new(message, digest);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // the digest of a different message
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let mut wrong_message = message;
+    wrong_message[0] ^= 1;
+    let circuit = TestCircuit::new(wrong_message, digest);
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,199 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+use dusk_plonk::prelude::*;
+use dusk_plonk::signature::{
+    PublicKey, SecretKey, Signature, WitnessSignature,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod common;
+use common::{check_satisfied_circuit, check_unsatisfied_circuit};
+
+const MESSAGE_LEN: usize = 2;
+
+pub struct TestCircuit {
+    public_key: PublicKey,
+    message: [BlsScalar; MESSAGE_LEN],
+    signature: Signature,
+}
+
+impl TestCircuit {
+    pub fn new(
+        public_key: PublicKey,
+        message: [BlsScalar; MESSAGE_LEN],
+        signature: Signature,
+    ) -> Self {
+        Self {
+            public_key,
+            message,
+            signature,
+        }
+    }
+}
+
+impl Default for TestCircuit {
+    fn default() -> Self {
+        let sk = SecretKey::new(JubJubScalar::one());
+        let message = [BlsScalar::zero(); MESSAGE_LEN];
+        let signature = sk.sign_eddsa(&message);
+
+        Self::new(sk.public_key(), message, signature)
+    }
+}
+
+// The public key is a public input, so the proof is bound to it
+fn public_key_pi(public_key: &PublicKey) -> Vec<BlsScalar> {
+    let affine = JubJubAffine::from(public_key.point());
+    vec![affine.get_u(), affine.get_v()]
+}
+
+#[test]
+fn verify_schnorr() {
+    #[derive(Default)]
+    pub struct SchnorrCircuit(TestCircuit);
+
+    impl Circuit for SchnorrCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let public_key =
+                composer.append_public_point(*self.0.public_key.point());
+
+            let mut message = [C::ZERO; MESSAGE_LEN];
+            message
+                .iter_mut()
+                .zip(self.0.message.iter())
+                .for_each(|(w, m)| *w = composer.append_witness(*m));
+
+            let signature =
+                WitnessSignature::append(composer, &self.0.signature);
+
+            composer.component_verify_schnorr(public_key, &message, signature)
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_verify_schnorr";
+    let mut rng = StdRng::seed_from_u64(0x5c4);
+    let capacity = 1 << 13;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<SchnorrCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let sk = SecretKey::random(&mut rng);
+    let pk = sk.public_key();
+    let message = [BlsScalar::from(0xdead), BlsScalar::from(0xbeef)];
+    let signature = sk.sign_schnorr(&mut rng, &message);
+    assert!(pk.verify_schnorr(&signature, &message));
+
+    // Test:
+    // valid signature
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = SchnorrCircuit(TestCircuit::new(pk, message, signature));
+    let pi = public_key_pi(&pk);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // signature of a different message
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let other = [BlsScalar::from(0xdead), BlsScalar::from(0xbeee)];
+    let circuit = SchnorrCircuit(TestCircuit::new(pk, other, signature));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // signature of a different key
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let other_pk = SecretKey::random(&mut rng).public_key();
+    let circuit =
+        SchnorrCircuit(TestCircuit::new(other_pk, message, signature));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // an EdDSA signature is not a Schnorr signature
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let signature = sk.sign_eddsa(&message);
+    let circuit = SchnorrCircuit(TestCircuit::new(pk, message, signature));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
+
+#[test]
+fn verify_eddsa() {
+    #[derive(Default)]
+    pub struct EddsaCircuit(TestCircuit);
+
+    impl Circuit for EddsaCircuit {
+        fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+        where
+            C: Composer,
+        {
+            let public_key =
+                composer.append_public_point(*self.0.public_key.point());
+
+            let mut message = [C::ZERO; MESSAGE_LEN];
+            message
+                .iter_mut()
+                .zip(self.0.message.iter())
+                .for_each(|(w, m)| *w = composer.append_witness(*m));
+
+            let signature =
+                WitnessSignature::append(composer, &self.0.signature);
+
+            composer.component_verify_eddsa(public_key, &message, signature)
+        }
+    }
+
+    // Compile common circuit descriptions for the prover and verifier to be
+    // used by all tests
+    let label = b"component_verify_eddsa";
+    let mut rng = StdRng::seed_from_u64(0xedd5a);
+    let capacity = 1 << 13;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+    let (prover, verifier) = Compiler::compile::<EddsaCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let sk = SecretKey::random(&mut rng);
+    let pk = sk.public_key();
+    let message = [BlsScalar::from(0xdead), BlsScalar::from(0xbeef)];
+    let signature = sk.sign_eddsa(&message);
+    assert!(pk.verify_eddsa(&signature, &message));
+
+    // Test:
+    // valid signature
+    let msg = "Verification of a satisfied circuit should pass";
+    let circuit = EddsaCircuit(TestCircuit::new(pk, message, signature));
+    let pi = public_key_pi(&pk);
+    check_satisfied_circuit(&prover, &verifier, &pi, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // signature of a different message
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let other = [BlsScalar::from(0xdead), BlsScalar::from(0xbeee)];
+    let circuit = EddsaCircuit(TestCircuit::new(pk, other, signature));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // forged signature with a different scalar
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let forged =
+        Signature::new(*signature.r(), signature.s() + JubJubScalar::one());
+    let circuit = EddsaCircuit(TestCircuit::new(pk, message, forged));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+
+    // Test fails:
+    // forged signature with a different point
+    let msg = "Proof creation of an unsatisfied circuit should fail";
+    let forged =
+        Signature::new(signature.r() + GENERATOR_EXTENDED, *signature.s());
+    let circuit = EddsaCircuit(TestCircuit::new(pk, message, forged));
+    check_unsatisfied_circuit(&prover, &circuit, &mut rng, &msg);
+}
@@ -0,0 +1,624 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use dusk_bls12_381::G1Affine;
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_plonk::keccak;
+use dusk_plonk::prelude::*;
+use dusk_plonk::transcript::{Keccak, TranscriptProtocol};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const POINT: usize = 128;
+const EVALUATIONS: usize = 11 * POINT;
+const PROOF_SIZE: usize = EVALUATIONS + 16 * 32;
+const LOOKUP_EVALUATIONS: usize = 15 * POINT;
+const LOOKUP_PROOF_SIZE: usize = LOOKUP_EVALUATIONS + 23 * 32;
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_c = composer.gate_mul(constraint);
+
+        composer.assert_equal_constant(w_c, 0, Some(self.a * self.b));
+
+        Ok(())
+    }
+}
+
+// Mirror of the `a · b = c` gate, as a custom gate
+struct Mul;
+
+impl CustomGate for Mul {
+    const SELECTOR: &'static [u8] = b"q_mul";
+
+    fn constraint(w: &GateWires) -> BlsScalar {
+        w.a * w.b - w.c
+    }
+}
+
+#[derive(Default)]
+pub struct CustomCircuit;
+
+impl Circuit for CustomCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(BlsScalar::from(2));
+        let w_b = composer.append_witness(BlsScalar::from(3));
+        let w_c = composer.append_witness(BlsScalar::from(6));
+
+        composer
+            .append_widget::<Mul>(Constraint::new().a(w_a).b(w_b).o(w_c))?;
+
+        Ok(())
+    }
+}
+
+// Circuit with public inputs spread over its gates
+#[derive(Default)]
+pub struct PublicInputsCircuit {
+    inputs: [BlsScalar; 3],
+}
+
+impl Circuit for PublicInputsCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        self.inputs.iter().try_for_each(|input| {
+            let w_input = composer.append_witness(*input);
+            let w_square = composer
+                .gate_mul(Constraint::new().mult(1).a(w_input).b(w_input));
+            composer.component_range::<8>(w_square);
+
+            composer.assert_equal_constant(w_input, 0, Some(*input));
+
+            Ok(())
+        })
+    }
+}
+
+// Circuit with public inputs looked up in a table of squares
+#[derive(Default)]
+pub struct LookupCircuit {
+    inputs: [BlsScalar; 2],
+}
+
+impl Circuit for LookupCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let squares: Vec<_> = (0..16u64)
+            .map(|a| [a.into(), (a * a).into(), BlsScalar::zero()])
+            .collect();
+        composer.append_lookup_table(1, &squares);
+
+        self.inputs.iter().try_for_each(|input| {
+            let w_input = composer.append_witness(*input);
+            let w_square = composer
+                .gate_mul(Constraint::new().mult(1).a(w_input).b(w_input));
+            composer.component_lookup(1, w_input, w_square, C::ZERO);
+
+            composer.assert_equal_constant(w_input, 0, Some(*input));
+
+            Ok(())
+        })
+    }
+}
+
+// Challenges derived by the Rust verifier, along with the evaluation of the
+// quotient polynomial it appends to the transcript
+static TRANSCRIPT: Mutex<Vec<(&[u8], BlsScalar)>> = Mutex::new(Vec::new());
+
+// Keccak transcript recording the challenges and the quotient evaluation
+#[derive(Clone)]
+struct Recorder(Keccak);
+
+impl TranscriptProtocol for Recorder {
+    fn new(label: &[u8]) -> Self {
+        Self(Keccak::new(label))
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.0.append_message(label, message);
+    }
+
+    fn append_point(&mut self, label: &'static [u8], point: &G1Affine) {
+        self.0.append_point(label, point);
+    }
+
+    fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        if label == b"t_eval" {
+            TRANSCRIPT.lock().unwrap().push((label, *s));
+        }
+        self.0.append_scalar(label, s);
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> BlsScalar {
+        let challenge = self.0.challenge_scalar(label);
+        TRANSCRIPT.lock().unwrap().push((label, challenge));
+        challenge
+    }
+}
+
+// Value of the constant `name` declared by the contract
+fn constant<'a>(source: &'a str, name: &str) -> &'a str {
+    let declaration = format!(" constant {name} = ");
+    let start = source.find(&declaration).expect("Constant should exist")
+        + declaration.len();
+    let end = start + source[start..].find(';').unwrap();
+
+    &source[start..end]
+}
+
+// Big endian bytes of an hexadecimal literal of the contract
+fn literal(hex: &str) -> Vec<u8> {
+    let hex = hex.trim().trim_start_matches("0x");
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+// `uint256(bytes) % R`, for 32 big endian bytes
+fn uint(bytes: &[u8]) -> BlsScalar {
+    let mut wide = [0u8; 64];
+    wide[..32].copy_from_slice(bytes);
+    wide[..32].reverse();
+    BlsScalar::from_bytes_wide(&wide)
+}
+
+// Replay of the transcript and of the quotient evaluation of the contract over
+// the calldata of a proof, as done by `_challenges`, `_quotientEvaluation` and
+// `_openingChallenges`, with the constants embedded in the contract
+fn replay_contract(
+    source: &str,
+    calldata: &[u8],
+) -> Vec<(&'static [u8], BlsScalar)> {
+    let r_256 = uint(&literal(constant(source, "R_256")));
+    let n: u64 = constant(source, "N").parse().unwrap();
+    let n_inv = uint(&literal(constant(source, "N_INV")));
+    let mut state: [u8; 32] =
+        literal(constant(source, "TRANSCRIPT")).try_into().unwrap();
+    let lookup: bool = constant(source, "LOOKUP").parse().unwrap();
+    let points: usize = constant(source, "POINTS").parse().unwrap();
+    let scalars: usize = constant(source, "SCALARS").parse().unwrap();
+    let evaluations = points * POINT;
+    let proof_size = evaluations + scalars * 32;
+
+    let args = &calldata[4..];
+    let proof = &args[96..96 + proof_size];
+    let public_inputs: Vec<_> = args[128 + proof_size..]
+        .chunks_exact(32)
+        .map(uint)
+        .collect();
+    let e: Vec<_> = proof[evaluations..].chunks_exact(32).map(uint).collect();
+
+    let transcript = RefCell::new(Vec::new());
+
+    let append = |state: &mut [u8; 32], bytes: &[u8]| {
+        *state = keccak256(&[&state[..], bytes].concat());
+    };
+    let point = |i: usize| &proof[i * POINT..(i + 1) * POINT];
+    let challenge = |state: &mut [u8; 32], label: &'static [u8]| {
+        let next = keccak256(&[&state[..], label].concat());
+        let low = keccak256(&next);
+        *state = next;
+
+        let c = uint(&next) * r_256 + uint(&low);
+        transcript.borrow_mut().push((label, c));
+        c
+    };
+
+    // `_challenges`
+    public_inputs
+        .iter()
+        .for_each(|pi| append(&mut state, &be_scalar(pi)));
+    (0..4).for_each(|i| append(&mut state, point(i)));
+
+    if lookup {
+        let zeta = challenge(&mut state, b"zeta");
+        append(&mut state, &be_scalar(&zeta));
+        (11..14).for_each(|i| append(&mut state, point(i)));
+    }
+
+    let beta = challenge(&mut state, b"beta");
+    append(&mut state, &be_scalar(&beta));
+    let gamma = challenge(&mut state, b"gamma");
+    let delta_epsilon = lookup.then(|| {
+        let delta = challenge(&mut state, b"delta");
+        append(&mut state, &be_scalar(&delta));
+        let epsilon = challenge(&mut state, b"epsilon");
+        (delta, epsilon)
+    });
+    append(&mut state, point(4));
+    if lookup {
+        append(&mut state, point(14));
+    }
+
+    let alpha = challenge(&mut state, b"alpha");
+    challenge(&mut state, b"range separation challenge");
+    challenge(&mut state, b"logic separation challenge");
+    challenge(&mut state, b"fixed base separation challenge");
+    challenge(&mut state, b"variable base separation challenge");
+    let lookup_sep =
+        lookup.then(|| challenge(&mut state, b"lookup separation challenge"));
+    (5..9).for_each(|i| append(&mut state, point(i)));
+    let z = challenge(&mut state, b"z_challenge");
+
+    // `_publicInputs`, summing the terms generated for every public input
+    let bases = source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("_inverse(_sub(mulmod("))
+        .map(|line| uint(&literal(&line[..line.find(',').unwrap()])));
+    let sum: BlsScalar = public_inputs
+        .iter()
+        .zip(bases)
+        .map(|(pi, basis)| {
+            pi * (basis * z - BlsScalar::one()).invert().unwrap()
+        })
+        .sum();
+
+    // `_quotientEvaluation`
+    let z_h = z.pow(&[n, 0, 0, 0]) - BlsScalar::one();
+    let l1 = z_h
+        * (BlsScalar::from(n) * (z - BlsScalar::one()))
+            .invert()
+            .unwrap();
+
+    let mut t = z_h * n_inv * sum + e[15];
+
+    let sigma_product =
+        (0..3).fold(alpha, |p, i| p * (e[i] + beta * e[7 + i] + gamma));
+    t -= sigma_product * (e[3] + gamma) * e[14];
+    t -= l1 * alpha.square();
+
+    if let Some(((delta, epsilon), lookup_sep)) = delta_epsilon.zip(lookup_sep)
+    {
+        let kappa = lookup_sep.square();
+        let epsilon_one_plus_delta = epsilon * (BlsScalar::one() + delta);
+        let d_0 = (epsilon_one_plus_delta + e[17] + delta * e[19])
+            * e[22]
+            * (epsilon_one_plus_delta + e[19] + delta * e[18]);
+        t -= (l1 + d_0 * kappa) * kappa * lookup_sep;
+    }
+
+    let t = t * z_h.invert().unwrap();
+    transcript.borrow_mut().push((b"t_eval", t));
+
+    // `_openingChallenges`
+    e[..15]
+        .iter()
+        .chain(&e[16..])
+        .for_each(|eval| append(&mut state, &be_scalar(eval)));
+    append(&mut state, &be_scalar(&t));
+    append(&mut state, &be_scalar(&e[15]));
+
+    challenge(&mut state, b"v_challenge");
+    challenge(&mut state, b"v_challenge");
+    append(&mut state, point(9));
+    append(&mut state, point(10));
+    challenge(&mut state, b"batch");
+
+    transcript.into_inner()
+}
+
+fn word(bytes: &[u8]) -> usize {
+    assert!(bytes[..24].iter().all(|b| *b == 0));
+
+    let mut be = [0u8; 8];
+    be.copy_from_slice(&bytes[24..32]);
+    u64::from_be_bytes(be) as usize
+}
+
+fn keccak256(message: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    digest
+        .chunks_exact_mut(8)
+        .zip(keccak::digest(message).iter())
+        .for_each(|(bytes, lane)| bytes.copy_from_slice(&lane.to_le_bytes()));
+    digest
+}
+
+fn be_scalar(s: &BlsScalar) -> [u8; 32] {
+    let mut bytes = s.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Check the encoding of the proof in the calldata, `evaluations` being the
+// offset of its evaluations and `proof_size` its size
+fn check_calldata(
+    proof: &Proof,
+    pi: &[BlsScalar],
+    evaluations: usize,
+    proof_size: usize,
+) {
+    let calldata = proof.to_calldata(pi);
+    assert_eq!(
+        calldata.len(),
+        4 + 32 * 3 + proof_size + 32 * (1 + pi.len())
+    );
+
+    // selector and head of `verify(bytes,uint256[])`
+    assert_eq!(calldata[..4], keccak256(b"verify(bytes,uint256[])")[..4]);
+    let args = &calldata[4..];
+    assert_eq!(word(&args[..32]), 64);
+    assert_eq!(word(&args[32..64]), 96 + proof_size);
+
+    // proof
+    assert_eq!(word(&args[64..96]), proof_size);
+    let evm_proof = &args[96..96 + proof_size];
+
+    // public inputs
+    let public_inputs = &args[96 + proof_size..];
+    assert_eq!(word(&public_inputs[..32]), pi.len());
+    public_inputs[32..]
+        .chunks_exact(32)
+        .zip(pi)
+        .for_each(|(encoded, pi)| assert_eq!(encoded, be_scalar(pi)));
+
+    // the commitments are the uncompressed points, padded to 64 bytes per
+    // coordinate, and are followed by the evaluations, with the ones of the
+    // lookup argument last in both the serialized and the encoded proof
+    let bytes = proof.to_var_bytes();
+    let (base, lookup) = bytes.split_at(Proof::SIZE);
+    let (lookup_commitments, lookup_evaluations) =
+        lookup.split_at(lookup.len().min(4 * G1Affine::SIZE));
+    let (commitments, base_evaluations) = base.split_at(11 * G1Affine::SIZE);
+
+    commitments
+        .chunks_exact(G1Affine::SIZE)
+        .chain(lookup_commitments.chunks_exact(G1Affine::SIZE))
+        .zip(evm_proof[..evaluations].chunks_exact(POINT))
+        .for_each(|(compressed, encoded)| {
+            let point = G1Affine::from_slice(compressed)
+                .expect("Commitment should deserialize");
+            let mut uncompressed = point.to_uncompressed();
+            uncompressed[0] &= 0x1f;
+
+            assert!(encoded[..16].iter().all(|b| *b == 0));
+            assert_eq!(encoded[16..64], uncompressed[..48]);
+            assert!(encoded[64..80].iter().all(|b| *b == 0));
+            assert_eq!(encoded[80..], uncompressed[48..]);
+        });
+
+    // the evaluations are in the order of the transcript, the linearization
+    // polynomial following the ones of the permutation argument
+    let serialized: Vec<_> = base_evaluations
+        .chunks_exact(BlsScalar::SIZE)
+        .chain(lookup_evaluations.chunks_exact(BlsScalar::SIZE))
+        .map(|s| BlsScalar::from_slice(s).expect("Scalar should deserialize"))
+        .collect();
+    let encoded: Vec<_> = evm_proof[evaluations..]
+        .chunks_exact(32)
+        .map(|s| {
+            let mut le = [0u8; 32];
+            le.copy_from_slice(s);
+            le.reverse();
+            BlsScalar::from_bytes(&le).expect("Scalar should be canonical")
+        })
+        .collect();
+
+    // positions of the serialized evaluations in the calldata
+    let order = [
+        0, 1, 2, 3, 4, 5, 6, 10, 11, 12, 13, 7, 8, 9, 15, 14, 16, 17, 18, 19,
+        20, 21, 22,
+    ];
+    assert_eq!(encoded.len(), serialized.len());
+    serialized
+        .iter()
+        .zip(order)
+        .for_each(|(eval, i)| assert_eq!(eval, &encoded[i]));
+}
+
+#[test]
+fn calldata() {
+    let mut rng = StdRng::seed_from_u64(0x501);
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let label = b"solidity";
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let prover = prover.with_transcript::<Keccak>();
+    let verifier = verifier.with_transcript::<Keccak>();
+
+    let circuit = TestCircuit::new(BlsScalar::from(3), BlsScalar::from(7));
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+
+    assert_eq!(pi, vec![BlsScalar::from(21)]);
+    check_calldata(&proof, &pi, EVALUATIONS, PROOF_SIZE);
+
+    // the proofs of circuits with lookups carry the lookup argument
+    let (prover, verifier) = Compiler::compile::<LookupCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let prover = prover.with_transcript::<Keccak>();
+    let verifier = verifier.with_transcript::<Keccak>();
+
+    let circuit = LookupCircuit {
+        inputs: [3, 5].map(BlsScalar::from),
+    };
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+
+    assert_eq!(pi.len(), 2);
+    check_calldata(&proof, &pi, LOOKUP_EVALUATIONS, LOOKUP_PROOF_SIZE);
+}
+
+#[test]
+fn contract() {
+    let mut rng = StdRng::seed_from_u64(0x502);
+    let capacity = 1 << 5;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let label = b"solidity";
+    let (_, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let verifier = verifier.with_transcript::<Keccak>();
+
+    let source = verifier
+        .to_solidity()
+        .expect("Verifier without custom gates should be exported");
+
+    assert!(source.contains("contract PlonkVerifier"));
+    assert!(!source.contains("{{"));
+    assert!(source.contains("uint256 internal constant PUBLIC_INPUTS = 1;"));
+    assert!(source.contains("publicInputs[0]"));
+    assert!(!source.contains("publicInputs[1]"));
+    assert_eq!(constant(&source, "LOOKUP"), "false");
+    assert_eq!(constant(&source, "POINTS"), "11");
+    assert_eq!(constant(&source, "SCALARS"), "16");
+    assert_eq!(constant(&source, "MSM_SIZE"), "26");
+    assert!(!source.contains("// q_lookup"));
+
+    // the generator of G1 of the opening key, serialized first by the public
+    // parameters, is embedded as is
+    let g = G1Affine::from_slice(&pp.to_var_bytes()[..G1Affine::SIZE])
+        .expect("Generator should deserialize")
+        .to_uncompressed();
+    assert!(source.contains(&hex(&g[..16])));
+    assert!(source.contains(&hex(&g[16..48])));
+
+    // the contract is bound to the circuit, its label and its transcript
+    let (_, other) = Compiler::compile::<TestCircuit>(&pp, b"other")
+        .expect("Circuit should compile");
+    let other = other
+        .with_transcript::<Keccak>()
+        .to_solidity()
+        .expect("Verifier without custom gates should be exported");
+    assert_ne!(source, other);
+
+    let regenerated = Verifier::try_from_bytes(verifier.to_bytes())
+        .expect("Verifier should deserialize")
+        .with_transcript::<Keccak>()
+        .to_solidity()
+        .expect("Verifier without custom gates should be exported");
+    assert_eq!(source, regenerated);
+
+    // the contracts of circuits with lookups embed the lookup part of the
+    // verifier key
+    let (_, verifier) = Compiler::compile::<LookupCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let source = verifier
+        .with_transcript::<Keccak>()
+        .to_solidity()
+        .expect("Verifier without custom gates should be exported");
+    assert_eq!(constant(&source, "LOOKUP"), "true");
+    assert_eq!(constant(&source, "POINTS"), "15");
+    assert_eq!(constant(&source, "SCALARS"), "23");
+    assert_eq!(constant(&source, "MSM_SIZE"), "35");
+    assert!(source.contains("// q_lookup"));
+
+    // custom gates can't be exported
+    let (_, verifier) = Compiler::compile::<CustomCircuit>(&pp, label)
+        .expect("Circuit should compile");
+    let result = verifier.with_transcript::<Keccak>().to_solidity();
+    assert!(matches!(result, Err(Error::CustomGatesNotSerializable)));
+}
+
+// Check the transcript of the contract against the one of the Rust verifier,
+// which derives `challenges` challenges
+fn check_transcript_vectors<C>(
+    circuit: &C,
+    capacity: usize,
+    challenges: usize,
+    seed: u64,
+) where
+    C: Circuit,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let label = b"solidity";
+    let (prover, verifier) =
+        Compiler::compile::<C>(&pp, label).expect("Circuit should compile");
+    let prover = prover.with_transcript::<Keccak>();
+    let source = Verifier::try_from_bytes(verifier.to_bytes())
+        .expect("Verifier should deserialize")
+        .with_transcript::<Keccak>()
+        .to_solidity()
+        .expect("Verifier without custom gates should be exported");
+    let verifier = verifier.with_transcript::<Recorder>();
+
+    let (proof, pi) = prover
+        .prove(&mut rng, circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    TRANSCRIPT.lock().unwrap().clear();
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+    let rust = TRANSCRIPT.lock().unwrap().clone();
+
+    // the contract derives the challenges of the Rust verifier from the
+    // calldata, and evaluates the public inputs to the same quotient
+    // evaluation, which the Rust verifier appends to the transcript
+    let contract = replay_contract(&source, &proof.to_calldata(&pi));
+    assert_eq!(rust.len(), challenges);
+    assert_eq!(contract, rust);
+
+    // other public inputs result in other challenges
+    let mut other = pi.clone();
+    other[pi.len() - 1] += BlsScalar::one();
+    let contract = replay_contract(&source, &proof.to_calldata(&other));
+    assert_ne!(contract[0], rust[0]);
+}
+
+#[test]
+fn transcript_vectors() {
+    let circuit = PublicInputsCircuit {
+        inputs: [3, 5, 11].map(BlsScalar::from),
+    };
+    check_transcript_vectors(&circuit, 1 << 7, 12, 0x503);
+
+    // the lookup argument adds the challenges zeta, delta, epsilon and the
+    // lookup separation challenge
+    let circuit = LookupCircuit {
+        inputs: [3, 5].map(BlsScalar::from),
+    };
+    check_transcript_vectors(&circuit, 1 << 6, 16, 0x504);
+}
@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use dusk_plonk::transcript::Keccak;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_c = composer.gate_mul(constraint);
+
+        composer.component_range::<64>(w_c);
+        composer.assert_equal_constant(w_c, 0, Some(self.a * self.b));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn capped_threads() {
+    let mut rng = StdRng::seed_from_u64(0x7ead);
+    let capacity = 1 << 6;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    let label = b"threads";
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(&pp, label)
+        .expect("Circuit should compile");
+
+    let circuit = TestCircuit::new(BlsScalar::from(3), BlsScalar::from(7));
+    let prove = |prover: &Prover| {
+        let mut rng = StdRng::seed_from_u64(0xba5e);
+        prover
+            .prove(&mut rng, &circuit)
+            .expect("Prover for valid circuit shouldn't fail")
+    };
+
+    let (proof, pi) = prove(&prover);
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+
+    // a prover needs at least a thread
+    assert_eq!(
+        prover.clone().with_threads(0).err(),
+        Some(Error::InvalidThreadCount)
+    );
+
+    // the concurrent steps of a proof don't depend on the threads they run on
+    [1, 2, 3].into_iter().for_each(|threads| {
+        let capped = prover
+            .clone()
+            .with_threads(threads)
+            .expect("The threads of the prover should spawn");
+        let (capped_proof, capped_pi) = prove(&capped);
+
+        assert_eq!(capped_proof.to_var_bytes(), proof.to_var_bytes());
+        assert_eq!(capped_pi, pi);
+    });
+
+    // a capped prover can switch its transcript
+    let prover = prover
+        .with_threads(1)
+        .expect("The threads of the prover should spawn")
+        .with_transcript::<Keccak>();
+    let verifier = verifier.with_transcript::<Keccak>();
+
+    let (proof, pi) = prover
+        .prove(&mut rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification of a satisfied circuit should pass");
+}
@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::*;
+use dusk_plonk::transcript::{
+    Blake2b, Hades, Keccak, Merlin, TranscriptProtocol,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Default)]
+pub struct TestCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+}
+
+impl TestCircuit {
+    pub fn new(a: BlsScalar, b: BlsScalar) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Circuit for TestCircuit {
+    fn circuit<C>(&self, composer: &mut C) -> Result<(), Error>
+    where
+        C: Composer,
+    {
+        let w_a = composer.append_witness(self.a);
+        let w_b = composer.append_witness(self.b);
+
+        let constraint = Constraint::new().mult(1).a(w_a).b(w_b);
+        let w_c = composer.gate_mul(constraint);
+
+        composer.assert_equal_constant(w_c, 0, Some(self.a * self.b));
+
+        Ok(())
+    }
+}
+
+// Prove and verify with the transcript `T`, and check the proof doesn't
+// verify with the default transcript
+fn check_transcript<T: TranscriptProtocol>(
+    pp: &PublicParameters,
+    rng: &mut StdRng,
+) {
+    let label = b"transcript";
+    let (prover, verifier) = Compiler::compile::<TestCircuit>(pp, label)
+        .expect("Circuit should compile");
+    let default_verifier = Verifier::try_from_bytes(verifier.to_bytes())
+        .expect("Verifier should deserialize");
+
+    let prover = prover.with_transcript::<T>();
+    let verifier = verifier.with_transcript::<T>();
+
+    let circuit = TestCircuit::new(BlsScalar::from(3), BlsScalar::from(7));
+    let (proof, pi) = prover
+        .prove(rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification with the same transcript should pass");
+
+    default_verifier
+        .verify(&proof, &pi)
+        .expect_err("Verification with another transcript should fail");
+
+    // the transcript of a verifier can be switched back
+    let verifier = verifier.with_transcript::<Merlin>();
+    let (proof, pi) = Prover::try_from_bytes(prover.to_bytes())
+        .expect("Prover should deserialize")
+        .prove(rng, &circuit)
+        .expect("Prover for valid circuit shouldn't fail");
+    verifier
+        .verify(&proof, &pi)
+        .expect("Verification with the same transcript should pass");
+}
+
+#[test]
+fn transcripts() {
+    let mut rng = StdRng::seed_from_u64(0x7a5);
+    let capacity = 1 << 4;
+    let pp = PublicParameters::setup(capacity, &mut rng)
+        .expect("Creation of public parameter shouldn't fail");
+
+    check_transcript::<Keccak>(&pp, &mut rng);
+    check_transcript::<Hades>(&pp, &mut rng);
+    check_transcript::<Blake2b>(&pp, &mut rng);
+}